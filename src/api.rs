@@ -1,10 +1,10 @@
 use crate::{
     executor::{default_executor, proxy_executor, Executor},
-    methods::Method,
-    types::Response,
+    methods::{Method, Request, SendMessage},
+    types::{ChatId, Integer, Message, ParseMode, Response, Text, MAX_TEXT_LENGTH},
 };
 use failure::Error;
-use futures::{future, Future, Poll};
+use futures::{future, stream, Future, Poll, Stream};
 use serde::de::DeserializeOwned;
 use std::{fmt::Debug, sync::Arc};
 
@@ -51,15 +51,75 @@ impl Api {
         let executor = self.executor.clone();
         ApiFuture {
             inner: Box::new(
-                future::result(method.get_request().map(|builder| builder.build(&self.token)))
-                    .and_then(move |req| executor.execute(req).from_err())
-                    .and_then(|data| future::result(serde_json::from_slice::<Response<M::Response>>(&data)).from_err())
-                    .and_then(|rep| {
-                        future::result(match rep {
-                            Response::Success(obj) => Ok(obj),
-                            Response::Error(err) => Err(err.into()),
-                        })
-                    }),
+                future::result(
+                    method
+                        .validate()
+                        .map_err(Error::from)
+                        .and_then(|()| method.get_request())
+                        .map(|builder| builder.build(&self.token)),
+                )
+                .and_then(move |req| executor.execute(req).from_err())
+                .and_then(|data| future::result(serde_json::from_slice::<Response<M::Response>>(&data)).from_err())
+                .and_then(|rep| {
+                    future::result(match rep {
+                        Response::Success(obj) => Ok(obj),
+                        Response::Error(err) => Err(err.into()),
+                    })
+                }),
+            ),
+        }
+    }
+
+    /// Downloads a file
+    ///
+    /// Fails when the server responds with a non-2xx status
+    ///
+    /// # Arguments
+    ///
+    /// * file_path - Path of the file from File returned by GetFile
+    pub fn download_file<P: AsRef<str>>(&self, file_path: P) -> ApiFuture<Vec<u8>> {
+        ApiFuture::new(
+            self.executor
+                .execute(Request::download(&self.token, file_path.as_ref())),
+        )
+    }
+
+    /// Sends a long text as several messages
+    ///
+    /// Text is split using Text::split() and each part is sent with HTML parse mode
+    /// Parts are sent in order, each one after the previous is delivered
+    /// Nothing is sent for an empty text
+    ///
+    /// # Arguments
+    ///
+    /// * chat_id - Unique identifier for the target chat
+    /// * text - Text to send
+    /// * reply_to_message_id - If set, every part is sent as a reply to this message
+    pub fn send_long_text<C: Into<ChatId>>(
+        &self,
+        chat_id: C,
+        text: &Text,
+        reply_to_message_id: Option<Integer>,
+    ) -> ApiFuture<Vec<Message>> {
+        let chat_id = chat_id.into();
+        let methods: Vec<SendMessage> = text
+            .split(MAX_TEXT_LENGTH)
+            .iter()
+            .filter(|part| !part.data.is_empty())
+            .map(|part| {
+                let method = SendMessage::new(chat_id.clone(), part.to_html()).parse_mode(ParseMode::Html);
+                match reply_to_message_id {
+                    Some(message_id) => method.reply_to_message_id(message_id),
+                    None => method,
+                }
+            })
+            .collect();
+        let api = self.clone();
+        ApiFuture {
+            inner: Box::new(
+                stream::iter_ok(methods)
+                    .and_then(move |method| api.execute(&method))
+                    .collect(),
             ),
         }
     }
@@ -84,6 +144,15 @@ pub struct ApiFuture<T> {
     inner: Box<Future<Item = T, Error = Error> + Send>,
 }
 
+impl<T> ApiFuture<T> {
+    pub(crate) fn new<F>(inner: F) -> Self
+    where
+        F: Future<Item = T, Error = Error> + Send + 'static,
+    {
+        ApiFuture { inner: Box::new(inner) }
+    }
+}
+
 impl<T> Future for ApiFuture<T> {
     type Item = T;
     type Error = Error;
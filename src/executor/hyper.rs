@@ -6,12 +6,13 @@ use failure::Error;
 use futures::{future, Future, Stream};
 use hyper::{
     client::{connect::Connect, Client, HttpConnector},
-    Body, Request as HttpRequest,
+    Body, Request as HttpRequest, StatusCode,
 };
 use hyper_proxy::{Intercept as HttpProxyIntercept, Proxy as HttpProxy, ProxyConnector as HttpProxyConnector};
 use hyper_socks2::{Auth as SocksAuth, Proxy as SocksProxy};
 use hyper_tls::HttpsConnector;
 use log::{debug, log_enabled, Level::Debug};
+use openssl::rand::rand_bytes;
 use std::{net::SocketAddr, sync::Arc};
 use typed_headers::Credentials as HttpProxyCredentials;
 use url::{percent_encoding::percent_decode, Url};
@@ -37,6 +38,7 @@ impl<C: Connect + 'static> Executor for HyperExecutor<C> {
             RequestMethod::Post => HttpRequest::post(req.url),
         };
         let client = self.client.clone();
+        let check_status = req.check_status;
         Box::new(
             future::result(match req.body {
                 RequestBody::Json(data) => {
@@ -46,30 +48,49 @@ impl<C: Connect + 'static> Executor for HyperExecutor<C> {
                     builder.header("Content-Type", "application/json");
                     builder.body(data.into())
                 }
+                RequestBody::Form(form) => {
+                    let boundary = match form_boundary() {
+                        Ok(boundary) => boundary,
+                        Err(err) => return Box::new(future::err(err)),
+                    };
+                    builder.header("Content-Type", format!("multipart/form-data; boundary={}", boundary));
+                    builder.body(form.encode(&boundary).into())
+                }
                 RequestBody::Empty => builder.body(Body::empty()),
             })
             .map_err(Error::from)
             .and_then(move |http_req| client.request(http_req).map_err(Error::from))
-            .and_then(|rep| {
-                Stream::fold(
-                    rep.into_body().from_err(),
-                    Vec::new(),
-                    |mut out, chunk| -> Result<Vec<u8>, Error> {
-                        out.extend_from_slice(&chunk);
-                        Ok(out)
-                    },
+            .and_then(move |rep| {
+                if check_status && !rep.status().is_success() {
+                    return future::Either::A(future::err(UnexpectedStatusError(rep.status()).into()));
+                }
+                future::Either::B(
+                    Stream::fold(
+                        rep.into_body().from_err(),
+                        Vec::new(),
+                        |mut out, chunk| -> Result<Vec<u8>, Error> {
+                            out.extend_from_slice(&chunk);
+                            Ok(out)
+                        },
+                    )
+                    .and_then(|body| {
+                        if log_enabled!(Debug) {
+                            debug!("Got response: {}", String::from_utf8_lossy(&body));
+                        }
+                        Ok(body)
+                    }),
                 )
-                .and_then(|body| {
-                    if log_enabled!(Debug) {
-                        debug!("Got response: {}", String::from_utf8_lossy(&body));
-                    }
-                    Ok(body)
-                })
             }),
         )
     }
 }
 
+fn form_boundary() -> Result<String, Error> {
+    let mut buf = [0; 16];
+    rand_bytes(&mut buf)?;
+    Ok(buf.iter().map(|x| format!("{:02x}", x)).collect())
+}
+
 fn https_connector() -> Result<HttpsConnector<HttpConnector>, Error> {
     Ok(HttpsConnector::new(DEFAULT_HTTPS_DNS_WORKER_THREADS)?)
 }
@@ -93,6 +114,10 @@ fn http_proxy_executor(proxy: HttpProxy) -> Result<Box<Executor>, Error> {
     Ok(Box::new(HyperExecutor::new(client)))
 }
 
+#[derive(Debug, failure::Fail)]
+#[fail(display = "Unexpected HTTP status: {}", _0)]
+struct UnexpectedStatusError(StatusCode);
+
 #[derive(Debug, failure::Fail)]
 #[fail(display = "Unexpected proxy: {}", _0)]
 struct UnexpectedProxyError(String);
@@ -0,0 +1,262 @@
+use crate::{
+    api::{Api, ApiFuture},
+    methods::{AnswerCallbackQuery, GetGameHighScores, SetGameScore},
+    types::{CallbackQuery, Integer},
+};
+use failure::Error;
+use futures::{future, Future};
+use openssl::{error::ErrorStack, hash::MessageDigest, memcmp, pkey::PKey, sign::Signer};
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use url::form_urlencoded;
+
+const DEFAULT_TTL: Duration = Duration::from_secs(3600);
+const UNKNOWN_GAME_TEXT: &str = "This game is not available";
+
+/// Message with a game
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum GameTarget {
+    /// Message sent by the bot
+    Message {
+        /// Chat identifier
+        chat_id: Integer,
+        /// Message identifier
+        message_id: Integer,
+    },
+    /// Message sent via the bot in inline mode
+    Inline(String),
+}
+
+/// A player who has launched a game
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GameSession {
+    /// Short name of the game
+    pub game_short_name: String,
+    /// Identifier of the player
+    pub user_id: Integer,
+    /// Message with the game
+    pub target: GameTarget,
+    /// Unix time when the launch URL expires
+    pub expires_at: Integer,
+}
+
+impl GameSession {
+    /// Returns a method which sets score of the player
+    pub fn set_score(&self, score: Integer) -> SetGameScore {
+        match self.target {
+            GameTarget::Message { chat_id, message_id } => SetGameScore::new(chat_id, message_id, self.user_id, score),
+            GameTarget::Inline(ref inline_message_id) => {
+                SetGameScore::with_inline_message_id(inline_message_id.clone(), self.user_id, score)
+            }
+        }
+    }
+
+    /// Returns a method which gets high scores of the player and his neighbors
+    pub fn get_high_scores(&self) -> GetGameHighScores {
+        match self.target {
+            GameTarget::Message { chat_id, message_id } => GetGameHighScores::new(self.user_id, chat_id, message_id),
+            GameTarget::Inline(ref inline_message_id) => {
+                GetGameHighScores::with_inline_message_id(self.user_id, inline_message_id.clone())
+            }
+        }
+    }
+
+    fn get_signed_data(&self) -> String {
+        let target = match self.target {
+            GameTarget::Message { chat_id, message_id } => format!("{}:{}", chat_id, message_id),
+            GameTarget::Inline(ref inline_message_id) => format!("inline:{}", inline_message_id),
+        };
+        format!(
+            "{}\n{}\n{}\n{}",
+            self.game_short_name, self.user_id, target, self.expires_at
+        )
+    }
+}
+
+/// Builds signed URLs which launch HTML5 games
+///
+/// A URL identifies the player and the message with the game,
+/// so the game server can verify it and submit scores without trusting the client
+#[derive(Clone, Debug)]
+pub struct GameLauncher {
+    secret: Vec<u8>,
+    games: HashMap<String, String>,
+    ttl: Duration,
+}
+
+impl GameLauncher {
+    /// Creates a new launcher
+    ///
+    /// # Arguments
+    ///
+    /// * secret - Key used to sign URLs, keep it private
+    pub fn new<S: Into<Vec<u8>>>(secret: S) -> Self {
+        GameLauncher {
+            secret: secret.into(),
+            games: HashMap::new(),
+            ttl: DEFAULT_TTL,
+        }
+    }
+
+    /// Adds a game
+    ///
+    /// # Arguments
+    ///
+    /// * short_name - Short name of the game from BotFather
+    /// * url - URL of the game, launch parameters are appended to its query
+    pub fn game<N: Into<String>, U: Into<String>>(mut self, short_name: N, url: U) -> Self {
+        self.games.insert(short_name.into(), url.into());
+        self
+    }
+
+    /// How long a launch URL is valid, defaults to 1 hour
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Creates a session for a callback query from a game button
+    ///
+    /// Returns None if the query is not a game launch request
+    pub fn create_session(&self, query: &CallbackQuery) -> Option<GameSession> {
+        let target = match (&query.message, &query.inline_message_id) {
+            (Some(message), _) => GameTarget::Message {
+                chat_id: message.get_chat_id(),
+                message_id: message.id,
+            },
+            (None, Some(inline_message_id)) => GameTarget::Inline(inline_message_id.clone()),
+            (None, None) => return None,
+        };
+        Some(GameSession {
+            game_short_name: query.game_short_name.clone()?,
+            user_id: query.from.id,
+            target,
+            expires_at: get_unix_time() + self.ttl.as_secs() as Integer,
+        })
+    }
+
+    /// Returns a signed launch URL for a session
+    pub fn get_url(&self, session: &GameSession) -> Result<String, GameUrlError> {
+        let url = self
+            .games
+            .get(&session.game_short_name)
+            .ok_or_else(|| GameUrlError::UnknownGame(session.game_short_name.clone()))?;
+        let mut query = form_urlencoded::Serializer::new(String::new());
+        query.append_pair("game", &session.game_short_name);
+        query.append_pair("user_id", &session.user_id.to_string());
+        match session.target {
+            GameTarget::Message { chat_id, message_id } => {
+                query.append_pair("chat_id", &chat_id.to_string());
+                query.append_pair("message_id", &message_id.to_string());
+            }
+            GameTarget::Inline(ref inline_message_id) => {
+                query.append_pair("inline_message_id", inline_message_id);
+            }
+        }
+        query.append_pair("expires", &session.expires_at.to_string());
+        query.append_pair("signature", &self.sign(session)?);
+        let separator = if url.contains('?') { '&' } else { '?' };
+        Ok(format!("{}{}{}", url, separator, query.finish()))
+    }
+
+    /// Verifies a launch URL or its query string and returns the session
+    pub fn verify(&self, url: &str) -> Result<GameSession, GameUrlError> {
+        let query = match url.find('?') {
+            Some(idx) => &url[idx + 1..],
+            None => url,
+        };
+        let query = query.split('#').next().unwrap_or_default();
+        let params: HashMap<String, String> = form_urlencoded::parse(query.as_bytes()).into_owned().collect();
+        let get = |name: &'static str| params.get(name).ok_or(GameUrlError::MissingParameter(name));
+        let get_integer = |name: &'static str| {
+            get(name)?
+                .parse::<Integer>()
+                .map_err(|_| GameUrlError::InvalidParameter(name))
+        };
+        let game_short_name = get("game")?.clone();
+        let user_id = get_integer("user_id")?;
+        let target = match params.get("inline_message_id") {
+            Some(inline_message_id) => GameTarget::Inline(inline_message_id.clone()),
+            None => GameTarget::Message {
+                chat_id: get_integer("chat_id")?,
+                message_id: get_integer("message_id")?,
+            },
+        };
+        let session = GameSession {
+            game_short_name,
+            user_id,
+            target,
+            expires_at: get_integer("expires")?,
+        };
+        let signature = get("signature")?;
+        let expected = self.sign(&session)?;
+        if signature.len() != expected.len() || !memcmp::eq(signature.as_bytes(), expected.as_bytes()) {
+            return Err(GameUrlError::InvalidSignature);
+        }
+        if session.expires_at < get_unix_time() {
+            return Err(GameUrlError::Expired);
+        }
+        Ok(session)
+    }
+
+    /// Answers a callback query from a game button with a launch URL
+    ///
+    /// Returns None if the query is not a game launch request
+    /// When the game is unknown, the user is notified and the future resolves with an error
+    pub fn handle_callback_query(&self, api: &Api, query: &CallbackQuery) -> Option<ApiFuture<GameSession>> {
+        let session = self.create_session(query)?;
+        let answer = AnswerCallbackQuery::new(query.id.clone());
+        Some(match self.get_url(&session) {
+            Ok(url) => ApiFuture::new(api.execute(&answer.url(url)).map(move |_| session)),
+            Err(err) => ApiFuture::new(
+                api.execute(&answer.text(UNKNOWN_GAME_TEXT).show_alert(true))
+                    .and_then(move |_| future::err(Error::from(err))),
+            ),
+        })
+    }
+
+    fn sign(&self, session: &GameSession) -> Result<String, GameUrlError> {
+        let key = PKey::hmac(&self.secret)?;
+        let mut signer = Signer::new(MessageDigest::sha256(), &key)?;
+        signer.update(session.get_signed_data().as_bytes())?;
+        Ok(signer.sign_to_vec()?.iter().map(|x| format!("{:02x}", x)).collect())
+    }
+}
+
+fn get_unix_time() -> Integer {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|time| time.as_secs() as Integer)
+        .unwrap_or(0)
+}
+
+/// An error when building or verifying a launch URL
+#[derive(Debug, failure::Fail)]
+pub enum GameUrlError {
+    /// Failed to compute a signature
+    #[fail(display = "OpenSSL error: {}", _0)]
+    Openssl(#[cause] ErrorStack),
+    /// Game is not added to the launcher
+    #[fail(display = "Unknown game: {}", _0)]
+    UnknownGame(String),
+    /// URL does not contain a required parameter
+    #[fail(display = "Missing parameter: {}", _0)]
+    MissingParameter(&'static str),
+    /// A parameter is not a valid number
+    #[fail(display = "Invalid parameter: {}", _0)]
+    InvalidParameter(&'static str),
+    /// Signature does not match
+    #[fail(display = "Invalid signature")]
+    InvalidSignature,
+    /// URL has expired
+    #[fail(display = "URL has expired")]
+    Expired,
+}
+
+impl From<ErrorStack> for GameUrlError {
+    fn from(err: ErrorStack) -> Self {
+        GameUrlError::Openssl(err)
+    }
+}
@@ -0,0 +1,6 @@
+mod launch;
+mod scores;
+#[cfg(test)]
+mod tests;
+
+pub use self::{launch::*, scores::*};
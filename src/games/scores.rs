@@ -0,0 +1,111 @@
+use crate::{
+    api::{Api, ApiFuture},
+    games::launch::{GameSession, GameTarget},
+    types::{EditMessageResult, GameHighScore, Integer},
+};
+use futures::Future;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+type Leaderboards = HashMap<GameTarget, HashMap<Integer, Integer>>;
+
+/// Score of a player in the local leaderboard
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct GameScore {
+    /// Identifier of the player
+    pub user_id: Integer,
+    /// Best score of the player
+    pub score: Integer,
+}
+
+/// Submits scores with SetGameScore and caches leaderboards locally
+///
+/// Clones share the same cache
+#[derive(Clone)]
+pub struct GameScores {
+    api: Api,
+    leaderboards: Arc<Mutex<Leaderboards>>,
+}
+
+impl GameScores {
+    /// Creates a new GameScores with an empty cache
+    pub fn new(api: Api) -> Self {
+        GameScores {
+            api,
+            leaderboards: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Submits a score of the player
+    ///
+    /// Telegram rejects a score which is not greater than the current one,
+    /// the cache is updated only when the score is accepted
+    pub fn submit(&self, session: &GameSession, score: Integer) -> ApiFuture<EditMessageResult> {
+        let scores = self.clone();
+        let (target, user_id) = (session.target.clone(), session.user_id);
+        ApiFuture::new(self.api.execute(&session.set_score(score)).map(move |result| {
+            scores.record(&target, user_id, score);
+            result
+        }))
+    }
+
+    /// Gets high scores of the player and his neighbors and stores them in the cache
+    pub fn refresh(&self, session: &GameSession) -> ApiFuture<Vec<GameHighScore>> {
+        let scores = self.clone();
+        let target = session.target.clone();
+        ApiFuture::new(self.api.execute(&session.get_high_scores()).map(move |high_scores| {
+            scores.update(&target, &high_scores);
+            high_scores
+        }))
+    }
+
+    /// Records a score in the cache, the best score of the player is kept
+    pub fn record(&self, target: &GameTarget, user_id: Integer, score: Integer) {
+        let mut leaderboards = self.lock();
+        let best = leaderboards
+            .entry(target.clone())
+            .or_default()
+            .entry(user_id)
+            .or_insert(score);
+        *best = (*best).max(score);
+    }
+
+    /// Replaces cached scores of players with high scores received from Telegram
+    pub fn update(&self, target: &GameTarget, high_scores: &[GameHighScore]) {
+        let mut leaderboards = self.lock();
+        let leaderboard = leaderboards.entry(target.clone()).or_default();
+        for high_score in high_scores {
+            leaderboard.insert(high_score.user.id, high_score.score);
+        }
+    }
+
+    /// Returns a cached score of the player
+    pub fn get_score(&self, target: &GameTarget, user_id: Integer) -> Option<Integer> {
+        self.lock()
+            .get(target)
+            .and_then(|leaderboard| leaderboard.get(&user_id))
+            .cloned()
+    }
+
+    /// Returns cached scores sorted from the best one
+    pub fn get_leaderboard(&self, target: &GameTarget) -> Vec<GameScore> {
+        let mut result: Vec<GameScore> = self
+            .lock()
+            .get(target)
+            .map(|leaderboard| {
+                leaderboard
+                    .iter()
+                    .map(|(&user_id, &score)| GameScore { user_id, score })
+                    .collect()
+            })
+            .unwrap_or_default();
+        result.sort_by(|a, b| b.score.cmp(&a.score).then(a.user_id.cmp(&b.user_id)));
+        result
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Leaderboards> {
+        self.leaderboards.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
@@ -0,0 +1,103 @@
+use crate::{games::*, types::*, Api};
+use serde_json::json;
+use std::time::Duration;
+
+fn get_query(inline: bool, game_short_name: Option<&str>) -> CallbackQuery {
+    let mut query = json!({
+        "id": "query-id",
+        "from": {"id": 1, "is_bot": false, "first_name": "test"},
+        "chat_instance": "instance",
+        "game_short_name": game_short_name
+    });
+    if inline {
+        query["inline_message_id"] = json!("inline-id");
+    } else {
+        query["message"] = json!({
+            "message_id": 2,
+            "from": {"id": 4, "is_bot": true, "first_name": "bot"},
+            "date": 0,
+            "chat": {"id": 3, "type": "private", "first_name": "test"},
+            "text": "game"
+        });
+    }
+    serde_json::from_value(query).unwrap()
+}
+
+#[test]
+fn test_launch_url() {
+    let launcher = GameLauncher::new("secret").game("game", "https://example.com/game?lang=en");
+
+    let session = launcher.create_session(&get_query(false, Some("game"))).unwrap();
+    assert_eq!(session.user_id, 1);
+    assert_eq!(
+        session.target,
+        GameTarget::Message {
+            chat_id: 3,
+            message_id: 2
+        }
+    );
+    let url = launcher.get_url(&session).unwrap();
+    assert!(url.starts_with("https://example.com/game?lang=en&game=game&user_id=1&chat_id=3&message_id=2"));
+    assert_eq!(launcher.verify(&url).unwrap(), session);
+    let query = url.split_once('?').unwrap().1;
+    assert_eq!(launcher.verify(query).unwrap(), session);
+
+    let tampered = url.replace("user_id=1", "user_id=4");
+    assert!(matches!(
+        launcher.verify(&tampered),
+        Err(GameUrlError::InvalidSignature)
+    ));
+    let other = GameLauncher::new("other").game("game", "https://example.com/game");
+    assert!(matches!(other.verify(&url), Err(GameUrlError::InvalidSignature)));
+    assert!(matches!(
+        launcher.verify("https://example.com/game?game=game"),
+        Err(GameUrlError::MissingParameter("user_id"))
+    ));
+
+    let session = launcher.create_session(&get_query(true, Some("game"))).unwrap();
+    assert_eq!(session.target, GameTarget::Inline(String::from("inline-id")));
+    let url = launcher.get_url(&session).unwrap();
+    assert!(url.contains("&inline_message_id=inline-id&"));
+    assert_eq!(launcher.verify(&url).unwrap(), session);
+
+    let mut expired = session.clone();
+    expired.expires_at -= 7200;
+    let url = launcher.get_url(&expired).unwrap();
+    assert!(matches!(launcher.verify(&url), Err(GameUrlError::Expired)));
+
+    let launcher = launcher.ttl(Duration::from_secs(0));
+    let session = launcher.create_session(&get_query(false, Some("unknown"))).unwrap();
+    assert!(matches!(launcher.get_url(&session), Err(GameUrlError::UnknownGame(_))));
+    assert!(launcher.create_session(&get_query(false, None)).is_none());
+}
+
+#[test]
+fn test_leaderboard() {
+    let scores = GameScores::new(Api::new("token", None::<&str>).unwrap());
+    let target = GameTarget::Inline(String::from("inline-id"));
+    scores.record(&target, 1, 10);
+    scores.record(&target, 2, 30);
+    scores.record(&target, 1, 5);
+    assert_eq!(scores.get_score(&target, 1), Some(10));
+    assert_eq!(scores.get_score(&target, 3), None);
+
+    let high_scores: Vec<GameHighScore> = serde_json::from_value(json!([
+        {"position": 1, "user": {"id": 3, "is_bot": false, "first_name": "test"}, "score": 50},
+        {"position": 2, "user": {"id": 2, "is_bot": false, "first_name": "test"}, "score": 30}
+    ]))
+    .unwrap();
+    scores.update(&target, &high_scores);
+    assert_eq!(
+        scores.get_leaderboard(&target),
+        vec![
+            GameScore { user_id: 3, score: 50 },
+            GameScore { user_id: 2, score: 30 },
+            GameScore { user_id: 1, score: 10 },
+        ]
+    );
+    let other = GameTarget::Message {
+        chat_id: 1,
+        message_id: 1,
+    };
+    assert!(scores.get_leaderboard(&other).is_empty());
+}
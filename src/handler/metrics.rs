@@ -0,0 +1,32 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Counters of a bot served by WebhookRouter or PollingSupervisor
+#[derive(Debug, Default)]
+pub struct BotMetrics {
+    updates: AtomicUsize,
+    errors: AtomicUsize,
+}
+
+impl BotMetrics {
+    /// Number of received updates
+    ///
+    /// For webhook it is a number of successful requests
+    pub fn updates(&self) -> usize {
+        self.updates.load(Ordering::Relaxed)
+    }
+
+    /// Number of errors
+    ///
+    /// For webhook it is a number of rejected or failed requests
+    pub fn errors(&self) -> usize {
+        self.errors.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn add_update(&self) {
+        self.updates.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_error(&self) {
+        self.errors.fetch_add(1, Ordering::Relaxed);
+    }
+}
@@ -1,17 +1,51 @@
-use crate::types::Update;
-use futures::{Future, Stream};
+use crate::{
+    api::Api,
+    types::{TextParseMode, Update},
+};
+use failure::Error;
+use futures::{future, stream, Future, Stream};
 use hyper::Server;
+use serde_json::Value;
 use std::net::SocketAddr;
 
+mod metrics;
 mod poll;
+mod supervisor;
 mod webhook;
 
-pub use self::{poll::*, webhook::*};
+pub use self::{metrics::*, poll::*, supervisor::*, webhook::*};
 
 /// An update handler
 pub trait UpdateHandler {
     /// Handles an update
     fn handle(&mut self, update: Update);
+
+    /// Handles an update along with its raw JSON
+    ///
+    /// Called instead of handle() when keeping of raw updates is enabled
+    /// in UpdatesStreamOptions or WebhookOptions
+    fn handle_raw(&mut self, update: Update, _raw: Value) {
+        self.handle(update)
+    }
+}
+
+/// An update handler which may reply with a method
+///
+/// When updates are received using webhook, the method is sent in the response to the webhook request
+pub trait ReplyHandler {
+    /// A future returned by the handler
+    type Future: Future<Item = Option<WebhookReply>, Error = Error> + Send + 'static;
+
+    /// Handles an update
+    fn handle(&mut self, update: Update) -> Self::Future;
+
+    /// Handles an update along with its raw JSON
+    ///
+    /// Called instead of handle() when keeping of raw updates is enabled
+    /// in UpdatesStreamOptions or WebhookOptions
+    fn handle_raw(&mut self, update: Update, _raw: Value) -> Self::Future {
+        self.handle(update)
+    }
 }
 
 /// Defines how to get updates from Telegram
@@ -34,6 +68,52 @@ impl UpdateMethod {
     /// - addr - Bind address
     /// - path - URL path for webhook
     pub fn webhook<A, S>(addr: A, path: S) -> Self
+    where
+        A: Into<SocketAddr>,
+        S: Into<String>,
+    {
+        Self::webhook_with_options(addr, path, WebhookOptions::default())
+    }
+
+    /// Get updates using webhook with given options
+    ///
+    /// # Arguments
+    ///
+    /// - addr - Bind address
+    /// - path - URL path for webhook
+    /// - options - Webhook options
+    pub fn webhook_with_options<A, S>(addr: A, path: S, options: WebhookOptions) -> Self
+    where
+        A: Into<SocketAddr>,
+        S: Into<String>,
+    {
+        Self {
+            kind: UpdateMethodKind::Webhook {
+                addr: addr.into(),
+                path: path.into(),
+                options: Box::new(options),
+                registration: None,
+            },
+        }
+    }
+
+    /// Get updates using webhook which is registered on start
+    ///
+    /// SetWebhook is called before the server starts,
+    /// the server does not start if webhook can not be set
+    ///
+    /// # Arguments
+    ///
+    /// - addr - Bind address
+    /// - path - URL path for webhook
+    /// - options - Webhook options
+    /// - registration - Public URL and options for SetWebhook
+    pub fn registered_webhook<A, S>(
+        addr: A,
+        path: S,
+        options: WebhookOptions,
+        registration: WebhookRegistration,
+    ) -> Self
     where
         A: Into<SocketAddr>,
         S: Into<String>,
@@ -42,6 +122,8 @@ impl UpdateMethod {
             kind: UpdateMethodKind::Webhook {
                 addr: addr.into(),
                 path: path.into(),
+                options: Box::new(options),
+                registration: Some(registration),
             },
         }
     }
@@ -49,7 +131,12 @@ impl UpdateMethod {
 
 enum UpdateMethodKind {
     Poll(UpdatesStream),
-    Webhook { addr: SocketAddr, path: String },
+    Webhook {
+        addr: SocketAddr,
+        path: String,
+        options: Box<WebhookOptions>,
+        registration: Option<WebhookRegistration>,
+    },
 }
 
 /// Start getting updates
@@ -58,20 +145,137 @@ where
     H: UpdateHandler + Send + Sync + 'static,
 {
     match update_method.kind {
-        UpdateMethodKind::Poll(stream) => {
+        UpdateMethodKind::Poll(mut stream) => {
+            tokio::run(
+                stream::poll_fn(move || stream.poll_with_raw())
+                    .for_each(move |(update, raw)| {
+                        match raw {
+                            Some(raw) => handler.handle_raw(update, raw),
+                            None => handler.handle(update),
+                        }
+                        Ok(())
+                    })
+                    .then(|_| Ok(())),
+            );
+        }
+        UpdateMethodKind::Webhook {
+            addr,
+            path,
+            options,
+            registration,
+        } => run_webhook(addr, *options, registration, move |options| {
+            WebhookServiceFactory::with_options(path, handler, options)
+        }),
+    }
+}
+
+/// Start getting updates using a handler which may reply with a method
+///
+/// With long polling the method is executed using given Api
+pub fn handle_updates_with_replies<H>(update_method: UpdateMethod, api: Api, mut handler: H)
+where
+    H: ReplyHandler + Send + Sync + 'static,
+{
+    match update_method.kind {
+        UpdateMethodKind::Poll(mut stream) => {
             tokio::run(
-                stream
-                    .for_each(move |update| {
-                        handler.handle(update);
+                stream::poll_fn(move || stream.poll_with_raw())
+                    .for_each(move |(update, raw)| {
+                        let api = api.clone();
+                        let reply = match raw {
+                            Some(raw) => handler.handle_raw(update, raw),
+                            None => handler.handle(update),
+                        };
+                        tokio::spawn(reply.then(move |result| {
+                            send_reply(&api, result, None);
+                            Ok(())
+                        }));
                         Ok(())
                     })
                     .then(|_| Ok(())),
             );
         }
-        UpdateMethodKind::Webhook { addr, path } => tokio::run(
-            Server::bind(&addr)
-                .serve(WebhookServiceFactory::new(path, handler))
-                .map_err(|e| log::error!("Server error: {}", e)),
-        ),
+        UpdateMethodKind::Webhook {
+            addr,
+            path,
+            options,
+            registration,
+        } => run_webhook(addr, *options, registration, move |options| {
+            WebhookServiceFactory::with_reply_handler(path, api, handler, options)
+        }),
     }
 }
+
+/// Start a webhook server for multiple bots
+///
+/// # Arguments
+///
+/// - addr - Bind address
+/// - router - Bots served by the server, can be changed while server is running
+pub fn handle_webhook_router<A: Into<SocketAddr>>(addr: A, router: WebhookRouter) {
+    tokio::run(
+        Server::bind(&addr.into())
+            .serve(router)
+            .map_err(|e| log::error!("Server error: {}", e)),
+    );
+}
+
+/// Parses an update received by a handler, raw JSON is returned when keep_raw is true
+pub(crate) fn parse_update(
+    value: Value,
+    keep_raw: bool,
+    text_parse_mode: TextParseMode,
+) -> Result<(Update, Option<Value>), serde_json::Error> {
+    let raw = if keep_raw { Some(value.clone()) } else { None };
+    let (update, warnings) = Update::parse(value, text_parse_mode)?;
+    for warning in warnings {
+        log::warn!("Malformed text entity in update {}: {}", update.id, warning);
+    }
+    Ok((update, raw))
+}
+
+fn run_webhook<F>(addr: SocketAddr, options: WebhookOptions, registration: Option<WebhookRegistration>, make_factory: F)
+where
+    F: FnOnce(WebhookOptions) -> WebhookServiceFactory,
+{
+    let tls = options.get_tls().cloned();
+    let register = match registration {
+        Some(ref registration) => future::Either::A(registration.register(&options)),
+        None => future::Either::B(future::ok(())),
+    };
+    let factory = make_factory(options);
+    tokio::run(future::lazy(move || {
+        register.and_then(move |()| {
+            let shutdown: Box<dyn Future<Item = (), Error = ()> + Send> = match registration {
+                Some(ref registration) if registration.is_delete_on_shutdown() => Box::new(shutdown_signal()),
+                _ => Box::new(future::empty()),
+            };
+            let server: Box<dyn Future<Item = (), Error = ()> + Send> = match tls {
+                Some(tls) => match tls.incoming(&addr) {
+                    Ok(incoming) => Box::new(
+                        Server::builder(incoming)
+                            .serve(factory)
+                            .with_graceful_shutdown(shutdown)
+                            .map_err(|e| log::error!("Server error: {}", e)),
+                    ),
+                    Err(e) => {
+                        log::error!("Failed to bind {}: {}", addr, e);
+                        Box::new(future::err(()))
+                    }
+                },
+                None => Box::new(
+                    Server::bind(&addr)
+                        .serve(factory)
+                        .with_graceful_shutdown(shutdown)
+                        .map_err(|e| log::error!("Server error: {}", e)),
+                ),
+            };
+            server.then(move |result| match registration {
+                Some(ref registration) if result.is_ok() && registration.is_delete_on_shutdown() => {
+                    future::Either::A(registration.unregister())
+                }
+                _ => future::Either::B(future::result(result)),
+            })
+        })
+    }));
+}
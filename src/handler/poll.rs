@@ -1,11 +1,13 @@
 use crate::{
     api::Api,
-    methods::GetUpdates,
-    types::{AllowedUpdate, Integer, ResponseError, Update},
+    handler::parse_update,
+    methods::{GetUpdates, Method, RequestBuilder},
+    types::{AllowedUpdate, Integer, ResponseError, TextParseMode, Update},
 };
 use failure::Error;
 use futures::{task, Async, Future, Poll, Stream};
 use log::error;
+use serde_json::Value;
 use std::{
     cmp::max,
     collections::{HashSet, VecDeque},
@@ -21,8 +23,8 @@ const DEFAULT_ERROR_TIMEOUT: Duration = Duration::from_secs(5);
 pub struct UpdatesStream {
     api: Api,
     options: UpdatesStreamOptions,
-    items: VecDeque<Update>,
-    request: Option<Box<Future<Item = Option<Vec<Update>>, Error = Error> + Send>>,
+    items: VecDeque<(Update, Option<Value>)>,
+    request: Option<Box<Future<Item = Option<Vec<Value>>, Error = Error> + Send>>,
 }
 
 impl UpdatesStream {
@@ -54,8 +56,15 @@ impl Stream for UpdatesStream {
     type Error = Error;
 
     fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
-        if let Some(update) = self.items.pop_front() {
-            return Ok(Async::Ready(Some(update)));
+        Ok(self.poll_with_raw()?.map(|item| item.map(|(update, _)| update)))
+    }
+}
+
+impl UpdatesStream {
+    /// Polls for an update along with its raw JSON, which is present when keeping of raw updates is enabled
+    pub(crate) fn poll_with_raw(&mut self) -> Poll<Option<(Update, Option<Value>)>, Error> {
+        if let Some(item) = self.items.pop_front() {
+            return Ok(Async::Ready(Some(item)));
         }
 
         let options = &mut self.options;
@@ -63,9 +72,14 @@ impl Stream for UpdatesStream {
         let should_request = match self.request {
             Some(ref mut request) => match request.poll() {
                 Ok(Async::Ready(Some(items))) => {
-                    for i in items {
-                        options.offset = max(options.offset, i.id);
-                        self.items.push_back(i);
+                    for item in items {
+                        if let Some(id) = item.get("update_id").and_then(Value::as_i64) {
+                            options.offset = max(options.offset, id);
+                        }
+                        match parse_update(item, options.keep_raw, options.text_parse_mode) {
+                            Ok(item) => self.items.push_back(item),
+                            Err(err) => error!("Failed to parse update: {}", err),
+                        }
                     }
                     Ok(())
                 }
@@ -80,13 +94,13 @@ impl Stream for UpdatesStream {
             Ok(()) => {
                 self.request = Some(Box::new(
                     self.api
-                        .execute(
-                            &GetUpdates::default()
+                        .execute(&RawGetUpdates(
+                            GetUpdates::default()
                                 .offset(options.offset + 1)
                                 .limit(options.limit)
                                 .timeout(options.poll_timeout)
                                 .allowed_updates(options.allowed_updates.clone()),
-                        )
+                        ))
                         .map(Some),
                 ));
             }
@@ -113,6 +127,17 @@ impl Stream for UpdatesStream {
     }
 }
 
+/// Returns updates as JSON values, so an update which can not be parsed does not fail others
+struct RawGetUpdates(GetUpdates);
+
+impl Method for RawGetUpdates {
+    type Response = Vec<Value>;
+
+    fn get_request(&self) -> Result<RequestBuilder, Error> {
+        self.0.get_request()
+    }
+}
+
 /// Update stream options
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct UpdatesStreamOptions {
@@ -121,6 +146,8 @@ pub struct UpdatesStreamOptions {
     poll_timeout: Duration,
     error_timeout: Duration,
     allowed_updates: HashSet<AllowedUpdate>,
+    keep_raw: bool,
+    text_parse_mode: TextParseMode,
 }
 
 impl UpdatesStreamOptions {
@@ -156,6 +183,22 @@ impl UpdatesStreamOptions {
         self.allowed_updates.insert(allowed_update);
         self
     }
+
+    /// Passes raw JSON of each update to UpdateHandler::handle_raw()
+    ///
+    /// Disabled by default
+    pub fn keep_raw(mut self, keep_raw: bool) -> Self {
+        self.keep_raw = keep_raw;
+        self
+    }
+
+    /// How to handle malformed text entities
+    ///
+    /// Defaults to TextParseMode::Lenient, so a malformed entity does not fail the whole update
+    pub fn text_parse_mode(mut self, text_parse_mode: TextParseMode) -> Self {
+        self.text_parse_mode = text_parse_mode;
+        self
+    }
 }
 
 impl Default for UpdatesStreamOptions {
@@ -166,6 +209,8 @@ impl Default for UpdatesStreamOptions {
             poll_timeout: DEFAULT_POLL_TIMEOUT,
             error_timeout: DEFAULT_ERROR_TIMEOUT,
             allowed_updates: HashSet::new(),
+            keep_raw: false,
+            text_parse_mode: TextParseMode::Lenient,
         }
     }
 }
@@ -0,0 +1,169 @@
+use crate::handler::{metrics::BotMetrics, poll::UpdatesStream, UpdateHandler};
+use futures::{
+    future::{self, Loop},
+    sync::oneshot,
+    Future, Stream,
+};
+use std::{
+    collections::HashMap,
+    io,
+    sync::{Arc, Mutex, MutexGuard},
+};
+use tokio::runtime::{Runtime, TaskExecutor};
+
+type BotFuture = Box<dyn Future<Item = (), Error = ()> + Send>;
+
+/// Runs updates streams of multiple bots using long polling
+///
+/// Bots can be added and removed at runtime
+/// Clones of the supervisor share the same set of bots
+#[derive(Clone, Default)]
+pub struct PollingSupervisor {
+    state: Arc<Mutex<SupervisorState>>,
+}
+
+#[derive(Default)]
+struct SupervisorState {
+    executor: Option<TaskExecutor>,
+    pending: Vec<BotFuture>,
+    bots: HashMap<String, SupervisedBot>,
+}
+
+struct SupervisedBot {
+    // Bot stops when the sender is dropped
+    _stop: oneshot::Sender<()>,
+    metrics: Arc<BotMetrics>,
+}
+
+impl PollingSupervisor {
+    /// Creates a supervisor without bots
+    pub fn new() -> Self {
+        PollingSupervisor::default()
+    }
+
+    /// Adds a bot with given name
+    ///
+    /// A bot with the same name is stopped and replaced
+    /// Errors of the stream are logged and polling continues
+    /// Returns metrics of the bot
+    pub fn add_bot<S, H>(&self, name: S, stream: UpdatesStream, handler: H) -> Arc<BotMetrics>
+    where
+        S: Into<String>,
+        H: UpdateHandler + Send + 'static,
+    {
+        let name = name.into();
+        let metrics = Arc::new(BotMetrics::default());
+        let (stop_tx, stop_rx) = oneshot::channel::<()>();
+        let bot = stop_rx.select2(run_bot(name.clone(), stream, handler, metrics.clone()));
+        let stopped_name = name.clone();
+        let bot: BotFuture = Box::new(bot.then(move |_| {
+            log::info!("Bot {} has been stopped", stopped_name);
+            Ok(())
+        }));
+        let mut state = self.lock();
+        match state.executor {
+            Some(ref executor) => executor.spawn(bot),
+            None => state.pending.push(bot),
+        }
+        let supervised = SupervisedBot {
+            _stop: stop_tx,
+            metrics: metrics.clone(),
+        };
+        if state.bots.insert(name.clone(), supervised).is_some() {
+            log::info!("Replaced bot {}", name);
+        }
+        metrics
+    }
+
+    /// Stops and removes a bot with given name
+    ///
+    /// Returns false if there is no such bot
+    pub fn remove_bot(&self, name: &str) -> bool {
+        self.lock().bots.remove(name).is_some()
+    }
+
+    /// Returns names of all bots
+    pub fn names(&self) -> Vec<String> {
+        self.lock().bots.keys().cloned().collect()
+    }
+
+    /// Returns metrics of a bot with given name
+    pub fn metrics(&self, name: &str) -> Option<Arc<BotMetrics>> {
+        self.lock().bots.get(name).map(|bot| bot.metrics.clone())
+    }
+
+    /// Runs bots and blocks until all of them are stopped
+    pub fn run(&self) -> Result<(), io::Error> {
+        let mut runtime = Runtime::new()?;
+        {
+            let mut state = self.lock();
+            for bot in state.pending.drain(..) {
+                runtime.spawn(bot);
+            }
+            state.executor = Some(runtime.executor());
+        }
+        let result = runtime.shutdown_on_idle().wait();
+        self.lock().executor = None;
+        result.map_err(|()| io::Error::other("Failed to shutdown runtime"))
+    }
+
+    fn lock(&self) -> MutexGuard<'_, SupervisorState> {
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+fn run_bot<H>(
+    name: String,
+    stream: UpdatesStream,
+    handler: H,
+    metrics: Arc<BotMetrics>,
+) -> impl Future<Item = (), Error = ()> + Send
+where
+    H: UpdateHandler + Send + 'static,
+{
+    future::loop_fn((stream, handler), move |(stream, mut handler)| {
+        let name = name.clone();
+        let metrics = metrics.clone();
+        stream.into_future().then(move |result| match result {
+            Ok((Some(update), stream)) => {
+                metrics.add_update();
+                handler.handle(update);
+                Ok(Loop::Continue((stream, handler)))
+            }
+            Ok((None, _)) => Ok(Loop::Break(())),
+            Err((err, stream)) => {
+                metrics.add_error();
+                log::error!("Bot {} failed to get updates: {}", name, err);
+                Ok(Loop::Continue((stream, handler)))
+            }
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{api::Api, types::Update};
+
+    struct Handler;
+
+    impl UpdateHandler for Handler {
+        fn handle(&mut self, _update: Update) {}
+    }
+
+    #[test]
+    fn test_supervisor_bots() {
+        let api = Api::new("token", None::<&str>).unwrap();
+        let supervisor = PollingSupervisor::new();
+        let metrics = supervisor.add_bot("bot1", UpdatesStream::new(api.clone()), Handler);
+        supervisor.clone().add_bot("bot2", UpdatesStream::new(api), Handler);
+        let mut names = supervisor.names();
+        names.sort();
+        assert_eq!(names, vec!["bot1", "bot2"]);
+        assert!(Arc::ptr_eq(&supervisor.metrics("bot1").unwrap(), &metrics));
+        assert!(supervisor.remove_bot("bot1"));
+        assert!(supervisor.remove_bot("bot2"));
+        assert!(supervisor.names().is_empty());
+        supervisor.run().unwrap();
+    }
+}
@@ -0,0 +1,351 @@
+use crate::{api::Api, Never, ReplyHandler, UpdateHandler};
+use futures::{
+    future::{ok, Either},
+    sync::oneshot,
+    Async, Future, Poll, Stream,
+};
+use hyper::{
+    header::{HeaderValue, ALLOW, CONTENT_LENGTH, CONTENT_TYPE},
+    server::conn::AddrStream,
+    service::{MakeService, Service},
+    Body, Error, Method, Request, Response, StatusCode,
+};
+use lazy_queue::sync::bounded::LazyQueue;
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio_executor::spawn;
+use tokio_timer::Delay;
+
+mod options;
+mod queue;
+mod registration;
+mod reply;
+mod router;
+mod tls;
+
+use self::{
+    queue::{Pushed, QueueItem, UpdateQueue},
+    reply::WaitReply,
+};
+
+pub use self::{
+    options::*, queue::QueueOverflow, registration::WebhookRegistration, reply::WebhookReply, router::*, tls::*,
+};
+pub(crate) use self::{registration::shutdown_signal, reply::send_reply};
+
+const SECRET_TOKEN_HEADER: &str = "X-Telegram-Bot-Api-Secret-Token";
+const FORWARDED_FOR_HEADER: &str = "X-Forwarded-For";
+
+/// A connection which knows address of the remote side
+pub trait RemoteAddr {
+    /// Returns address of the remote side
+    fn remote_addr(&self) -> Option<SocketAddr>;
+}
+
+impl RemoteAddr for AddrStream {
+    fn remote_addr(&self) -> Option<SocketAddr> {
+        Some(AddrStream::remote_addr(self))
+    }
+}
+
+/// Creates a webhook service
+pub struct WebhookServiceFactory {
+    path: String,
+    options: Arc<WebhookOptions>,
+    queue: UpdateQueue,
+    processor: Option<Box<dyn Future<Item = (), Error = ()> + Send>>,
+    reply_timeout: Option<Duration>,
+}
+
+impl WebhookServiceFactory {
+    /// Creates a new factory
+    pub fn new<S, H>(path: S, update_handler: H) -> WebhookServiceFactory
+    where
+        S: Into<String>,
+        H: UpdateHandler + Send + Sync + 'static,
+    {
+        WebhookServiceFactory::with_options(path, update_handler, WebhookOptions::default())
+    }
+
+    /// Creates a new factory with given options
+    ///
+    /// # Panics
+    ///
+    /// Panics if a spill file for queue overflow can not be opened
+    pub fn with_options<S, H>(path: S, mut update_handler: H, options: WebhookOptions) -> WebhookServiceFactory
+    where
+        S: Into<String>,
+        H: UpdateHandler + Send + Sync + 'static,
+    {
+        let (queue, processor) = LazyQueue::new(
+            move |item: QueueItem| {
+                match item.raw {
+                    Some(raw) => update_handler.handle_raw(item.update, raw),
+                    None => update_handler.handle(item.update),
+                }
+                Ok::<_, Never>(())
+            },
+            options.get_queue_size(),
+        );
+        let processor = processor.map_err(|e| log::error!("Processing error: {}", e));
+        WebhookServiceFactory::from_queue(path.into(), options, queue, processor, None)
+    }
+
+    /// Creates a new factory with a handler which may reply with a method
+    ///
+    /// A method is sent in the response to the webhook request when the handler
+    /// resolves within a reply timeout (see WebhookOptions::reply_timeout),
+    /// otherwise it is executed using given Api
+    ///
+    /// # Panics
+    ///
+    /// Panics if a spill file for queue overflow can not be opened
+    pub fn with_reply_handler<S, H>(path: S, api: Api, mut handler: H, options: WebhookOptions) -> WebhookServiceFactory
+    where
+        S: Into<String>,
+        H: ReplyHandler + Send + Sync + 'static,
+    {
+        let reply_timeout = options.get_reply_timeout();
+        let (queue, processor) = LazyQueue::new(
+            move |item: QueueItem| {
+                let api = api.clone();
+                let sender = item.reply;
+                let reply = match item.raw {
+                    Some(raw) => handler.handle_raw(item.update, raw),
+                    None => handler.handle(item.update),
+                };
+                spawn(reply.then(move |result| {
+                    send_reply(&api, result, sender);
+                    Ok(())
+                }));
+                Ok::<_, Never>(())
+            },
+            options.get_queue_size(),
+        );
+        let processor = processor.map_err(|e| log::error!("Processing error: {}", e));
+        WebhookServiceFactory::from_queue(path.into(), options, queue, processor, Some(reply_timeout))
+    }
+
+    pub(crate) fn get_path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns a service for a connection and starts processing updates on first call
+    pub(crate) fn make_webhook_service(&mut self, remote_addr: Option<SocketAddr>) -> WebhookService {
+        if let Some(fut) = self.processor.take() {
+            spawn(fut);
+        }
+        WebhookService {
+            path: self.path.clone(),
+            options: self.options.clone(),
+            queue: self.queue.clone(),
+            remote_addr: remote_addr.map(|addr| addr.ip()),
+            reply_timeout: self.reply_timeout,
+        }
+    }
+
+    fn from_queue(
+        path: String,
+        options: WebhookOptions,
+        queue: LazyQueue<QueueItem>,
+        processor: impl Future<Item = (), Error = ()> + Send + 'static,
+        reply_timeout: Option<Duration>,
+    ) -> WebhookServiceFactory {
+        let queue = UpdateQueue::new(queue, &options).expect("Failed to open a spill file");
+        let processor: Box<dyn Future<Item = (), Error = ()> + Send> = match queue.drain_spill() {
+            Some(drain) => Box::new(processor.join(drain).map(|_| ())),
+            None => Box::new(processor),
+        };
+        WebhookServiceFactory {
+            path,
+            options: Arc::new(options),
+            queue,
+            processor: Some(processor),
+            reply_timeout,
+        }
+    }
+}
+
+impl<'a, C: RemoteAddr> MakeService<&'a C> for WebhookServiceFactory {
+    type ReqBody = Body;
+    type ResBody = Body;
+    type Error = Error;
+    type Service = WebhookService;
+    type Future = Box<Future<Item = Self::Service, Error = Self::MakeError> + Send>;
+    type MakeError = Never;
+
+    fn make_service(&mut self, ctx: &'a C) -> Self::Future {
+        Box::new(ok(self.make_webhook_service(ctx.remote_addr())))
+    }
+}
+
+/// Webhook service
+pub struct WebhookService {
+    path: String,
+    options: Arc<WebhookOptions>,
+    queue: UpdateQueue,
+    remote_addr: Option<IpAddr>,
+    reply_timeout: Option<Duration>,
+}
+
+fn empty_response(status: StatusCode) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .body(Body::empty())
+        .expect("Can't construct an empty response")
+}
+
+/// Reads a request body until it exceeds given limit
+struct ReadBody {
+    body: Body,
+    buf: Vec<u8>,
+    limit: Option<usize>,
+}
+
+impl Future for ReadBody {
+    type Item = Option<Vec<u8>>;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        loop {
+            match self.body.poll()? {
+                Async::Ready(Some(chunk)) => {
+                    if let Some(limit) = self.limit {
+                        if self.buf.len() + chunk.len() > limit {
+                            return Ok(Async::Ready(None));
+                        }
+                    }
+                    self.buf.extend_from_slice(&chunk);
+                }
+                Async::Ready(None) => return Ok(Async::Ready(Some(self.buf.split_off(0)))),
+                Async::NotReady => return Ok(Async::NotReady),
+            }
+        }
+    }
+}
+
+fn reply_response(reply: Option<WebhookReply>) -> Response<Body> {
+    match reply.as_ref().and_then(WebhookReply::get_body) {
+        Some(body) => Response::builder()
+            .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+            .body(Body::from(body.to_vec()))
+            .expect("Can't construct a reply response"),
+        None => Response::new(Body::empty()),
+    }
+}
+
+fn put_on_a_queue(
+    request: Request<Body>,
+    max_body_size: Option<usize>,
+    reply_timeout: Option<Duration>,
+    queue: UpdateQueue,
+) -> impl Future<Item = Response<Body>, Error = Error> {
+    ReadBody {
+        body: request.into_body(),
+        buf: Vec::new(),
+        limit: max_body_size,
+    }
+    .and_then(move |body| match body {
+        Some(body) => Either::A(match queue.parse(&body) {
+            Ok((update, raw)) => {
+                let (sender, receiver) = match reply_timeout {
+                    Some(_) => {
+                        let (sender, receiver) = oneshot::channel();
+                        (Some(sender), Some(receiver))
+                    }
+                    None => (None, None),
+                };
+                let item = QueueItem {
+                    update,
+                    raw,
+                    reply: sender,
+                };
+                Either::A(queue.push(&body, item).then(move |result| {
+                    match result {
+                        Ok(Pushed::Queued) => match (receiver, reply_timeout) {
+                            (Some(receiver), Some(reply_timeout)) => Either::B(
+                                WaitReply::new(receiver, Delay::new(Instant::now() + reply_timeout))
+                                    .then(|reply| Ok(reply_response(reply.unwrap_or(None)))),
+                            ),
+                            _ => Either::A(ok(Response::new(Body::empty()))),
+                        },
+                        Ok(Pushed::Accepted) => Either::A(ok(Response::new(Body::empty()))),
+                        Err(status) => Either::A(ok(empty_response(status))),
+                    }
+                }))
+            }
+            Err(err) => Either::B(ok(Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::from(err.to_string()))
+                .expect("Can't construct a BAD_REQUEST response"))),
+        }),
+        None => Either::B(ok(empty_response(StatusCode::PAYLOAD_TOO_LARGE))),
+    })
+}
+
+impl WebhookService {
+    /// Checks whether request is allowed by options and returns an error status if not
+    fn verify(&self, req: &Request<Body>) -> Result<(), StatusCode> {
+        let headers = req.headers();
+        let forwarded_for = headers.get(FORWARDED_FOR_HEADER).and_then(|x| x.to_str().ok());
+        let client_addr = self.options.get_client_addr(self.remote_addr, forwarded_for);
+        if !self.options.is_addr_allowed(client_addr) {
+            log::warn!("Rejected webhook request from {:?}", client_addr);
+            return Err(StatusCode::FORBIDDEN);
+        }
+        let secret_token = headers.get(SECRET_TOKEN_HEADER).map(HeaderValue::as_bytes);
+        if !self.options.is_secret_token_valid(secret_token) {
+            log::warn!(
+                "Rejected webhook request with invalid secret token from {:?}",
+                client_addr
+            );
+            return Err(StatusCode::FORBIDDEN);
+        }
+        if let Some(max_body_size) = self.options.get_max_body_size() {
+            let content_length = headers
+                .get(CONTENT_LENGTH)
+                .and_then(|x| x.to_str().ok())
+                .and_then(|x| x.parse::<usize>().ok());
+            if let Some(content_length) = content_length {
+                if content_length > max_body_size {
+                    return Err(StatusCode::PAYLOAD_TOO_LARGE);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Service for WebhookService {
+    type ReqBody = Body;
+    type ResBody = Body;
+    type Error = Error;
+    type Future = Box<Future<Item = Response<Body>, Error = Error> + Send>;
+
+    fn call(&mut self, req: Request<Self::ReqBody>) -> Self::Future {
+        if let Method::POST = *req.method() {
+            if req.uri().path() == self.path {
+                match self.verify(&req) {
+                    Ok(()) => Box::new(put_on_a_queue(
+                        req,
+                        self.options.get_max_body_size(),
+                        self.reply_timeout,
+                        self.queue.clone(),
+                    )),
+                    Err(status) => Box::new(ok(empty_response(status))),
+                }
+            } else {
+                Box::new(ok(empty_response(StatusCode::NOT_FOUND)))
+            }
+        } else {
+            Box::new(ok(Response::builder()
+                .status(StatusCode::METHOD_NOT_ALLOWED)
+                .header(ALLOW, HeaderValue::from_static("POST"))
+                .body(Body::empty())
+                .expect("Can't construct a METHOD_NOT_ALLOWED response")))
+        }
+    }
+}
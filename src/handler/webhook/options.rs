@@ -0,0 +1,397 @@
+use crate::{
+    handler::webhook::{queue::QueueOverflow, tls::WebhookTls},
+    types::TextParseMode,
+};
+use openssl::memcmp;
+use std::{
+    fmt,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    str::FromStr,
+    time::Duration,
+};
+
+const DEFAULT_REPLY_TIMEOUT: Duration = Duration::from_secs(2);
+const DEFAULT_QUEUE_SIZE: usize = 10;
+
+/// Subnets Telegram sends webhook requests from
+const TELEGRAM_NETWORKS: [IpNetwork; 2] = [
+    IpNetwork {
+        addr: IpAddr::V4(Ipv4Addr::new(149, 154, 160, 0)),
+        prefix: 20,
+    },
+    IpNetwork {
+        addr: IpAddr::V4(Ipv4Addr::new(91, 108, 4, 0)),
+        prefix: 22,
+    },
+];
+
+/// Webhook options
+#[derive(Clone, Debug, Default)]
+pub struct WebhookOptions {
+    secret_token: Option<String>,
+    allowed_networks: Option<Vec<IpNetwork>>,
+    trusted_proxies: Vec<IpAddr>,
+    max_body_size: Option<usize>,
+    tls: Option<WebhookTls>,
+    reply_timeout: Option<Duration>,
+    queue_size: Option<usize>,
+    queue_overflow: QueueOverflow,
+    recent_updates: Option<usize>,
+    keep_raw_updates: bool,
+    text_parse_mode: Option<TextParseMode>,
+}
+
+impl WebhookOptions {
+    /// Requires X-Telegram-Bot-Api-Secret-Token header to be equal to given value
+    ///
+    /// Pass the same token to SetWebhook::secret_token
+    pub fn secret_token<S: Into<String>>(mut self, secret_token: S) -> Self {
+        self.secret_token = Some(secret_token.into());
+        self
+    }
+
+    /// Accepts requests from given network only
+    ///
+    /// Can be called multiple times, requests from any of added networks are accepted
+    /// By default requests from any address are accepted
+    pub fn allow_network(mut self, network: IpNetwork) -> Self {
+        self.allowed_networks.get_or_insert_with(Vec::new).push(network);
+        self
+    }
+
+    /// Accepts requests from Telegram subnets only (149.154.160.0/20 and 91.108.4.0/22)
+    pub fn allow_telegram_networks(mut self) -> Self {
+        self.allowed_networks
+            .get_or_insert_with(Vec::new)
+            .extend_from_slice(&TELEGRAM_NETWORKS);
+        self
+    }
+
+    /// Trusts X-Forwarded-For header when request comes from given address
+    ///
+    /// Use it when webhook server is running behind a reverse proxy
+    pub fn trust_proxy(mut self, addr: IpAddr) -> Self {
+        self.trusted_proxies.push(addr);
+        self
+    }
+
+    /// Maximum size of request body in bytes
+    ///
+    /// Requests with larger body are rejected with 413 Payload Too Large
+    /// Not limited by default
+    pub fn max_body_size(mut self, max_body_size: usize) -> Self {
+        self.max_body_size = Some(max_body_size);
+        self
+    }
+
+    /// Serves webhook over HTTPS using given TLS settings
+    ///
+    /// Used by handle_updates, a custom server must terminate TLS itself
+    pub fn tls(mut self, tls: WebhookTls) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
+    /// How long to wait for a reply handler before responding to a webhook request
+    ///
+    /// A reply which is not ready in time is executed using Api
+    /// Defaults to 2 seconds
+    pub fn reply_timeout(mut self, reply_timeout: Duration) -> Self {
+        self.reply_timeout = Some(reply_timeout);
+        self
+    }
+
+    pub(super) fn get_reply_timeout(&self) -> Duration {
+        self.reply_timeout.unwrap_or(DEFAULT_REPLY_TIMEOUT)
+    }
+
+    /// Maximum number of updates waiting for a handler
+    ///
+    /// Defaults to 10
+    pub fn queue_size(mut self, queue_size: usize) -> Self {
+        self.queue_size = Some(queue_size);
+        self
+    }
+
+    /// What to do with an update when queue is full
+    ///
+    /// Defaults to QueueOverflow::Block
+    pub fn queue_overflow(mut self, queue_overflow: QueueOverflow) -> Self {
+        self.queue_overflow = queue_overflow;
+        self
+    }
+
+    /// Skips updates with an ID equal to one of given number of recently received updates
+    ///
+    /// Telegram delivers an update again when a response to the webhook request is not received in time
+    /// Duplicates are not skipped by default
+    pub fn skip_duplicates(mut self, recent_updates: usize) -> Self {
+        self.recent_updates = Some(recent_updates);
+        self
+    }
+
+    /// Passes raw JSON of each update to UpdateHandler::handle_raw()
+    ///
+    /// Disabled by default
+    pub fn keep_raw_updates(mut self, keep_raw_updates: bool) -> Self {
+        self.keep_raw_updates = keep_raw_updates;
+        self
+    }
+
+    /// How to handle malformed text entities
+    ///
+    /// Defaults to TextParseMode::Lenient, so a malformed entity does not fail the whole update
+    pub fn text_parse_mode(mut self, text_parse_mode: TextParseMode) -> Self {
+        self.text_parse_mode = Some(text_parse_mode);
+        self
+    }
+
+    pub(super) fn get_queue_size(&self) -> usize {
+        self.queue_size.unwrap_or(DEFAULT_QUEUE_SIZE)
+    }
+
+    pub(super) fn get_queue_overflow(&self) -> QueueOverflow {
+        self.queue_overflow.clone()
+    }
+
+    pub(super) fn get_recent_updates(&self) -> Option<usize> {
+        self.recent_updates
+    }
+
+    pub(super) fn is_keep_raw_updates(&self) -> bool {
+        self.keep_raw_updates
+    }
+
+    pub(super) fn get_text_parse_mode(&self) -> TextParseMode {
+        self.text_parse_mode.unwrap_or(TextParseMode::Lenient)
+    }
+
+    pub(crate) fn get_secret_token(&self) -> Option<&str> {
+        self.secret_token.as_deref()
+    }
+
+    pub(crate) fn get_tls(&self) -> Option<&WebhookTls> {
+        self.tls.as_ref()
+    }
+
+    pub(super) fn get_max_body_size(&self) -> Option<usize> {
+        self.max_body_size
+    }
+
+    pub(super) fn is_secret_token_valid(&self, token: Option<&[u8]>) -> bool {
+        match self.secret_token {
+            Some(ref expected) => token
+                .map(|token| token.len() == expected.len() && memcmp::eq(token, expected.as_bytes()))
+                .unwrap_or(false),
+            None => true,
+        }
+    }
+
+    pub(super) fn is_addr_allowed(&self, addr: Option<IpAddr>) -> bool {
+        match self.allowed_networks {
+            Some(ref networks) => addr
+                .map(|addr| networks.iter().any(|network| network.contains(addr)))
+                .unwrap_or(false),
+            None => true,
+        }
+    }
+
+    /// Returns address of a client taking trusted proxies into account
+    ///
+    /// The rightmost address in X-Forwarded-For which is not a trusted proxy is used
+    pub(super) fn get_client_addr(&self, remote_addr: Option<IpAddr>, forwarded_for: Option<&str>) -> Option<IpAddr> {
+        let mut addr = remote_addr?;
+        if let Some(forwarded_for) = forwarded_for {
+            for item in forwarded_for.rsplit(',') {
+                if !self.trusted_proxies.contains(&addr) {
+                    break;
+                }
+                addr = item.trim().parse().ok()?;
+            }
+        }
+        Some(addr)
+    }
+}
+
+/// IP network in CIDR notation
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct IpNetwork {
+    addr: IpAddr,
+    prefix: u8,
+}
+
+impl IpNetwork {
+    /// Creates a new network
+    ///
+    /// # Arguments
+    ///
+    /// * addr - Network address
+    /// * prefix - Length of network prefix in bits
+    pub fn new(addr: IpAddr, prefix: u8) -> Result<Self, ParseIpNetworkError> {
+        let max_prefix = match addr {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+        if prefix > max_prefix {
+            Err(ParseIpNetworkError::BadPrefix(prefix.to_string()))
+        } else {
+            Ok(IpNetwork { addr, prefix })
+        }
+    }
+
+    /// Returns true if network contains given address
+    pub fn contains(&self, addr: IpAddr) -> bool {
+        match (self.addr, addr) {
+            (IpAddr::V4(network), IpAddr::V4(addr)) => {
+                let mask = mask_u32(self.prefix);
+                u32::from(network) & mask == u32::from(addr) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(addr)) => {
+                let mask = mask_u128(self.prefix);
+                u128::from(network) & mask == u128::from(addr) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V4(addr)) => IpNetwork {
+                addr: IpAddr::V6(network),
+                prefix: self.prefix,
+            }
+            .contains(IpAddr::V6(addr.to_ipv6_mapped())),
+            (IpAddr::V4(_), IpAddr::V6(addr)) => match to_ipv4_mapped(addr) {
+                Some(addr) => self.contains(IpAddr::V4(addr)),
+                None => false,
+            },
+        }
+    }
+}
+
+fn mask_u32(prefix: u8) -> u32 {
+    if prefix == 0 {
+        0
+    } else {
+        !0u32 << (32 - u32::from(prefix))
+    }
+}
+
+fn mask_u128(prefix: u8) -> u128 {
+    if prefix == 0 {
+        0
+    } else {
+        !0u128 << (128 - u32::from(prefix))
+    }
+}
+
+fn to_ipv4_mapped(addr: Ipv6Addr) -> Option<Ipv4Addr> {
+    match addr.segments() {
+        [0, 0, 0, 0, 0, 0xffff, hi, lo] => Some(Ipv4Addr::from((u32::from(hi) << 16) | u32::from(lo))),
+        _ => None,
+    }
+}
+
+impl FromStr for IpNetwork {
+    type Err = ParseIpNetworkError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.splitn(2, '/');
+        let addr = parts.next().unwrap_or("");
+        let addr: IpAddr = addr
+            .parse()
+            .map_err(|_| ParseIpNetworkError::BadAddress(addr.to_string()))?;
+        match parts.next() {
+            Some(prefix) => IpNetwork::new(
+                addr,
+                prefix
+                    .parse()
+                    .map_err(|_| ParseIpNetworkError::BadPrefix(prefix.to_string()))?,
+            ),
+            None => IpNetwork::new(
+                addr,
+                match addr {
+                    IpAddr::V4(_) => 32,
+                    IpAddr::V6(_) => 128,
+                },
+            ),
+        }
+    }
+}
+
+impl fmt::Display for IpNetwork {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        write!(out, "{}/{}", self.addr, self.prefix)
+    }
+}
+
+/// An error when parsing IP network
+#[derive(Debug, failure::Fail)]
+pub enum ParseIpNetworkError {
+    /// Can not parse network address
+    #[fail(display = "Bad network address: {}", _0)]
+    BadAddress(String),
+    /// Can not parse network prefix
+    #[fail(display = "Bad network prefix: {}", _0)]
+    BadPrefix(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ip_network() {
+        let network: IpNetwork = "149.154.160.0/20".parse().unwrap();
+        assert_eq!(network.to_string(), "149.154.160.0/20");
+        assert!(network.contains("149.154.167.220".parse().unwrap()));
+        assert!(network.contains("::ffff:149.154.167.220".parse().unwrap()));
+        assert!(!network.contains("149.154.176.1".parse().unwrap()));
+        assert!(!network.contains("::1".parse().unwrap()));
+
+        let network: IpNetwork = "2001:db8::/32".parse().unwrap();
+        assert!(network.contains("2001:db8::1".parse().unwrap()));
+        assert!(!network.contains("2001:db9::1".parse().unwrap()));
+
+        let network: IpNetwork = "127.0.0.1".parse().unwrap();
+        assert!(network.contains("127.0.0.1".parse().unwrap()));
+        assert!(!network.contains("127.0.0.2".parse().unwrap()));
+
+        let network: IpNetwork = "0.0.0.0/0".parse().unwrap();
+        assert!(network.contains("8.8.8.8".parse().unwrap()));
+
+        assert!("127.0.0.1/33".parse::<IpNetwork>().is_err());
+        assert!("localhost/8".parse::<IpNetwork>().is_err());
+    }
+
+    #[test]
+    fn test_options() {
+        let options = WebhookOptions::default();
+        assert!(options.is_secret_token_valid(None));
+        assert!(options.is_addr_allowed(None));
+
+        let proxy: IpAddr = "10.0.0.1".parse().unwrap();
+        let options = WebhookOptions::default()
+            .secret_token("secret")
+            .allow_telegram_networks()
+            .trust_proxy(proxy);
+        assert!(options.is_secret_token_valid(Some(b"secret")));
+        assert!(!options.is_secret_token_valid(Some(b"bad")));
+        assert!(!options.is_secret_token_valid(Some(b"secreT")));
+        assert!(!options.is_secret_token_valid(None));
+        assert!(options.is_addr_allowed(Some("91.108.4.1".parse().unwrap())));
+        assert!(!options.is_addr_allowed(Some("10.0.0.1".parse().unwrap())));
+        assert!(!options.is_addr_allowed(None));
+
+        let client: IpAddr = "91.108.4.1".parse().unwrap();
+        assert_eq!(options.get_client_addr(Some(client), None), Some(client));
+        assert_eq!(
+            options.get_client_addr(Some(client), Some("10.0.0.2")),
+            Some(client),
+            "X-Forwarded-For must be ignored when request does not come from a trusted proxy"
+        );
+        assert_eq!(
+            options.get_client_addr(Some(proxy), Some("1.1.1.1, 91.108.4.1")),
+            Some(client)
+        );
+        assert_eq!(
+            options.get_client_addr(Some(proxy), Some("91.108.4.1, 10.0.0.1")),
+            Some(client)
+        );
+        assert_eq!(options.get_client_addr(Some(proxy), Some("garbage")), None);
+    }
+}
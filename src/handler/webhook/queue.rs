@@ -0,0 +1,445 @@
+use crate::{
+    handler::{
+        parse_update,
+        webhook::{WebhookOptions, WebhookReply},
+    },
+    types::{Integer, TextParseMode, Update},
+};
+use futures::{
+    future::{self, Either},
+    sync::oneshot,
+    task::AtomicTask,
+    Async, AsyncSink, Future, Poll, Sink,
+};
+use hyper::StatusCode;
+use lazy_queue::sync::bounded::LazyQueue;
+use serde_json::Value;
+use std::{
+    collections::{HashSet, VecDeque},
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, BufReader, Lines, Write},
+    path::PathBuf,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+/// What to do with an update when webhook queue is full
+#[derive(Clone, Debug, Default)]
+pub enum QueueOverflow {
+    /// Wait for free space in the queue before responding to a webhook request (default)
+    #[default]
+    Block,
+    /// Respond with given status, so Telegram delivers the update later
+    ///
+    /// Use 429 Too Many Requests or 503 Service Unavailable
+    Reject(StatusCode),
+    /// Write updates to given file and put them on the queue when it has free space
+    ///
+    /// Updates left in the file are processed on next start
+    Spill(PathBuf),
+}
+
+/// An update with a channel for a reply
+pub(super) struct QueueItem {
+    pub(super) update: Update,
+    pub(super) raw: Option<Value>,
+    pub(super) reply: Option<oneshot::Sender<WebhookReply>>,
+}
+
+/// Result of putting an update on a queue
+pub(super) enum Pushed {
+    /// Update is on the queue and handler will receive the reply channel
+    Queued,
+    /// Update is accepted, but a handler will not reply to the request
+    Accepted,
+}
+
+/// A queue of updates received using webhook
+#[derive(Clone)]
+pub(super) struct UpdateQueue {
+    queue: LazyQueue<QueueItem>,
+    overflow: QueueOverflow,
+    spill: Option<Arc<Spill>>,
+    recent: Option<Arc<Mutex<RecentUpdates>>>,
+    parse_options: ParseOptions,
+}
+
+impl UpdateQueue {
+    pub(super) fn new(queue: LazyQueue<QueueItem>, options: &WebhookOptions) -> Result<Self, io::Error> {
+        let overflow = options.get_queue_overflow();
+        let spill = match overflow {
+            QueueOverflow::Spill(ref path) => Some(Arc::new(Spill::open(path.clone())?)),
+            _ => None,
+        };
+        Ok(UpdateQueue {
+            queue,
+            overflow,
+            spill,
+            recent: options
+                .get_recent_updates()
+                .map(|capacity| Arc::new(Mutex::new(RecentUpdates::new(capacity)))),
+            parse_options: ParseOptions {
+                keep_raw: options.is_keep_raw_updates(),
+                text_parse_mode: options.get_text_parse_mode(),
+            },
+        })
+    }
+
+    /// Parses an update from a request body
+    pub(super) fn parse(&self, body: &[u8]) -> Result<(Update, Option<Value>), serde_json::Error> {
+        self.parse_options.parse(body)
+    }
+
+    /// Returns a future which moves spilled updates to the queue
+    pub(super) fn drain_spill(&self) -> Option<DrainSpill> {
+        self.spill.as_ref().map(|spill| DrainSpill {
+            spill: spill.clone(),
+            queue: self.queue.clone(),
+            lines: None,
+            item: None,
+            parse_options: self.parse_options,
+        })
+    }
+
+    /// Puts an update on the queue according to overflow policy
+    ///
+    /// Resolves with a status to respond with when update is not accepted
+    pub(super) fn push(&self, body: &[u8], item: QueueItem) -> impl Future<Item = Pushed, Error = StatusCode> + Send {
+        let id = item.update.id;
+        if let Some(ref recent) = self.recent {
+            if !lock(recent).insert(id) {
+                log::info!("Skipping duplicate update: {}", id);
+                return Either::A(future::ok(Pushed::Accepted));
+            }
+        }
+        let recent = self.recent.clone();
+        let forget = move || {
+            if let Some(recent) = recent {
+                lock(&recent).remove(id);
+            }
+        };
+        let queue = self.queue.clone();
+        Either::B(match self.overflow {
+            QueueOverflow::Block => Either::A(queue.send(item).then(move |result| match result {
+                Ok(_) => Ok(Pushed::Queued),
+                Err(_) => {
+                    log::warn!("The receiving end has been dropped");
+                    forget();
+                    Err(StatusCode::INTERNAL_SERVER_ERROR)
+                }
+            })),
+            QueueOverflow::Reject(status) => {
+                Either::B(Either::A(try_send(queue, item).then(move |result| match result {
+                    Ok(None) => Ok(Pushed::Queued),
+                    Ok(Some(_)) => {
+                        log::warn!("Webhook queue is full, rejecting update: {}", id);
+                        forget();
+                        Err(status)
+                    }
+                    Err(()) => {
+                        log::warn!("The receiving end has been dropped");
+                        forget();
+                        Err(StatusCode::INTERNAL_SERVER_ERROR)
+                    }
+                })))
+            }
+            QueueOverflow::Spill(_) => {
+                let spill = self.spill.clone().expect("Spill is not opened");
+                let is_active = spill.is_active();
+                let body = body.to_vec();
+                let write = move || match spill.write(&body) {
+                    Ok(()) => Ok(Pushed::Accepted),
+                    Err(err) => {
+                        log::error!("Failed to spill update {}: {}", id, err);
+                        forget();
+                        Err(StatusCode::INTERNAL_SERVER_ERROR)
+                    }
+                };
+                // Updates go to the file while it is not empty to keep their order
+                if is_active {
+                    Either::B(Either::B(Either::A(future::result(write()))))
+                } else {
+                    Either::B(Either::B(Either::B(try_send(queue, item).then(
+                        move |result| match result {
+                            Ok(None) => Ok(Pushed::Queued),
+                            Ok(Some(_)) => write(),
+                            Err(()) => {
+                                log::warn!("The receiving end has been dropped");
+                                write()
+                            }
+                        },
+                    ))))
+                }
+            }
+        })
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+#[derive(Clone, Copy)]
+struct ParseOptions {
+    keep_raw: bool,
+    text_parse_mode: TextParseMode,
+}
+
+impl ParseOptions {
+    fn parse(self, data: &[u8]) -> Result<(Update, Option<Value>), serde_json::Error> {
+        parse_update(serde_json::from_slice(data)?, self.keep_raw, self.text_parse_mode)
+    }
+}
+
+/// Puts an item on a queue if it has free space
+///
+/// Resolves with the item back when queue is full
+fn try_send(queue: LazyQueue<QueueItem>, item: QueueItem) -> impl Future<Item = Option<QueueItem>, Error = ()> + Send {
+    let mut queue = queue;
+    let mut item = Some(item);
+    future::poll_fn(move || {
+        let value = item.take().expect("Polled after completion");
+        match queue.start_send(value) {
+            Ok(AsyncSink::Ready) => {
+                let _ = queue.poll_complete();
+                Ok(Async::Ready(None))
+            }
+            Ok(AsyncSink::NotReady(value)) => Ok(Async::Ready(Some(value))),
+            Err(_) => Err(()),
+        }
+    })
+}
+
+/// Recently received update IDs
+struct RecentUpdates {
+    capacity: usize,
+    ids: HashSet<Integer>,
+    order: VecDeque<Integer>,
+}
+
+impl RecentUpdates {
+    fn new(capacity: usize) -> Self {
+        RecentUpdates {
+            capacity,
+            ids: HashSet::with_capacity(capacity),
+            order: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Returns false if ID is already known
+    fn insert(&mut self, id: Integer) -> bool {
+        if !self.ids.insert(id) {
+            return false;
+        }
+        self.order.push_back(id);
+        while self.order.len() > self.capacity {
+            if let Some(old_id) = self.order.pop_front() {
+                self.ids.remove(&old_id);
+            }
+        }
+        true
+    }
+
+    fn remove(&mut self, id: Integer) {
+        if self.ids.remove(&id) {
+            self.order.retain(|x| *x != id);
+        }
+    }
+}
+
+/// A file with updates which did not fit in the queue
+///
+/// Updates are stored as JSON lines, the file is renamed when draining starts,
+/// so new updates are written to a new file
+struct Spill {
+    path: PathBuf,
+    draining_path: PathBuf,
+    state: Mutex<SpillState>,
+    task: AtomicTask,
+}
+
+struct SpillState {
+    pending: bool,
+    draining: bool,
+}
+
+impl Spill {
+    fn open(path: PathBuf) -> Result<Self, io::Error> {
+        let mut draining_path = path.clone().into_os_string();
+        draining_path.push(".draining");
+        let draining_path = PathBuf::from(draining_path);
+        let state = SpillState {
+            pending: is_not_empty(&path)?,
+            draining: is_not_empty(&draining_path)?,
+        };
+        Ok(Spill {
+            path,
+            draining_path,
+            state: Mutex::new(state),
+            task: AtomicTask::new(),
+        })
+    }
+
+    fn is_active(&self) -> bool {
+        let state = lock(&self.state);
+        state.pending || state.draining
+    }
+
+    fn write(&self, body: &[u8]) -> Result<(), io::Error> {
+        let value: Value = serde_json::from_slice(body)?;
+        let mut line = serde_json::to_vec(&value)?;
+        line.push(b'\n');
+        let mut state = lock(&self.state);
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?
+            .write_all(&line)?;
+        state.pending = true;
+        drop(state);
+        self.task.notify();
+        Ok(())
+    }
+
+    /// Moves pending updates to a draining file and opens it
+    fn start_draining(&self) -> Result<Option<Lines<BufReader<File>>>, io::Error> {
+        let mut state = lock(&self.state);
+        if !state.draining {
+            if !state.pending {
+                return Ok(None);
+            }
+            fs::rename(&self.path, &self.draining_path)?;
+            state.pending = false;
+            state.draining = true;
+        }
+        Ok(Some(BufReader::new(File::open(&self.draining_path)?).lines()))
+    }
+
+    fn finish_draining(&self) {
+        let mut state = lock(&self.state);
+        if let Err(err) = fs::remove_file(&self.draining_path) {
+            log::error!("Failed to remove {}: {}", self.draining_path.display(), err);
+        }
+        state.draining = false;
+    }
+}
+
+fn is_not_empty(path: &PathBuf) -> Result<bool, io::Error> {
+    match fs::metadata(path) {
+        Ok(metadata) => Ok(metadata.len() > 0),
+        Err(ref err) if err.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+/// Moves spilled updates to the queue
+pub(super) struct DrainSpill {
+    spill: Arc<Spill>,
+    queue: LazyQueue<QueueItem>,
+    lines: Option<Lines<BufReader<File>>>,
+    item: Option<QueueItem>,
+    parse_options: ParseOptions,
+}
+
+impl Future for DrainSpill {
+    type Item = ();
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        loop {
+            if let Some(item) = self.item.take() {
+                match self.queue.start_send(item) {
+                    Ok(AsyncSink::Ready) => {}
+                    Ok(AsyncSink::NotReady(item)) => {
+                        self.item = Some(item);
+                        return Ok(Async::NotReady);
+                    }
+                    Err(_) => {
+                        log::warn!("The receiving end has been dropped");
+                        return Err(());
+                    }
+                }
+                if let Err(err) = self.queue.poll_complete() {
+                    log::warn!("The receiving end has been dropped: {}", err);
+                    return Err(());
+                }
+            }
+            match self.lines {
+                Some(ref mut lines) => match lines.next() {
+                    Some(Ok(line)) => match self.parse_options.parse(line.as_bytes()) {
+                        Ok((update, raw)) => {
+                            self.item = Some(QueueItem {
+                                update,
+                                raw,
+                                reply: None,
+                            })
+                        }
+                        Err(err) => log::error!("Failed to parse spilled update: {}", err),
+                    },
+                    Some(Err(err)) => {
+                        log::error!("Failed to read spilled updates: {}", err);
+                        self.lines = None;
+                        self.spill.finish_draining();
+                    }
+                    None => {
+                        self.lines = None;
+                        self.spill.finish_draining();
+                    }
+                },
+                None => {
+                    self.spill.task.register();
+                    match self.spill.start_draining() {
+                        Ok(Some(lines)) => self.lines = Some(lines),
+                        Ok(None) => return Ok(Async::NotReady),
+                        Err(err) => {
+                            log::error!("Failed to drain spilled updates: {}", err);
+                            return Ok(Async::NotReady);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, process};
+
+    #[test]
+    fn test_recent_updates() {
+        let mut recent = RecentUpdates::new(2);
+        assert!(recent.insert(1));
+        assert!(!recent.insert(1));
+        assert!(recent.insert(2));
+        assert!(recent.insert(3));
+        assert!(recent.insert(1), "Oldest ID must be forgotten");
+        recent.remove(1);
+        assert!(recent.insert(1));
+        assert_eq!(recent.order.len(), 2);
+    }
+
+    #[test]
+    fn test_spill() {
+        let path = env::temp_dir().join(format!("tgbot-spill-{}", process::id()));
+        let spill = Spill::open(path.clone()).unwrap();
+        assert!(!spill.is_active());
+        spill.write(b"{\n\"update_id\": 1}").unwrap();
+        spill.write(b"{\"update_id\": 2}").unwrap();
+        assert!(spill.is_active());
+        assert!(Spill::open(path.clone()).unwrap().is_active());
+
+        let lines: Vec<String> = spill.start_draining().unwrap().unwrap().map(Result::unwrap).collect();
+        assert_eq!(lines, vec![r#"{"update_id":1}"#, r#"{"update_id":2}"#]);
+        assert!(spill.is_active());
+        spill.write(b"{\"update_id\": 3}").unwrap();
+        spill.finish_draining();
+        assert!(spill.is_active());
+
+        let lines: Vec<String> = spill.start_draining().unwrap().unwrap().map(Result::unwrap).collect();
+        assert_eq!(lines, vec![r#"{"update_id":3}"#]);
+        spill.finish_draining();
+        assert!(!spill.is_active());
+        assert!(spill.start_draining().unwrap().is_none());
+    }
+}
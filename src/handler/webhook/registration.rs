@@ -0,0 +1,281 @@
+use crate::{
+    api::Api,
+    handler::webhook::WebhookOptions,
+    methods::{DeleteWebhook, GetWebhookInfo, SetWebhook},
+    types::{AllowedUpdate, InputFile, Integer, WebhookInfo},
+};
+use futures::{
+    future::{self, Either},
+    Future, Stream,
+};
+use std::collections::HashSet;
+
+/// Registers webhook on start and optionally removes it on shutdown
+///
+/// SetWebhook is called with the URL and the options matching the webhook server
+/// (e.g. secret token), then GetWebhookInfo is called and the result is logged
+#[derive(Clone)]
+pub struct WebhookRegistration {
+    api: Api,
+    url: String,
+    certificate: Option<InputFile>,
+    max_connections: Option<Integer>,
+    allowed_updates: Option<HashSet<AllowedUpdate>>,
+    delete_on_shutdown: bool,
+}
+
+impl WebhookRegistration {
+    /// Creates a new registration
+    ///
+    /// # Arguments
+    ///
+    /// * api - Bot API client
+    /// * url - Public HTTPS url of the webhook, including path
+    pub fn new<S: Into<String>>(api: Api, url: S) -> Self {
+        WebhookRegistration {
+            api,
+            url: url.into(),
+            certificate: None,
+            max_connections: None,
+            allowed_updates: None,
+            delete_on_shutdown: false,
+        }
+    }
+
+    /// Public key certificate to upload, required for self-signed certificates
+    pub fn certificate(mut self, certificate: InputFile) -> Self {
+        self.certificate = Some(certificate);
+        self
+    }
+
+    /// Maximum allowed number of simultaneous HTTPS connections to the webhook, 1-100
+    pub fn max_connections(mut self, max_connections: Integer) -> Self {
+        self.max_connections = Some(max_connections);
+        self
+    }
+
+    /// List the types of updates you want your bot to receive
+    pub fn allowed_updates(mut self, allowed_updates: HashSet<AllowedUpdate>) -> Self {
+        self.allowed_updates = Some(allowed_updates);
+        self
+    }
+
+    /// Adds a type of updates you want your bot to receive
+    pub fn add_allowed_update(mut self, allowed_update: AllowedUpdate) -> Self {
+        self.allowed_updates
+            .get_or_insert_with(HashSet::new)
+            .insert(allowed_update);
+        self
+    }
+
+    /// Calls DeleteWebhook when server is stopped by SIGINT (Ctrl+C) or SIGTERM
+    ///
+    /// Server stops accepting new connections and waits for active ones before removing webhook
+    pub fn delete_on_shutdown(mut self, delete_on_shutdown: bool) -> Self {
+        self.delete_on_shutdown = delete_on_shutdown;
+        self
+    }
+
+    pub(crate) fn is_delete_on_shutdown(&self) -> bool {
+        self.delete_on_shutdown
+    }
+
+    /// Returns SetWebhook method matching given server options
+    pub fn get_set_webhook(&self, options: &WebhookOptions) -> SetWebhook {
+        let mut method = SetWebhook::new(self.url.clone());
+        if let Some(ref certificate) = self.certificate {
+            method = method.certificate(certificate.clone());
+        }
+        if let Some(max_connections) = self.max_connections {
+            method = method.max_connections(max_connections);
+        }
+        if let Some(ref allowed_updates) = self.allowed_updates {
+            method = method.allowed_updates(allowed_updates.clone());
+        }
+        if let Some(secret_token) = options.get_secret_token() {
+            method = method.secret_token(secret_token);
+        }
+        method
+    }
+
+    /// Returns a list of differences between the registration and actual webhook info
+    pub fn check_webhook_info(&self, info: &WebhookInfo) -> Vec<String> {
+        let mut problems = Vec::new();
+        if info.url != self.url {
+            problems.push(format!("URL is {:?}, expected {:?}", info.url, self.url));
+        }
+        if let Some(max_connections) = self.max_connections {
+            if info.max_connections != Some(max_connections) {
+                problems.push(format!(
+                    "max_connections is {:?}, expected {}",
+                    info.max_connections, max_connections
+                ));
+            }
+        }
+        if let Some(ref allowed_updates) = self.allowed_updates {
+            let actual: HashSet<AllowedUpdate> = info.allowed_updates.iter().cloned().collect();
+            // Telegram reports an empty list when all updates are allowed
+            if !(actual == *allowed_updates || actual.is_empty() && allowed_updates.is_empty()) {
+                problems.push(format!(
+                    "allowed_updates are {:?}, expected {:?}",
+                    info.allowed_updates, allowed_updates
+                ));
+            }
+        }
+        if self.certificate.is_some() && !info.has_custom_certificate {
+            problems.push(String::from("custom certificate is not set"));
+        }
+        problems
+    }
+
+    /// Sets webhook and logs its info
+    ///
+    /// Resolves with an error when SetWebhook fails
+    pub(crate) fn register(&self, options: &WebhookOptions) -> impl Future<Item = (), Error = ()> + Send {
+        let registration = self.clone();
+        let api = self.api.clone();
+        self.api
+            .execute(&self.get_set_webhook(options))
+            .map_err(|err| log::error!("Failed to set webhook: {}", err))
+            .and_then(move |_| {
+                log::info!("Webhook is set to {}", registration.url);
+                api.execute(&GetWebhookInfo).then(move |result| {
+                    match result {
+                        Ok(info) => registration.log_webhook_info(&info),
+                        Err(err) => log::warn!("Failed to get webhook info: {}", err),
+                    }
+                    Ok(())
+                })
+            })
+    }
+
+    fn log_webhook_info(&self, info: &WebhookInfo) {
+        log::info!("Pending updates: {}", info.pending_update_count);
+        if let Some(ref message) = info.last_error_message {
+            log::warn!(
+                "Last webhook error (date: {}): {}",
+                info.last_error_date.unwrap_or_default(),
+                message
+            );
+        }
+        for problem in self.check_webhook_info(info) {
+            log::warn!("Webhook mismatch: {}", problem);
+        }
+    }
+
+    /// Deletes webhook
+    pub(crate) fn unregister(&self) -> impl Future<Item = (), Error = ()> + Send {
+        self.api.execute(&DeleteWebhook).then(|result| {
+            match result {
+                Ok(_) => log::info!("Webhook has been deleted"),
+                Err(err) => log::error!("Failed to delete webhook: {}", err),
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Resolves when the process receives SIGINT (Ctrl+C) or SIGTERM
+///
+/// Never resolves if signal handlers can not be installed
+pub(crate) fn shutdown_signal() -> impl Future<Item = (), Error = ()> + Send {
+    fn first<S: Stream + Send + 'static>(stream: S) -> Box<dyn Future<Item = (), Error = S::Error> + Send> {
+        Box::new(stream.into_future().map(|_| ()).map_err(|(err, _)| err))
+    }
+
+    let ctrl_c = first(tokio_signal::ctrl_c().flatten_stream());
+    #[cfg(unix)]
+    let signal = {
+        use tokio_signal::unix::{Signal, SIGTERM};
+        Box::new(
+            ctrl_c
+                .select(first(Signal::new(SIGTERM).flatten_stream()))
+                .map(|_| ())
+                .map_err(|(err, _)| err),
+        )
+    };
+    #[cfg(not(unix))]
+    let signal = ctrl_c;
+    signal.then(|result| match result {
+        Ok(()) => {
+            log::info!("Shutting down");
+            Either::A(future::ok(()))
+        }
+        Err(err) => {
+            log::error!("Failed to listen for shutdown signals: {}", err);
+            Either::B(future::empty())
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::methods::{Method, RequestBody};
+    use serde_json::Value;
+
+    fn webhook_info(data: Value) -> WebhookInfo {
+        serde_json::from_value(data).unwrap()
+    }
+
+    #[test]
+    fn test_get_set_webhook() {
+        let api = Api::new("token", None::<&str>).unwrap();
+        let registration = WebhookRegistration::new(api, "https://example.com/hook")
+            .max_connections(10)
+            .add_allowed_update(AllowedUpdate::Message);
+        let options = WebhookOptions::default().secret_token("secret");
+        let req = registration
+            .get_set_webhook(&options)
+            .get_request()
+            .unwrap()
+            .build("token");
+        match req.body {
+            RequestBody::Json(data) => {
+                let data: Value = serde_json::from_slice(&data).unwrap();
+                assert_eq!(data["url"], "https://example.com/hook");
+                assert_eq!(data["max_connections"], 10);
+                assert_eq!(data["allowed_updates"], serde_json::json!(["message"]));
+                assert_eq!(data["secret_token"], "secret");
+            }
+            data => panic!("Unexpected request data: {:?}", data),
+        }
+    }
+
+    #[test]
+    fn test_check_webhook_info() {
+        let api = Api::new("token", None::<&str>).unwrap();
+        let registration = WebhookRegistration::new(api.clone(), "https://example.com/hook");
+        let info = webhook_info(serde_json::json!({
+            "url": "https://example.com/hook",
+            "has_custom_certificate": false,
+            "pending_update_count": 0,
+            "allowed_updates": []
+        }));
+        assert!(registration.check_webhook_info(&info).is_empty());
+
+        let registration = registration
+            .max_connections(10)
+            .allowed_updates(HashSet::new())
+            .certificate(InputFile::bytes("cert.pem", "cert"));
+        assert_eq!(registration.check_webhook_info(&info).len(), 2);
+
+        let registration = WebhookRegistration::new(api, "https://example.org/hook")
+            .max_connections(40)
+            .add_allowed_update(AllowedUpdate::Message);
+        let info = webhook_info(serde_json::json!({
+            "url": "https://example.com/hook",
+            "has_custom_certificate": false,
+            "pending_update_count": 10,
+            "max_connections": 40,
+            "allowed_updates": ["message", "callback_query"]
+        }));
+        assert_eq!(
+            registration.check_webhook_info(&info),
+            vec![
+                String::from(r#"URL is "https://example.com/hook", expected "https://example.org/hook""#),
+                String::from("allowed_updates are [Message, CallbackQuery], expected {Message}"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use crate::{
+    api::Api,
+    methods::{Method, RequestBody, RequestBuilder},
+};
+use failure::Error;
+use futures::{sync::oneshot, Async, Future, Poll};
+use serde_json::Value;
+use tokio_executor::spawn;
+use tokio_timer::Delay;
+
+/// A method to be sent as a response to a webhook request
+///
+/// Methods with file uploads can not be sent in a response,
+/// such methods are executed using Api
+#[derive(Clone, Debug)]
+pub struct WebhookReply {
+    request: RequestBuilder,
+    body: Option<Vec<u8>>,
+}
+
+impl WebhookReply {
+    /// Creates a reply from a method
+    ///
+    /// Returns an error if the method is not valid, see Method::validate()
+    pub fn new<M: Method>(method: &M) -> Result<Self, Error> {
+        method.validate()?;
+        let request = method.get_request()?;
+        let body = match request.get_body() {
+            RequestBody::Json(data) => match serde_json::from_slice(data)? {
+                Value::Object(mut map) => {
+                    map.insert(String::from("method"), Value::from(request.get_path()));
+                    Some(serde_json::to_vec(&map)?)
+                }
+                _ => None,
+            },
+            RequestBody::Empty => Some(serde_json::to_vec(&serde_json::json!({
+                "method": request.get_path()
+            }))?),
+            RequestBody::Form(_) => None,
+        };
+        Ok(WebhookReply { request, body })
+    }
+
+    /// Returns a JSON body for a webhook response if the method can be sent in a response
+    pub(crate) fn get_body(&self) -> Option<&[u8]> {
+        self.body.as_deref()
+    }
+}
+
+impl Method for WebhookReply {
+    type Response = Value;
+
+    fn get_request(&self) -> Result<RequestBuilder, Error> {
+        Ok(self.request.clone())
+    }
+}
+
+/// Sends a reply to a waiting webhook request or executes it when request is gone
+pub(crate) fn send_reply(
+    api: &Api,
+    result: Result<Option<WebhookReply>, Error>,
+    sender: Option<oneshot::Sender<WebhookReply>>,
+) {
+    let reply = match result {
+        Ok(Some(reply)) => reply,
+        Ok(None) => return,
+        Err(err) => {
+            log::error!("Failed to handle update: {}", err);
+            return;
+        }
+    };
+    let reply = match sender {
+        Some(sender) if reply.get_body().is_some() => match sender.send(reply) {
+            Ok(()) => return,
+            Err(reply) => reply,
+        },
+        _ => reply,
+    };
+    spawn(api.execute(&reply).then(|result| {
+        if let Err(err) = result {
+            log::error!("Failed to execute a reply: {}", err);
+        }
+        Ok(())
+    }));
+}
+
+/// Waits for a reply until deadline
+pub(super) struct WaitReply {
+    receiver: oneshot::Receiver<WebhookReply>,
+    delay: Delay,
+}
+
+impl WaitReply {
+    pub(super) fn new(receiver: oneshot::Receiver<WebhookReply>, delay: Delay) -> Self {
+        WaitReply { receiver, delay }
+    }
+}
+
+impl Future for WaitReply {
+    type Item = Option<WebhookReply>;
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        match self.receiver.poll() {
+            Ok(Async::Ready(reply)) => return Ok(Async::Ready(Some(reply))),
+            Ok(Async::NotReady) => {}
+            Err(oneshot::Canceled) => return Ok(Async::Ready(None)),
+        }
+        match self.delay.poll() {
+            Ok(Async::NotReady) => Ok(Async::NotReady),
+            result => {
+                if let Err(err) = result {
+                    log::error!("Timer error: {}", err);
+                }
+                // Sender gets the reply back after close, so it will be executed using Api,
+                // a reply sent right before close is taken here
+                self.receiver.close();
+                Ok(Async::Ready(self.receiver.try_recv().ok().and_then(|reply| reply)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::methods::{GetWebhookInfo, SendMessage, SetWebhook};
+    use crate::types::{InputFile, ValidationError};
+
+    #[test]
+    fn test_webhook_reply() {
+        let reply = WebhookReply::new(&SendMessage::new(1, "text")).unwrap();
+        let body: Value = serde_json::from_slice(reply.get_body().unwrap()).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "method": "sendMessage",
+                "chat_id": 1,
+                "text": "text"
+            })
+        );
+
+        let reply = WebhookReply::new(&GetWebhookInfo).unwrap();
+        let body: Value = serde_json::from_slice(reply.get_body().unwrap()).unwrap();
+        assert_eq!(body, serde_json::json!({"method": "getWebhookInfo"}));
+
+        let reply =
+            WebhookReply::new(&SetWebhook::new("url").certificate(InputFile::bytes("cert.pem", "cert"))).unwrap();
+        assert!(reply.get_body().is_none());
+
+        let err = WebhookReply::new(&SendMessage::new(1, "")).unwrap_err();
+        assert!(err.downcast_ref::<ValidationError>().is_some());
+    }
+}
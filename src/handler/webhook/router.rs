@@ -0,0 +1,182 @@
+use crate::{
+    handler::{
+        metrics::BotMetrics,
+        webhook::{empty_response, RemoteAddr, WebhookServiceFactory},
+    },
+    Never,
+};
+use futures::{future::ok, Future};
+use hyper::{
+    service::{MakeService, Service},
+    Body, Error, Request, Response, StatusCode,
+};
+use openssl::sha::sha256;
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{Arc, Mutex, RwLock},
+};
+
+/// Routes webhook requests of multiple bots by URL path
+///
+/// Each bot is served by its own WebhookServiceFactory, bots can be added and removed at runtime
+/// Clones of the router share the same set of bots
+#[derive(Clone, Default)]
+pub struct WebhookRouter {
+    bots: Arc<RwLock<HashMap<String, Arc<RouterEntry>>>>,
+}
+
+struct RouterEntry {
+    factory: Mutex<WebhookServiceFactory>,
+    metrics: Arc<BotMetrics>,
+}
+
+impl WebhookRouter {
+    /// Creates a router without bots
+    pub fn new() -> Self {
+        WebhookRouter::default()
+    }
+
+    /// Returns a URL path containing SHA-256 hash of a bot token
+    ///
+    /// Such path is unique for each bot and does not expose the token
+    pub fn token_path<S: AsRef<str>>(token: S) -> String {
+        let hash = sha256(token.as_ref().as_bytes());
+        let mut path = String::from("/");
+        for byte in hash.iter() {
+            path += &format!("{:02x}", byte);
+        }
+        path
+    }
+
+    /// Adds a bot served on the path of given factory
+    ///
+    /// A bot with the same path is replaced
+    /// Returns metrics of the bot
+    pub fn add_bot(&self, factory: WebhookServiceFactory) -> Arc<BotMetrics> {
+        let path = factory.get_path().to_string();
+        let metrics = Arc::new(BotMetrics::default());
+        let entry = Arc::new(RouterEntry {
+            factory: Mutex::new(factory),
+            metrics: metrics.clone(),
+        });
+        if self.write().insert(path.clone(), entry).is_some() {
+            log::info!("Replaced bot on {}", path);
+        }
+        metrics
+    }
+
+    /// Removes a bot with given path
+    ///
+    /// Updates which are already received are still processed
+    /// Returns false if there is no such bot
+    pub fn remove_bot(&self, path: &str) -> bool {
+        self.write().remove(path).is_some()
+    }
+
+    /// Returns paths of all bots
+    pub fn paths(&self) -> Vec<String> {
+        self.read().keys().cloned().collect()
+    }
+
+    /// Returns metrics of a bot with given path
+    pub fn metrics(&self, path: &str) -> Option<Arc<BotMetrics>> {
+        self.read().get(path).map(|entry| entry.metrics.clone())
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, HashMap<String, Arc<RouterEntry>>> {
+        self.bots.read().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, HashMap<String, Arc<RouterEntry>>> {
+        self.bots.write().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl<'a, C: RemoteAddr> MakeService<&'a C> for WebhookRouter {
+    type ReqBody = Body;
+    type ResBody = Body;
+    type Error = Error;
+    type Service = WebhookRouterService;
+    type Future = Box<dyn Future<Item = Self::Service, Error = Self::MakeError> + Send>;
+    type MakeError = Never;
+
+    fn make_service(&mut self, ctx: &'a C) -> Self::Future {
+        Box::new(ok(WebhookRouterService {
+            router: self.clone(),
+            remote_addr: ctx.remote_addr(),
+        }))
+    }
+}
+
+/// A service routing webhook requests to bots
+pub struct WebhookRouterService {
+    router: WebhookRouter,
+    remote_addr: Option<SocketAddr>,
+}
+
+impl Service for WebhookRouterService {
+    type ReqBody = Body;
+    type ResBody = Body;
+    type Error = Error;
+    type Future = Box<dyn Future<Item = Response<Body>, Error = Error> + Send>;
+
+    fn call(&mut self, req: Request<Self::ReqBody>) -> Self::Future {
+        let entry = self.router.read().get(req.uri().path()).cloned();
+        match entry {
+            Some(entry) => {
+                let mut service = entry
+                    .factory
+                    .lock()
+                    .unwrap_or_else(|err| err.into_inner())
+                    .make_webhook_service(self.remote_addr);
+                let metrics = entry.metrics.clone();
+                Box::new(service.call(req).map(move |rep| {
+                    if rep.status().is_success() {
+                        metrics.add_update();
+                    } else {
+                        metrics.add_error();
+                    }
+                    rep
+                }))
+            }
+            None => Box::new(ok(empty_response(StatusCode::NOT_FOUND))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{types::Update, UpdateHandler};
+
+    struct Handler;
+
+    impl UpdateHandler for Handler {
+        fn handle(&mut self, _update: Update) {}
+    }
+
+    #[test]
+    fn test_token_path() {
+        let path = WebhookRouter::token_path("token");
+        assert_eq!(
+            path,
+            "/3c469e9d6c5875d37a43f353d4f88e61fcf812c66eee3457465a40b0da4153e0"
+        );
+    }
+
+    #[test]
+    fn test_router_bots() {
+        let router = WebhookRouter::new();
+        let metrics = router.add_bot(WebhookServiceFactory::new("/bot1", Handler));
+        router.clone().add_bot(WebhookServiceFactory::new("/bot2", Handler));
+        let mut paths = router.paths();
+        paths.sort();
+        assert_eq!(paths, vec!["/bot1", "/bot2"]);
+        assert!(Arc::ptr_eq(&router.metrics("/bot1").unwrap(), &metrics));
+        assert!(router.remove_bot("/bot1"));
+        assert!(!router.remove_bot("/bot1"));
+        assert!(router.metrics("/bot1").is_none());
+        assert_eq!(router.paths(), vec!["/bot2"]);
+    }
+}
@@ -0,0 +1,248 @@
+use crate::{handler::webhook::RemoteAddr, methods::SetWebhook, types::InputFile};
+use failure::Error;
+use futures::{Future, Stream};
+use native_tls::{Identity, TlsAcceptor as NativeTlsAcceptor};
+use openssl::{
+    asn1::Asn1Time,
+    bn::{BigNum, MsbOption},
+    hash::MessageDigest,
+    pkey::PKey,
+    rsa::Rsa,
+    x509::{extension::SubjectAlternativeName, X509NameBuilder, X509},
+};
+use std::{fmt, fs, io, net::IpAddr, net::SocketAddr, path::Path, time::Duration};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_timer::Timeout;
+use tokio_tls::{TlsAcceptor, TlsStream};
+
+const CERTIFICATE_DAYS: u32 = 365;
+const RSA_BITS: u32 = 2048;
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_PENDING_HANDSHAKES: usize = 128;
+
+/// TLS settings for a webhook server
+#[derive(Clone)]
+pub struct WebhookTls {
+    acceptor: TlsAcceptor,
+}
+
+impl WebhookTls {
+    /// Creates TLS settings from a PEM-encoded certificate (chain) and a private key
+    pub fn from_pem(certificate: &[u8], private_key: &[u8]) -> Result<Self, Error> {
+        // native-tls accepts PKCS#8 keys only, so PKCS#1 keys are converted first
+        let private_key = PKey::private_key_from_pem(private_key)?.private_key_to_pem_pkcs8()?;
+        let identity = Identity::from_pkcs8(certificate, &private_key)?;
+        Ok(WebhookTls {
+            acceptor: NativeTlsAcceptor::new(identity)?.into(),
+        })
+    }
+
+    /// Creates TLS settings from PEM files with a certificate (chain) and a private key
+    pub fn from_pem_files<C, K>(certificate: C, private_key: K) -> Result<Self, Error>
+    where
+        C: AsRef<Path>,
+        K: AsRef<Path>,
+    {
+        WebhookTls::from_pem(&fs::read(certificate)?, &fs::read(private_key)?)
+    }
+
+    /// Returns a stream of accepted TLS connections
+    ///
+    /// Handshakes run concurrently, failed and timed out handshakes are logged and skipped
+    pub(crate) fn incoming(
+        &self,
+        addr: &SocketAddr,
+    ) -> Result<impl Stream<Item = TlsStream<TcpStream>, Error = io::Error>, io::Error> {
+        let acceptor = self.acceptor.clone();
+        Ok(TcpListener::bind(addr)?
+            .incoming()
+            .then(|result| match result {
+                Ok(stream) => Ok(Some(stream)),
+                Err(err) => {
+                    log::error!("Failed to accept a connection: {}", err);
+                    Ok(None)
+                }
+            })
+            .filter_map(|stream| stream)
+            .map(move |stream| {
+                Timeout::new(acceptor.accept(stream), HANDSHAKE_TIMEOUT).then(|result| match result {
+                    Ok(stream) => Ok(Some(stream)),
+                    Err(ref err) if err.is_elapsed() => {
+                        log::warn!("TLS handshake timed out");
+                        Ok(None)
+                    }
+                    Err(err) => {
+                        log::warn!("TLS handshake failed: {}", err);
+                        Ok(None)
+                    }
+                })
+            })
+            .buffer_unordered(MAX_PENDING_HANDSHAKES)
+            .filter_map(|stream| stream))
+    }
+}
+
+impl fmt::Debug for WebhookTls {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        write!(out, "WebhookTls")
+    }
+}
+
+impl RemoteAddr for TlsStream<TcpStream> {
+    fn remote_addr(&self) -> Option<SocketAddr> {
+        self.get_ref().get_ref().peer_addr().ok()
+    }
+}
+
+/// Self-signed certificate for a webhook server
+///
+/// Telegram accepts self-signed certificates when they are uploaded using SetWebhook
+#[derive(Clone)]
+pub struct SelfSignedCertificate {
+    certificate: Vec<u8>,
+    private_key: Vec<u8>,
+}
+
+impl SelfSignedCertificate {
+    /// Generates a new certificate
+    ///
+    /// # Arguments
+    ///
+    /// * host - Domain name or IP address of the webhook server,
+    ///          must match the host in the webhook URL
+    pub fn generate<S: AsRef<str>>(host: S) -> Result<Self, Error> {
+        let host = host.as_ref();
+        let key = PKey::from_rsa(Rsa::generate(RSA_BITS)?)?;
+
+        let mut name = X509NameBuilder::new()?;
+        name.append_entry_by_text("CN", host)?;
+        let name = name.build();
+
+        let mut serial_number = BigNum::new()?;
+        serial_number.rand(128, MsbOption::MAYBE_ZERO, false)?;
+
+        let mut builder = X509::builder()?;
+        builder.set_version(2)?;
+        let serial_number = serial_number.to_asn1_integer()?;
+        let not_before = Asn1Time::days_from_now(0)?;
+        let not_after = Asn1Time::days_from_now(CERTIFICATE_DAYS)?;
+        builder.set_serial_number(&serial_number)?;
+        builder.set_subject_name(&name)?;
+        builder.set_issuer_name(&name)?;
+        builder.set_pubkey(&key)?;
+        builder.set_not_before(&not_before)?;
+        builder.set_not_after(&not_after)?;
+        let mut alt_name = SubjectAlternativeName::new();
+        if host.parse::<IpAddr>().is_ok() {
+            alt_name.ip(host);
+        } else {
+            alt_name.dns(host);
+        }
+        let alt_name = alt_name.build(&builder.x509v3_context(None, None))?;
+        builder.append_extension(alt_name)?;
+        builder.sign(&key, MessageDigest::sha256())?;
+
+        Ok(SelfSignedCertificate {
+            certificate: builder.build().to_pem()?,
+            private_key: key.private_key_to_pem_pkcs8()?,
+        })
+    }
+
+    /// PEM-encoded certificate
+    pub fn certificate_pem(&self) -> &[u8] {
+        &self.certificate
+    }
+
+    /// PEM-encoded private key
+    pub fn private_key_pem(&self) -> &[u8] {
+        &self.private_key
+    }
+
+    /// Saves PEM-encoded certificate and private key to given files
+    pub fn save<C, K>(&self, certificate: C, private_key: K) -> Result<(), io::Error>
+    where
+        C: AsRef<Path>,
+        K: AsRef<Path>,
+    {
+        fs::write(certificate, &self.certificate)?;
+        fs::write(private_key, &self.private_key)
+    }
+
+    /// Returns TLS settings for a webhook server
+    pub fn tls(&self) -> Result<WebhookTls, Error> {
+        WebhookTls::from_pem(&self.certificate, &self.private_key)
+    }
+
+    /// Returns SetWebhook method with the certificate attached
+    ///
+    /// # Arguments
+    ///
+    /// * url - HTTPS url to send updates to
+    pub fn set_webhook<S: Into<String>>(&self, url: S) -> SetWebhook {
+        SetWebhook::new(url).certificate(InputFile::bytes("certificate.pem", self.certificate.clone()))
+    }
+}
+
+impl fmt::Debug for SelfSignedCertificate {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        write!(out, "SelfSignedCertificate")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_self_signed_certificate() {
+        for host in &["example.com", "127.0.0.1"] {
+            let certificate = SelfSignedCertificate::generate(host).unwrap();
+            let x509 = X509::from_pem(certificate.certificate_pem()).unwrap();
+            let common_name = x509.subject_name().entries().next().unwrap().data().as_slice();
+            assert_eq!(common_name, host.as_bytes());
+            assert!(certificate.tls().is_ok());
+        }
+    }
+
+    #[test]
+    fn test_pkcs1_private_key() {
+        let certificate = SelfSignedCertificate::generate("example.com").unwrap();
+        let private_key = PKey::private_key_from_pem(certificate.private_key_pem())
+            .unwrap()
+            .rsa()
+            .unwrap()
+            .private_key_to_pem()
+            .unwrap();
+        assert!(WebhookTls::from_pem(certificate.certificate_pem(), &private_key).is_ok());
+    }
+
+    #[test]
+    fn test_concurrent_handshakes() {
+        use native_tls::TlsConnector;
+        use std::{io::Write, net::TcpStream as StdTcpStream, thread};
+        use tokio::runtime::Runtime;
+
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let tls = SelfSignedCertificate::generate("127.0.0.1").unwrap().tls().unwrap();
+        let incoming = tls.incoming(&addr).unwrap();
+        let client = thread::spawn(move || {
+            // A client which never finishes the handshake must not block other connections
+            let _stalled = StdTcpStream::connect(addr).unwrap();
+            let connector = TlsConnector::builder()
+                .danger_accept_invalid_certs(true)
+                .build()
+                .unwrap();
+            let mut stream = connector
+                .connect("127.0.0.1", StdTcpStream::connect(addr).unwrap())
+                .unwrap();
+            stream.write_all(b"ping").unwrap();
+        });
+        let accepted = Timeout::new(incoming.into_future().map_err(|(err, _)| err), Duration::from_secs(5));
+        let (stream, _) = Runtime::new().unwrap().block_on(accepted).unwrap();
+        assert!(stream.is_some());
+        client.join().unwrap();
+    }
+}
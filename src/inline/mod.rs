@@ -0,0 +1,263 @@
+use crate::{
+    api::{Api, ApiFuture},
+    methods::AnswerInlineQuery,
+    types::{InlineQuery, InlineQueryResult, Integer},
+};
+use failure::Error;
+use futures::{future, Future, IntoFuture, Stream};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Maximum number of results in an answer to an inline query
+pub const MAX_PAGE_SIZE: usize = 50;
+
+const DEFAULT_MAX_RESULTS: usize = 1000;
+const DEFAULT_MAX_ENTRIES: usize = 1000;
+const DEFAULT_TTL: Duration = Duration::from_secs(300);
+const MAX_ID_LENGTH: usize = 64;
+
+type ResultsFuture = Box<dyn Future<Item = Vec<InlineQueryResult>, Error = Error> + Send>;
+type ResultsProvider = dyn Fn(&InlineQuery, usize) -> ResultsFuture + Send + Sync;
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct CacheKey {
+    user_id: Option<Integer>,
+    query: String,
+}
+
+struct CacheEntry {
+    results: Arc<Vec<InlineQueryResult>>,
+    expires_at: Instant,
+}
+
+/// Answers inline queries page by page
+///
+/// Results returned by the provider are cached per query string,
+/// next_offset of an answer points to the next page in the cached results
+/// Results with duplicate or invalid identifiers are skipped,
+/// so identifiers are unique across all pages
+/// Clones share the same cache and provider
+#[derive(Clone)]
+pub struct InlinePager {
+    provider: Arc<ResultsProvider>,
+    cache: Arc<Mutex<HashMap<CacheKey, CacheEntry>>>,
+    page_size: usize,
+    max_results: usize,
+    max_entries: usize,
+    ttl: Duration,
+    personal: bool,
+    cache_time: Option<Integer>,
+}
+
+impl InlinePager {
+    /// Creates a new pager
+    ///
+    /// # Arguments
+    ///
+    /// * provider - Returns all results for a query, the iterator is consumed lazily up to max_results
+    pub fn new<F, R, I>(provider: F) -> Self
+    where
+        F: Fn(&InlineQuery) -> R + Send + Sync + 'static,
+        R: IntoFuture<Item = I, Error = Error>,
+        R::Future: Send + 'static,
+        I: IntoIterator<Item = InlineQueryResult>,
+    {
+        InlinePager::from_provider(Arc::new(move |query: &InlineQuery, max_results: usize| {
+            Box::new(provider(query).into_future().map(move |results| {
+                let mut filter = ResultsFilter::default();
+                results
+                    .into_iter()
+                    .filter(|result| filter.accept(result))
+                    .take(max_results)
+                    .collect()
+            })) as ResultsFuture
+        }))
+    }
+
+    /// Creates a new pager with a provider which returns a stream of results
+    ///
+    /// The stream is polled up to max_results
+    pub fn with_stream<F, S>(provider: F) -> Self
+    where
+        F: Fn(&InlineQuery) -> S + Send + Sync + 'static,
+        S: Stream<Item = InlineQueryResult, Error = Error> + Send + 'static,
+    {
+        InlinePager::from_provider(Arc::new(move |query: &InlineQuery, max_results: usize| {
+            let mut filter = ResultsFilter::default();
+            Box::new(
+                provider(query)
+                    .filter(move |result| filter.accept(result))
+                    .take(max_results as u64)
+                    .collect(),
+            ) as ResultsFuture
+        }))
+    }
+
+    fn from_provider(provider: Arc<ResultsProvider>) -> Self {
+        InlinePager {
+            provider,
+            cache: Arc::new(Mutex::new(HashMap::new())),
+            page_size: MAX_PAGE_SIZE,
+            max_results: DEFAULT_MAX_RESULTS,
+            max_entries: DEFAULT_MAX_ENTRIES,
+            ttl: DEFAULT_TTL,
+            personal: false,
+            cache_time: None,
+        }
+    }
+
+    /// Number of results in a page, 1-50, defaults to 50
+    pub fn page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size.clamp(1, MAX_PAGE_SIZE);
+        self
+    }
+
+    /// Maximum number of results for a query, defaults to 1000
+    pub fn max_results(mut self, max_results: usize) -> Self {
+        self.max_results = max_results;
+        self
+    }
+
+    /// Maximum number of cached queries, defaults to 1000
+    ///
+    /// When the cache is full, the entry which expires first is evicted
+    pub fn max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries.max(1);
+        self
+    }
+
+    /// How long results are cached, defaults to 5 minutes
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Cache results per user and ask Telegram to do the same
+    pub fn personal(mut self, personal: bool) -> Self {
+        self.personal = personal;
+        self
+    }
+
+    /// Maximum amount of time in seconds that Telegram may cache the answers
+    pub fn cache_time(mut self, cache_time: Integer) -> Self {
+        self.cache_time = Some(cache_time);
+        self
+    }
+
+    /// Returns an answer with a page of results requested by the query offset
+    pub fn get_answer(&self, query: &InlineQuery) -> ApiFuture<AnswerInlineQuery> {
+        let key = CacheKey {
+            user_id: if self.personal { Some(query.from.id) } else { None },
+            query: query.query.clone(),
+        };
+        let pager = self.clone();
+        let (id, offset) = (query.id.clone(), parse_offset(&query.offset));
+        let results = match self.get_cached(&key) {
+            Some(results) => future::Either::A(future::ok(results)),
+            None => {
+                let cache = self.clone();
+                future::Either::B((self.provider)(query, self.max_results).map(move |results| {
+                    let results = Arc::new(results);
+                    cache.put(key, results.clone());
+                    results
+                }))
+            }
+        };
+        ApiFuture::new(results.map(move |results| pager.get_page(id, &results, offset)))
+    }
+
+    /// Answers an inline query with a page of results
+    pub fn answer(&self, api: &Api, query: &InlineQuery) -> ApiFuture<bool> {
+        let api = api.clone();
+        ApiFuture::new(self.get_answer(query).and_then(move |answer| api.execute(&answer)))
+    }
+
+    /// Removes all cached results
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn get_page(&self, id: String, results: &[InlineQueryResult], offset: usize) -> AnswerInlineQuery {
+        let start = offset.min(results.len());
+        let end = (start + self.page_size).min(results.len());
+        let mut answer = AnswerInlineQuery::new(id, results[start..end].to_vec());
+        // An empty next_offset tells Telegram that there are no more results
+        answer = answer.next_offset(if end < results.len() {
+            end.to_string()
+        } else {
+            String::new()
+        });
+        if self.personal {
+            answer = answer.personal(true);
+        }
+        if let Some(cache_time) = self.cache_time {
+            answer = answer.cache_time(cache_time);
+        }
+        answer
+    }
+
+    fn get_cached(&self, key: &CacheKey) -> Option<Arc<Vec<InlineQueryResult>>> {
+        self.lock()
+            .get(key)
+            .filter(|entry| entry.expires_at > Instant::now())
+            .map(|entry| entry.results.clone())
+    }
+
+    fn put(&self, key: CacheKey, results: Arc<Vec<InlineQueryResult>>) {
+        let now = Instant::now();
+        let mut cache = self.lock();
+        cache.retain(|_, entry| entry.expires_at > now);
+        while cache.len() >= self.max_entries && !cache.contains_key(&key) {
+            let oldest = cache
+                .iter()
+                .min_by_key(|(_, entry)| entry.expires_at)
+                .map(|(key, _)| key.clone());
+            match oldest {
+                Some(oldest) => cache.remove(&oldest),
+                None => break,
+            };
+        }
+        cache.insert(
+            key,
+            CacheEntry {
+                results,
+                expires_at: now + self.ttl,
+            },
+        );
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<CacheKey, CacheEntry>> {
+        self.cache.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+fn parse_offset(offset: &str) -> usize {
+    offset.parse().unwrap_or(0)
+}
+
+/// Skips results with invalid or duplicate identifiers
+#[derive(Default)]
+struct ResultsFilter {
+    ids: HashSet<String>,
+}
+
+impl ResultsFilter {
+    fn accept(&mut self, result: &InlineQueryResult) -> bool {
+        let id = result.get_id();
+        if id.is_empty() || id.len() > MAX_ID_LENGTH {
+            log::warn!("Skipping inline query result with invalid id: {:?}", id);
+            false
+        } else if !self.ids.insert(id.to_string()) {
+            log::warn!("Skipping inline query result with duplicate id: {:?}", id);
+            false
+        } else {
+            true
+        }
+    }
+}
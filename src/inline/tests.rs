@@ -0,0 +1,126 @@
+use crate::{
+    inline::*,
+    methods::{Method, RequestBody},
+    types::*,
+};
+use futures::{stream, Future};
+use serde_json::{json, Value};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+fn get_query(user_id: Integer, query: &str, offset: &str) -> InlineQuery {
+    serde_json::from_value(json!({
+        "id": "query-id",
+        "from": {"id": user_id, "is_bot": false, "first_name": "test"},
+        "query": query,
+        "offset": offset
+    }))
+    .unwrap()
+}
+
+fn get_result(id: &str) -> InlineQueryResult {
+    InlineQueryResultArticle::new(id, "title", InputMessageContentText::new("text").into()).into()
+}
+
+fn get_answer(pager: &InlinePager, query: &InlineQuery) -> (Vec<String>, Value) {
+    let answer = pager.get_answer(query).wait().unwrap();
+    let data: Value = match answer.get_request().unwrap().build("token").body {
+        RequestBody::Json(data) => serde_json::from_slice(&data).unwrap(),
+        body => panic!("Unexpected request body: {:?}", body),
+    };
+    let ids = data["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|result| String::from(result["id"].as_str().unwrap()))
+        .collect();
+    (ids, data)
+}
+
+#[test]
+fn test_pagination() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let provider_calls = calls.clone();
+    let pager = InlinePager::new(move |query: &InlineQuery| {
+        provider_calls.fetch_add(1, Ordering::SeqCst);
+        let query = query.query.clone();
+        let results: Vec<_> = (0..7)
+            .map(|idx| get_result(&format!("{}-{}", query, idx % 5)))
+            .chain(vec![get_result(""), get_result(&"x".repeat(65))])
+            .chain((5..8).map(|idx| get_result(&format!("{}-{}", query, idx))))
+            .collect();
+        Ok(results)
+    })
+    .page_size(3)
+    .cache_time(10);
+
+    let (ids, data) = get_answer(&pager, &get_query(1, "q", ""));
+    assert_eq!(ids, vec!["q-0", "q-1", "q-2"]);
+    assert_eq!(data["next_offset"], "3");
+    assert_eq!(data["cache_time"], 10);
+    assert!(data.get("is_personal").is_none());
+    let (ids, data) = get_answer(&pager, &get_query(2, "q", "3"));
+    assert_eq!(ids, vec!["q-3", "q-4", "q-5"]);
+    assert_eq!(data["next_offset"], "6");
+    let (ids, data) = get_answer(&pager, &get_query(1, "q", "6"));
+    assert_eq!(ids, vec!["q-6", "q-7"]);
+    assert_eq!(data["next_offset"], "");
+    let (ids, data) = get_answer(&pager, &get_query(1, "q", "100"));
+    assert!(ids.is_empty());
+    assert_eq!(data["next_offset"], "");
+    let (ids, _) = get_answer(&pager, &get_query(1, "q", "invalid"));
+    assert_eq!(ids, vec!["q-0", "q-1", "q-2"]);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    let (ids, _) = get_answer(&pager, &get_query(1, "other", ""));
+    assert_eq!(ids, vec!["other-0", "other-1", "other-2"]);
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    pager.clear();
+    get_answer(&pager, &get_query(1, "q", "3"));
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+    let pager = pager.ttl(Duration::from_secs(0)).personal(true).max_results(4);
+    let (ids, data) = get_answer(&pager, &get_query(1, "q", "3"));
+    assert_eq!(ids, vec!["q-3"]);
+    assert_eq!(data["next_offset"], "");
+    assert_eq!(data["is_personal"], true);
+    get_answer(&pager, &get_query(1, "q", ""));
+    assert_eq!(calls.load(Ordering::SeqCst), 5);
+}
+
+#[test]
+fn test_stream_provider() {
+    let pager = InlinePager::with_stream(|_: &InlineQuery| {
+        stream::iter_ok((0..).map(|idx| get_result(&(idx / 2).to_string())))
+    })
+    .max_results(60);
+    let (ids, data) = get_answer(&pager, &get_query(1, "", ""));
+    assert_eq!(ids.len(), MAX_PAGE_SIZE);
+    assert_eq!(ids[49], "49");
+    assert_eq!(data["next_offset"], "50");
+    let (ids, data) = get_answer(&pager, &get_query(1, "", "50"));
+    assert_eq!(ids.len(), 10);
+    assert_eq!(data["next_offset"], "");
+}
+
+#[test]
+fn test_cache_eviction() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let provider_calls = calls.clone();
+    let pager = InlinePager::new(move |query: &InlineQuery| {
+        provider_calls.fetch_add(1, Ordering::SeqCst);
+        Ok(vec![get_result(&query.query)])
+    })
+    .max_entries(2);
+    for query in &["a", "b", "c", "b", "c"] {
+        get_answer(&pager, &get_query(1, query, ""));
+    }
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+    get_answer(&pager, &get_query(1, "a", ""));
+    assert_eq!(calls.load(Ordering::SeqCst), 4);
+}
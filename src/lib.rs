@@ -16,5 +16,23 @@ pub mod types;
 /// A "prelude" for users of the library
 pub mod prelude;
 
+/// HTML5 games launch and high scores
+pub mod games;
+
+/// Inline mode helpers
+pub mod inline;
+
+/// Telegram Passport decryption
+pub mod passport;
+
+/// Payment flow orchestration
+pub mod payments;
+
+/// Sticker set management
+pub mod stickers;
+
+/// Reusable interactive widgets built on inline keyboards
+pub mod widgets;
+
 pub use self::{api::*, handler::*};
 use never::Never;
@@ -1,4 +1,7 @@
-use crate::{methods::method::*, types::Integer};
+use crate::{
+    methods::method::*,
+    types::{check_length, Integer, ValidationError},
+};
 use failure::Error;
 use serde::Serialize;
 
@@ -81,4 +84,11 @@ impl Method for AnswerCallbackQuery {
     fn get_request(&self) -> Result<RequestBuilder, Error> {
         RequestBuilder::json("answerCallbackQuery", &self)
     }
+
+    fn validate(&self) -> Result<(), ValidationError> {
+        if let Some(ref text) = self.text {
+            check_length("text", text, None, 0, 200)?;
+        }
+        Ok(())
+    }
 }
@@ -1,6 +1,6 @@
 use crate::{
     methods::method::*,
-    types::{InlineQueryResult, Integer},
+    types::{check_bytes, check_count, InlineQueryResult, Integer, ValidationError},
 };
 use failure::Error;
 use serde::Serialize;
@@ -100,4 +100,24 @@ impl Method for AnswerInlineQuery {
     fn get_request(&self) -> Result<RequestBuilder, Error> {
         RequestBuilder::json("answerInlineQuery", &self)
     }
+
+    fn validate(&self) -> Result<(), ValidationError> {
+        check_count("results", self.results.len(), 0, 50)?;
+        if let Some(ref next_offset) = self.next_offset {
+            check_bytes("next_offset", next_offset, 0, 64)?;
+        }
+        if let Some(ref switch_pm_parameter) = self.switch_pm_parameter {
+            check_bytes("switch_pm_parameter", switch_pm_parameter, 1, 64)?;
+            if !switch_pm_parameter
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            {
+                return Err(ValidationError::new(
+                    "switch_pm_parameter",
+                    "only A-Z, a-z, 0-9, _ and - are allowed",
+                ));
+            }
+        }
+        Ok(())
+    }
 }
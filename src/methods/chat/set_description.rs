@@ -1,4 +1,7 @@
-use crate::{methods::method::*, types::ChatId};
+use crate::{
+    methods::method::*,
+    types::{check_length, ChatId, ValidationError},
+};
 use failure::Error;
 use serde::Serialize;
 
@@ -39,4 +42,11 @@ impl Method for SetChatDescription {
     fn get_request(&self) -> Result<RequestBuilder, Error> {
         RequestBuilder::json("setChatDescription", &self)
     }
+
+    fn validate(&self) -> Result<(), ValidationError> {
+        if let Some(ref description) = self.description {
+            check_length("description", description, None, 0, 255)?;
+        }
+        Ok(())
+    }
 }
@@ -1,4 +1,7 @@
-use crate::{methods::method::*, types::ChatId};
+use crate::{
+    methods::method::*,
+    types::{check_length, ChatId, ValidationError},
+};
 use failure::Error;
 use serde::Serialize;
 
@@ -37,4 +40,8 @@ impl Method for SetChatTitle {
     fn get_request(&self) -> Result<RequestBuilder, Error> {
         RequestBuilder::json("setChatTitle", &self)
     }
+
+    fn validate(&self) -> Result<(), ValidationError> {
+        check_length("title", &self.title, None, 1, 255)
+    }
 }
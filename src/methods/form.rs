@@ -0,0 +1,125 @@
+use crate::types::{InputFile, InputFileKind};
+use failure::Error;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Data of a multipart/form-data request
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Form {
+    fields: Vec<(String, FormValue)>,
+}
+
+#[derive(Clone, Debug)]
+enum FormValue {
+    Text(String),
+    File { file_name: String, data: Vec<u8> },
+}
+
+impl Form {
+    /// Creates a form from fields of a serializable struct
+    ///
+    /// Strings are passed as is, other values are JSON-encoded, nulls are omitted
+    pub(crate) fn from_serialize(s: &impl Serialize) -> Result<Form, Error> {
+        let mut form = Form::default();
+        match serde_json::to_value(s)? {
+            Value::Object(map) => {
+                for (key, value) in map {
+                    match value {
+                        Value::Null => {}
+                        Value::String(value) => form.insert_text(key, value),
+                        value => form.insert_text(key, value.to_string()),
+                    }
+                }
+            }
+            value => return Err(failure::format_err!("Can not convert {} to a form", value)),
+        }
+        Ok(form)
+    }
+
+    pub(crate) fn insert_text<K: Into<String>, V: Into<String>>(&mut self, key: K, value: V) {
+        self.fields.push((key.into(), FormValue::Text(value.into())));
+    }
+
+    pub(crate) fn insert_file<K: Into<String>>(&mut self, key: K, file: InputFile) {
+        let value = match file.kind {
+            InputFileKind::Id(file_id) => FormValue::Text(file_id),
+            InputFileKind::Url(url) => FormValue::Text(url),
+            InputFileKind::Upload { file_name, data } => FormValue::File { file_name, data },
+        };
+        self.fields.push((key.into(), value));
+    }
+
+    /// Encodes form using given boundary
+    pub(crate) fn encode(&self, boundary: &str) -> Vec<u8> {
+        let mut buf = Vec::new();
+        for (key, value) in &self.fields {
+            buf.extend_from_slice(format!("--{}\r\n", boundary).as_bytes());
+            match value {
+                FormValue::Text(text) => {
+                    buf.extend_from_slice(
+                        format!("Content-Disposition: form-data; name=\"{}\"\r\n\r\n", escape(key)).as_bytes(),
+                    );
+                    buf.extend_from_slice(text.as_bytes());
+                }
+                FormValue::File { file_name, data } => {
+                    buf.extend_from_slice(
+                        format!(
+                            "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n",
+                            escape(key),
+                            escape(file_name)
+                        )
+                        .as_bytes(),
+                    );
+                    buf.extend_from_slice(b"Content-Type: application/octet-stream\r\n\r\n");
+                    buf.extend_from_slice(data);
+                }
+            }
+            buf.extend_from_slice(b"\r\n");
+        }
+        buf.extend_from_slice(format!("--{}--\r\n", boundary).as_bytes());
+        buf
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"").replace(['\r', '\n'], "")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Serialize;
+
+    #[derive(Serialize)]
+    struct Data {
+        text: &'static str,
+        number: i64,
+        list: Vec<&'static str>,
+        none: Option<bool>,
+    }
+
+    #[test]
+    fn test_encode_form() {
+        let mut form = Form::from_serialize(&Data {
+            text: "value",
+            number: 1,
+            list: vec!["message"],
+            none: None,
+        })
+        .unwrap();
+        form.insert_file("id", InputFile::file_id("file-id"));
+        form.insert_file("upload", InputFile::bytes("cert.pem", "data"));
+        assert_eq!(
+            String::from_utf8(form.encode("boundary")).unwrap(),
+            concat!(
+                "--boundary\r\nContent-Disposition: form-data; name=\"list\"\r\n\r\n[\"message\"]\r\n",
+                "--boundary\r\nContent-Disposition: form-data; name=\"number\"\r\n\r\n1\r\n",
+                "--boundary\r\nContent-Disposition: form-data; name=\"text\"\r\n\r\nvalue\r\n",
+                "--boundary\r\nContent-Disposition: form-data; name=\"id\"\r\n\r\nfile-id\r\n",
+                "--boundary\r\nContent-Disposition: form-data; name=\"upload\"; filename=\"cert.pem\"\r\n",
+                "Content-Type: application/octet-stream\r\n\r\ndata\r\n",
+                "--boundary--\r\n"
+            )
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     methods::method::*,
-    types::{InlineKeyboardMarkup, Integer, Message},
+    types::{InlineKeyboardMarkup, Integer, Message, ValidationError},
 };
 use failure::Error;
 use serde::Serialize;
@@ -62,4 +62,11 @@ impl Method for SendGame {
     fn get_request(&self) -> Result<RequestBuilder, Error> {
         RequestBuilder::json("sendGame", &self)
     }
+
+    fn validate(&self) -> Result<(), ValidationError> {
+        if let Some(ref reply_markup) = self.reply_markup {
+            reply_markup.validate()?;
+        }
+        Ok(())
+    }
 }
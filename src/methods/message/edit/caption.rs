@@ -1,6 +1,9 @@
 use crate::{
     methods::method::*,
-    types::{ChatId, EditMessageResult, InlineKeyboardMarkup, Integer, ParseMode},
+    types::{
+        check_caption, ChatId, EditMessageResult, FormattedText, InlineKeyboardMarkup, Integer, ParseMode,
+        ValidationError,
+    },
 };
 use failure::Error;
 use serde::Serialize;
@@ -68,6 +71,13 @@ impl EditMessageCaption {
         self
     }
 
+    /// Sets caption and parse mode using formatted text
+    pub fn formatted_caption(mut self, caption: FormattedText) -> Self {
+        self.caption = Some(caption.render());
+        self.parse_mode = Some(caption.get_parse_mode());
+        self
+    }
+
     /// Inline keyboard
     pub fn reply_markup<I: Into<InlineKeyboardMarkup>>(mut self, reply_markup: I) -> Self {
         self.reply_markup = Some(reply_markup.into());
@@ -81,4 +91,12 @@ impl Method for EditMessageCaption {
     fn get_request(&self) -> Result<RequestBuilder, Error> {
         RequestBuilder::json("editMessageCaption", &self)
     }
+
+    fn validate(&self) -> Result<(), ValidationError> {
+        check_caption(self.caption.as_deref(), self.parse_mode)?;
+        if let Some(ref reply_markup) = self.reply_markup {
+            reply_markup.validate()?;
+        }
+        Ok(())
+    }
 }
@@ -1,6 +1,6 @@
 use crate::{
     methods::method::*,
-    types::{ChatId, EditMessageResult, Float, InlineKeyboardMarkup, Integer},
+    types::{ChatId, EditMessageResult, Float, InlineKeyboardMarkup, Integer, ValidationError},
 };
 use failure::Error;
 use serde::Serialize;
@@ -74,6 +74,13 @@ impl Method for EditMessageLiveLocation {
     fn get_request(&self) -> Result<RequestBuilder, Error> {
         RequestBuilder::json("editMessageLiveLocation", &self)
     }
+
+    fn validate(&self) -> Result<(), ValidationError> {
+        if let Some(ref reply_markup) = self.reply_markup {
+            reply_markup.validate()?;
+        }
+        Ok(())
+    }
 }
 
 /// Stop updating a live location message
@@ -130,4 +137,11 @@ impl Method for StopMessageLiveLocation {
     fn get_request(&self) -> Result<RequestBuilder, Error> {
         RequestBuilder::json("stopMessageLiveLocation", &self)
     }
+
+    fn validate(&self) -> Result<(), ValidationError> {
+        if let Some(ref reply_markup) = self.reply_markup {
+            reply_markup.validate()?;
+        }
+        Ok(())
+    }
 }
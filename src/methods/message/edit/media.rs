@@ -1,6 +1,6 @@
 use crate::{
     methods::method::*,
-    types::{ChatId, EditMessageResult, InlineKeyboardMarkup, InputMedia, Integer},
+    types::{ChatId, EditMessageResult, InlineKeyboardMarkup, InputMedia, Integer, ValidationError},
 };
 use failure::Error;
 use serde::Serialize;
@@ -71,4 +71,11 @@ impl Method for EditMessageMedia {
     fn get_request(&self) -> Result<RequestBuilder, Error> {
         RequestBuilder::json("editMessageMedia", &self)
     }
+
+    fn validate(&self) -> Result<(), ValidationError> {
+        if let Some(ref reply_markup) = self.reply_markup {
+            reply_markup.validate()?;
+        }
+        Ok(())
+    }
 }
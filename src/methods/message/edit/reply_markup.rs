@@ -1,6 +1,6 @@
 use crate::{
     methods::method::*,
-    types::{ChatId, EditMessageResult, InlineKeyboardMarkup, Integer},
+    types::{ChatId, EditMessageResult, InlineKeyboardMarkup, Integer, ValidationError},
 };
 use failure::Error;
 use serde::Serialize;
@@ -61,4 +61,11 @@ impl Method for EditMessageReplyMarkup {
     fn get_request(&self) -> Result<RequestBuilder, Error> {
         RequestBuilder::json("editMessageReplyMarkup", &self)
     }
+
+    fn validate(&self) -> Result<(), ValidationError> {
+        if let Some(ref reply_markup) = self.reply_markup {
+            reply_markup.validate()?;
+        }
+        Ok(())
+    }
 }
@@ -1,6 +1,8 @@
 use crate::{
     methods::method::*,
-    types::{ChatId, Integer, Message, ParseMode, ReplyMarkup},
+    types::{
+        check_length, ChatId, FormattedText, Integer, Message, ParseMode, ReplyMarkup, ValidationError, MAX_TEXT_LENGTH,
+    },
 };
 use failure::Error;
 use serde::Serialize;
@@ -47,6 +49,13 @@ impl SendMessage {
         self
     }
 
+    /// Sets text and parse mode using formatted text
+    pub fn formatted_text(mut self, text: FormattedText) -> Self {
+        self.text = text.render();
+        self.parse_mode = Some(text.get_parse_mode());
+        self
+    }
+
     /// Disables link previews for links in this message
     pub fn disable_web_page_preview(mut self, disable_web_page_preview: bool) -> Self {
         self.disable_web_page_preview = Some(disable_web_page_preview);
@@ -80,4 +89,12 @@ impl Method for SendMessage {
     fn get_request(&self) -> Result<RequestBuilder, Error> {
         RequestBuilder::json("sendMessage", &self)
     }
+
+    fn validate(&self) -> Result<(), ValidationError> {
+        check_length("text", &self.text, self.parse_mode, 1, MAX_TEXT_LENGTH)?;
+        if let Some(ref reply_markup) = self.reply_markup {
+            reply_markup.validate()?;
+        }
+        Ok(())
+    }
 }
@@ -1,3 +1,4 @@
+use crate::{methods::form::Form, types::ValidationError};
 use failure::Error;
 use serde::ser::Serialize;
 use std::fmt::Display;
@@ -11,6 +12,13 @@ pub trait Method {
 
     /// Returns information about HTTP request
     fn get_request(&self) -> Result<RequestBuilder, Error>;
+
+    /// Checks parameters before sending a request
+    ///
+    /// Called by Api::execute(), default implementation accepts any parameters
+    fn validate(&self) -> Result<(), ValidationError> {
+        Ok(())
+    }
 }
 
 /// A request builder
@@ -30,6 +38,14 @@ impl RequestBuilder {
         })
     }
 
+    pub(crate) fn form(path: &'static str, form: Form) -> Result<RequestBuilder, Error> {
+        Ok(RequestBuilder {
+            method: RequestMethod::Post,
+            body: RequestBody::Form(form),
+            url: RequestUrl(path),
+        })
+    }
+
     pub(crate) fn empty(path: &'static str) -> Result<RequestBuilder, Error> {
         Ok(RequestBuilder {
             method: RequestMethod::Get,
@@ -38,11 +54,20 @@ impl RequestBuilder {
         })
     }
 
+    pub(crate) fn get_path(&self) -> &'static str {
+        self.url.0
+    }
+
+    pub(crate) fn get_body(&self) -> &RequestBody {
+        &self.body
+    }
+
     pub(crate) fn build(self, token: &str) -> Request {
         Request {
             method: self.method,
             url: self.url.build(token),
             body: self.body,
+            check_status: false,
         }
     }
 }
@@ -53,6 +78,19 @@ pub(crate) struct Request {
     pub(crate) method: RequestMethod,
     pub(crate) url: String,
     pub(crate) body: RequestBody,
+    /// Fail on a non-2xx status, API methods return errors in the body instead
+    pub(crate) check_status: bool,
+}
+
+impl Request {
+    pub(crate) fn download(token: &str, file_path: &str) -> Request {
+        Request {
+            method: RequestMethod::Get,
+            url: format!("{}/file/bot{}/{}", BASE_URL, token, file_path),
+            body: RequestBody::Empty,
+            check_status: true,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
@@ -73,5 +111,6 @@ impl RequestUrl {
 #[derive(Clone, Debug)]
 pub(crate) enum RequestBody {
     Json(Vec<u8>),
+    Form(Form),
     Empty,
 }
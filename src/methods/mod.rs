@@ -1,6 +1,7 @@
 mod answer;
 mod chat;
 mod chat_member;
+mod form;
 mod game;
 mod get_file;
 mod message;
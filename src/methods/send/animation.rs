@@ -1,6 +1,6 @@
 use crate::{
     methods::method::*,
-    types::{ChatId, Integer, Message, ParseMode, ReplyMarkup},
+    types::{check_caption, ChatId, FormattedText, Integer, Message, ParseMode, ReplyMarkup, ValidationError},
 };
 use failure::Error;
 use serde::Serialize;
@@ -104,6 +104,13 @@ impl SendAnimation {
         self
     }
 
+    /// Sets caption and parse mode using formatted text
+    pub fn formatted_caption(mut self, caption: FormattedText) -> Self {
+        self.caption = Some(caption.render());
+        self.parse_mode = Some(caption.get_parse_mode());
+        self
+    }
+
     /// Whether to send the message silently
     ///
     /// Users will receive a notification with no sound if enabled
@@ -131,4 +138,12 @@ impl Method for SendAnimation {
     fn get_request(&self) -> Result<RequestBuilder, Error> {
         RequestBuilder::json("sendAnimation", &self)
     }
+
+    fn validate(&self) -> Result<(), ValidationError> {
+        check_caption(self.caption.as_deref(), self.parse_mode)?;
+        if let Some(ref reply_markup) = self.reply_markup {
+            reply_markup.validate()?;
+        }
+        Ok(())
+    }
 }
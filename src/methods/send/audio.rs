@@ -1,6 +1,6 @@
 use crate::{
     methods::method::*,
-    types::{ChatId, Integer, Message, ParseMode, ReplyMarkup},
+    types::{check_caption, ChatId, FormattedText, Integer, Message, ParseMode, ReplyMarkup, ValidationError},
 };
 use failure::Error;
 use serde::Serialize;
@@ -73,6 +73,13 @@ impl SendAudio {
         self
     }
 
+    /// Sets caption and parse mode using formatted text
+    pub fn formatted_caption(mut self, caption: FormattedText) -> Self {
+        self.caption = Some(caption.render());
+        self.parse_mode = Some(caption.get_parse_mode());
+        self
+    }
+
     /// Sets duration of the audio in seconds
     pub fn duration(mut self, duration: Integer) -> Self {
         self.duration = Some(duration);
@@ -132,4 +139,12 @@ impl Method for SendAudio {
     fn get_request(&self) -> Result<RequestBuilder, Error> {
         RequestBuilder::json("sendAudio", &self)
     }
+
+    fn validate(&self) -> Result<(), ValidationError> {
+        check_caption(self.caption.as_deref(), self.parse_mode)?;
+        if let Some(ref reply_markup) = self.reply_markup {
+            reply_markup.validate()?;
+        }
+        Ok(())
+    }
 }
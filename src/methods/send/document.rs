@@ -1,6 +1,6 @@
 use crate::{
     methods::method::*,
-    types::{ChatId, Integer, Message, ParseMode, ReplyMarkup},
+    types::{check_caption, ChatId, FormattedText, Integer, Message, ParseMode, ReplyMarkup, ValidationError},
 };
 use failure::Error;
 use serde::Serialize;
@@ -78,6 +78,13 @@ impl SendDocument {
         self
     }
 
+    /// Sets caption and parse mode using formatted text
+    pub fn formatted_caption(mut self, caption: FormattedText) -> Self {
+        self.caption = Some(caption.render());
+        self.parse_mode = Some(caption.get_parse_mode());
+        self
+    }
+
     /// Sends the message silently
     ///
     /// Users will receive a notification with no sound
@@ -105,4 +112,12 @@ impl Method for SendDocument {
     fn get_request(&self) -> Result<RequestBuilder, Error> {
         RequestBuilder::json("sendDocument", &self)
     }
+
+    fn validate(&self) -> Result<(), ValidationError> {
+        check_caption(self.caption.as_deref(), self.parse_mode)?;
+        if let Some(ref reply_markup) = self.reply_markup {
+            reply_markup.validate()?;
+        }
+        Ok(())
+    }
 }
@@ -1,6 +1,9 @@
 use crate::{
     methods::method::*,
-    types::{InlineKeyboardMarkup, Integer, LabeledPrice, Message},
+    types::{
+        check_bytes, check_length, InlineKeyboardMarkup, Integer, LabeledPrice, Message, Money, ParseMoneyError,
+        ValidationError,
+    },
 };
 use failure::Error;
 use serde::Serialize;
@@ -201,6 +204,29 @@ impl SendInvoice {
         self.reply_markup = Some(reply_markup.into());
         self
     }
+
+    /// Returns total price of the invoice with parsed currency
+    ///
+    /// Use Money::is_within_limits() to check the amount against limits from currencies.json
+    pub fn get_total_amount(&self) -> Result<Money, ParseMoneyError> {
+        Money::from_code(&self.currency, self.prices.iter().map(|price| price.amount).sum())
+    }
+
+    pub(crate) fn get_chat_id(&self) -> Integer {
+        self.chat_id
+    }
+
+    pub(crate) fn get_payload(&self) -> &str {
+        &self.payload
+    }
+
+    pub(crate) fn get_currency(&self) -> &str {
+        &self.currency
+    }
+
+    pub(crate) fn get_prices(&self) -> &[LabeledPrice] {
+        &self.prices
+    }
 }
 
 impl Method for SendInvoice {
@@ -209,4 +235,79 @@ impl Method for SendInvoice {
     fn get_request(&self) -> Result<RequestBuilder, Error> {
         RequestBuilder::json("sendInvoice", &self)
     }
+
+    fn validate(&self) -> Result<(), ValidationError> {
+        check_length("title", &self.title, None, 1, 32)?;
+        check_length("description", &self.description, None, 1, 255)?;
+        check_bytes("payload", &self.payload, 1, 128)?;
+        if self.prices.is_empty() {
+            return Err(ValidationError::new("prices", "at least one price is required"));
+        }
+        let total = self
+            .get_total_amount()
+            .map_err(|err| ValidationError::new("currency", err.to_string()))?;
+        if !total.is_within_limits() {
+            let currency = total.get_currency();
+            return Err(ValidationError::new(
+                "prices",
+                format!(
+                    "total amount is {}, expected {}-{}",
+                    total,
+                    Money::new(currency, currency.get_min_amount()).format_amount(),
+                    Money::new(currency, currency.get_max_amount()).format_amount()
+                ),
+            ));
+        }
+        if let Some(ref reply_markup) = self.reply_markup {
+            reply_markup.validate()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_invoice(currency: &str, amounts: &[Integer]) -> SendInvoice {
+        let prices = amounts
+            .iter()
+            .map(|amount| LabeledPrice {
+                label: String::from("price"),
+                amount: *amount,
+            })
+            .collect();
+        SendInvoice::new(1, "title", "description", "payload", "token", "start", currency, prices)
+    }
+
+    #[test]
+    fn test_validate_send_invoice() {
+        assert!(get_invoice("USD", &[145]).validate().is_ok());
+        assert!(get_invoice("USD", &[200, -150]).validate().is_err());
+        assert!(get_invoice("USD", &[]).validate().is_err());
+        assert!(get_invoice("XYZ", &[145]).validate().is_err());
+        assert_eq!(
+            get_invoice("USD", &[200, -150]).validate().unwrap_err().to_string(),
+            "Invalid parameter \"prices\": total amount is US$ 0.50, expected 1.00-10000.00"
+        );
+        assert!(SendInvoice::new(1, "", "d", "p", "t", "s", "USD", vec![])
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_send_invoice_total_amount() {
+        let total = get_invoice("USD", &[200, -100]).get_total_amount().unwrap();
+        assert_eq!(total.to_string(), "US$ 1.00");
+        assert!(total.is_within_limits());
+        assert!(!get_invoice("USD", &[200, -150])
+            .get_total_amount()
+            .unwrap()
+            .is_within_limits());
+        assert!(!get_invoice("JPY", &[1_080_001])
+            .get_total_amount()
+            .unwrap()
+            .is_within_limits());
+        assert!(get_invoice("XYZ", &[145]).get_total_amount().is_err());
+    }
 }
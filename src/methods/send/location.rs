@@ -1,6 +1,6 @@
 use crate::{
     methods::method::*,
-    types::{ChatId, Float, Integer, Message, ReplyMarkup},
+    types::{ChatId, Float, Integer, Message, ReplyMarkup, ValidationError},
 };
 use failure::Error;
 use serde::Serialize;
@@ -76,4 +76,11 @@ impl Method for SendLocation {
     fn get_request(&self) -> Result<RequestBuilder, Error> {
         RequestBuilder::json("sendLocation", &self)
     }
+
+    fn validate(&self) -> Result<(), ValidationError> {
+        if let Some(ref reply_markup) = self.reply_markup {
+            reply_markup.validate()?;
+        }
+        Ok(())
+    }
 }
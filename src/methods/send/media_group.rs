@@ -1,6 +1,6 @@
 use crate::{
     methods::method::*,
-    types::{ChatId, Integer, MediaGroupItem, Message},
+    types::{check_count, ChatId, Integer, MediaGroupItem, Message, ValidationError},
 };
 use failure::Error;
 use serde::Serialize;
@@ -51,4 +51,12 @@ impl Method for SendMediaGroup {
     fn get_request(&self) -> Result<RequestBuilder, Error> {
         RequestBuilder::json("sendMediaGroup", &self)
     }
+
+    fn validate(&self) -> Result<(), ValidationError> {
+        check_count("media", self.media.len(), 2, 10)?;
+        for item in &self.media {
+            item.validate()?;
+        }
+        Ok(())
+    }
 }
@@ -1,6 +1,6 @@
 use crate::{
     methods::method::*,
-    types::{ChatId, Float, Integer, Message, ReplyMarkup},
+    types::{ChatId, Float, Integer, Message, ReplyMarkup, ValidationError},
 };
 use failure::Error;
 use serde::Serialize;
@@ -96,4 +96,11 @@ impl Method for SendVenue {
     fn get_request(&self) -> Result<RequestBuilder, Error> {
         RequestBuilder::json("sendVenue", &self)
     }
+
+    fn validate(&self) -> Result<(), ValidationError> {
+        if let Some(ref reply_markup) = self.reply_markup {
+            reply_markup.validate()?;
+        }
+        Ok(())
+    }
 }
@@ -1,6 +1,6 @@
 use crate::{
     methods::method::*,
-    types::{ChatId, Integer, Message, ParseMode, ReplyMarkup},
+    types::{check_caption, ChatId, FormattedText, Integer, Message, ParseMode, ReplyMarkup, ValidationError},
 };
 use failure::Error;
 use serde::Serialize;
@@ -108,6 +108,13 @@ impl SendVideo {
         self
     }
 
+    /// Sets caption and parse mode using formatted text
+    pub fn formatted_caption(mut self, caption: FormattedText) -> Self {
+        self.caption = Some(caption.render());
+        self.parse_mode = Some(caption.get_parse_mode());
+        self
+    }
+
     /// Pass True, if the uploaded video is suitable for streaming
     pub fn supports_streaming(mut self, supports_streaming: bool) -> Self {
         self.supports_streaming = Some(supports_streaming);
@@ -140,4 +147,12 @@ impl Method for SendVideo {
     fn get_request(&self) -> Result<RequestBuilder, Error> {
         RequestBuilder::json("sendVideo", &self)
     }
+
+    fn validate(&self) -> Result<(), ValidationError> {
+        check_caption(self.caption.as_deref(), self.parse_mode)?;
+        if let Some(ref reply_markup) = self.reply_markup {
+            reply_markup.validate()?;
+        }
+        Ok(())
+    }
 }
@@ -1,6 +1,6 @@
 use crate::{
     methods::method::*,
-    types::{ChatId, Integer, Message, ReplyMarkup},
+    types::{ChatId, Integer, Message, ReplyMarkup, ValidationError},
 };
 use failure::Error;
 use serde::Serialize;
@@ -100,4 +100,11 @@ impl Method for SendVideoNote {
     fn get_request(&self) -> Result<RequestBuilder, Error> {
         RequestBuilder::json("sendVideoNote", &self)
     }
+
+    fn validate(&self) -> Result<(), ValidationError> {
+        if let Some(ref reply_markup) = self.reply_markup {
+            reply_markup.validate()?;
+        }
+        Ok(())
+    }
 }
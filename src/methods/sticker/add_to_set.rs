@@ -1,6 +1,6 @@
 use crate::{
     methods::method::*,
-    types::{Integer, MaskPosition},
+    types::{check_emojis, check_mask_position, Integer, MaskPosition, ValidationError},
 };
 use failure::Error;
 use serde::Serialize;
@@ -53,4 +53,12 @@ impl Method for AddStickerToSet {
     fn get_request(&self) -> Result<RequestBuilder, Error> {
         RequestBuilder::json("addStickerToSet", &self)
     }
+
+    fn validate(&self) -> Result<(), ValidationError> {
+        check_emojis("emojis", &self.emojis)?;
+        match self.mask_position {
+            Some(ref mask_position) => check_mask_position("mask_position", mask_position),
+            None => Ok(()),
+        }
+    }
 }
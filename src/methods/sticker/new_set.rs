@@ -1,6 +1,6 @@
 use crate::{
     methods::method::*,
-    types::{Integer, MaskPosition},
+    types::{check_emojis, check_length, check_mask_position, Integer, MaskPosition, ValidationError},
 };
 use failure::Error;
 use serde::Serialize;
@@ -72,4 +72,14 @@ impl Method for CreateNewStickerSet {
     fn get_request(&self) -> Result<RequestBuilder, Error> {
         RequestBuilder::json("createNewStickerSet", &self)
     }
+
+    fn validate(&self) -> Result<(), ValidationError> {
+        check_length("name", &self.name, None, 1, 64)?;
+        check_length("title", &self.title, None, 1, 64)?;
+        check_emojis("emojis", &self.emojis)?;
+        match self.mask_position {
+            Some(ref mask_position) => check_mask_position("mask_position", mask_position),
+            None => Ok(()),
+        }
+    }
 }
@@ -1,6 +1,6 @@
 use crate::{
-    methods::method::*,
-    types::{File, Integer},
+    methods::{form::Form, method::*},
+    types::{File, InputFile, Integer},
 };
 use failure::Error;
 use serde::Serialize;
@@ -9,7 +9,8 @@ use serde::Serialize;
 #[derive(Clone, Debug, Serialize)]
 pub struct UploadStickerFile {
     user_id: Integer,
-    png_sticker: String,
+    #[serde(skip)]
+    png_sticker: InputFile,
 }
 
 impl UploadStickerFile {
@@ -20,11 +21,9 @@ impl UploadStickerFile {
     /// * user_id - User identifier of sticker file owner
     /// * png_sticker - Png image with the sticker, must be up to 512 kilobytes in size,
     ///                 dimensions must not exceed 512px, and either width or height must be exactly 512px
-    pub fn new<S: Into<String>>(user_id: Integer, png_sticker: S) -> Self {
-        UploadStickerFile {
-            user_id,
-            png_sticker: png_sticker.into(),
-        }
+    ///                 The file must be uploaded, use InputFile::bytes or InputFile::path
+    pub fn new(user_id: Integer, png_sticker: InputFile) -> Self {
+        UploadStickerFile { user_id, png_sticker }
     }
 }
 
@@ -32,6 +31,8 @@ impl Method for UploadStickerFile {
     type Response = File;
 
     fn get_request(&self) -> Result<RequestBuilder, Error> {
-        RequestBuilder::json("uploadStickerFile", &self)
+        let mut form = Form::from_serialize(self)?;
+        form.insert_file("png_sticker", self.png_sticker.clone());
+        RequestBuilder::form("uploadStickerFile", form)
     }
 }
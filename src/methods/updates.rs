@@ -1,6 +1,6 @@
 use crate::{
-    methods::method::*,
-    types::{AllowedUpdate, Integer, Update, WebhookInfo},
+    methods::{form::Form, method::*},
+    types::{check_range, AllowedUpdate, InputFile, Integer, Update, ValidationError, WebhookInfo},
 };
 use failure::Error;
 use serde::Serialize;
@@ -27,6 +27,13 @@ impl Method for GetUpdates {
     fn get_request(&self) -> Result<RequestBuilder, Error> {
         RequestBuilder::json("getUpdates", &self)
     }
+
+    fn validate(&self) -> Result<(), ValidationError> {
+        if let Some(limit) = self.limit {
+            check_range("limit", limit, 1, 100)?;
+        }
+        Ok(())
+    }
 }
 
 impl GetUpdates {
@@ -100,12 +107,14 @@ impl GetUpdates {
 #[derive(Clone, Debug, Serialize)]
 pub struct SetWebhook {
     url: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    certificate: Option<String>,
+    #[serde(skip)]
+    certificate: Option<InputFile>,
     #[serde(skip_serializing_if = "Option::is_none")]
     max_connections: Option<Integer>,
     #[serde(skip_serializing_if = "Option::is_none")]
     allowed_updates: Option<HashSet<AllowedUpdate>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    secret_token: Option<String>,
 }
 
 impl SetWebhook {
@@ -121,11 +130,14 @@ impl SetWebhook {
             certificate: None,
             max_connections: None,
             allowed_updates: None,
+            secret_token: None,
         }
     }
 
     /// Upload your public key certificate so that the root certificate in use can be checked
-    pub fn certificate(mut self, certificate: String) -> Self {
+    ///
+    /// The certificate must be uploaded, use InputFile::bytes or InputFile::path
+    pub fn certificate(mut self, certificate: InputFile) -> Self {
         self.certificate = Some(certificate);
         self
     }
@@ -167,13 +179,36 @@ impl SetWebhook {
         };
         self
     }
+
+    /// A secret token to be sent in a header “X-Telegram-Bot-Api-Secret-Token” in every webhook request
+    ///
+    /// 1-256 characters, only A-Z, a-z, 0-9, _ and - are allowed
+    /// The header is useful to ensure that the request comes from a webhook set by you
+    pub fn secret_token<S: Into<String>>(mut self, secret_token: S) -> Self {
+        self.secret_token = Some(secret_token.into());
+        self
+    }
 }
 
 impl Method for SetWebhook {
     type Response = bool;
 
     fn get_request(&self) -> Result<RequestBuilder, Error> {
-        RequestBuilder::json("setWebhook", &self)
+        match self.certificate {
+            Some(ref certificate) => {
+                let mut form = Form::from_serialize(self)?;
+                form.insert_file("certificate", certificate.clone());
+                RequestBuilder::form("setWebhook", form)
+            }
+            None => RequestBuilder::json("setWebhook", &self),
+        }
+    }
+
+    fn validate(&self) -> Result<(), ValidationError> {
+        if let Some(max_connections) = self.max_connections {
+            check_range("max_connections", max_connections, 1, 100)?;
+        }
+        Ok(())
     }
 }
 
@@ -208,6 +243,17 @@ mod tests {
     use super::*;
     use serde_json::Value;
 
+    #[test]
+    fn test_validate_get_updates() {
+        assert!(GetUpdates::default().validate().is_ok());
+        assert!(GetUpdates::default().limit(100).validate().is_ok());
+        assert_eq!(
+            GetUpdates::default().limit(101).validate().unwrap_err().to_string(),
+            "Invalid parameter \"limit\": value is 101, expected 1-100"
+        );
+        assert!(SetWebhook::new("url").max_connections(0).validate().is_err());
+    }
+
     #[test]
     fn test_serialize_get_updates() {
         let req = GetUpdates::default().get_request().unwrap().build("token");
@@ -240,7 +286,7 @@ mod tests {
             .build("token");
         match req.body {
             RequestBody::Json(data) => {
-                let data: Value = serde_json::from_slice(&data).unwrap();;
+                let data: Value = serde_json::from_slice(&data).unwrap();
                 assert_eq!(data["offset"], 0);
                 assert_eq!(data["limit"], 10);
                 let mut updates: Vec<&str> = data["allowed_updates"]
@@ -280,6 +326,37 @@ mod tests {
             }
             data => panic!("Unexpected request data: {:?}", data),
         }
+
+        let req = SetWebhook::new("url")
+            .max_connections(10)
+            .secret_token("secret")
+            .get_request()
+            .unwrap()
+            .build("token");
+        match req.body {
+            RequestBody::Json(data) => {
+                let data: Value = serde_json::from_slice(&data).unwrap();
+                assert_eq!(data["url"], "url");
+                assert_eq!(data["max_connections"], 10);
+                assert_eq!(data["secret_token"], "secret");
+            }
+            data => panic!("Unexpected request data: {:?}", data),
+        }
+
+        let req = SetWebhook::new("url")
+            .certificate(InputFile::bytes("cert.pem", "cert"))
+            .get_request()
+            .unwrap()
+            .build("token");
+        assert_eq!(req.method, RequestMethod::Post);
+        match req.body {
+            RequestBody::Form(form) => {
+                let data = String::from_utf8(form.encode("boundary")).unwrap();
+                assert!(data.contains("name=\"url\"\r\n\r\nurl\r\n"));
+                assert!(data.contains("name=\"certificate\"; filename=\"cert.pem\""));
+            }
+            data => panic!("Unexpected request data: {:?}", data),
+        }
     }
 
     #[test]
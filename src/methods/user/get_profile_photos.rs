@@ -1,6 +1,6 @@
 use crate::{
     methods::method::*,
-    types::{Integer, UserProfilePhotos},
+    types::{check_range, Integer, UserProfilePhotos, ValidationError},
 };
 use failure::Error;
 use serde::Serialize;
@@ -53,4 +53,11 @@ impl Method for GetUserProfilePhotos {
     fn get_request(&self) -> Result<RequestBuilder, Error> {
         RequestBuilder::json("getUserProfilePhotos", &self)
     }
+
+    fn validate(&self) -> Result<(), ValidationError> {
+        if let Some(limit) = self.limit {
+            check_range("limit", limit, 1, 100)?;
+        }
+        Ok(())
+    }
 }
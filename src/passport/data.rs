@@ -0,0 +1,291 @@
+use crate::{
+    passport::decrypt::{DecryptError, DecryptedPassportData, SecureValue},
+    types::{EncryptedPassportElement, EncryptedPassportElementKind},
+};
+use chrono::NaiveDate;
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+use std::{collections::HashMap, fmt, str::FromStr};
+
+const DATE_FORMAT: &str = "%d.%m.%Y";
+
+/// Decrypted passport data with typed elements
+#[derive(Clone, Debug)]
+pub struct DecryptedPassport {
+    /// Nonce from the authorization request
+    pub nonce: String,
+    /// Elements shared with the bot
+    pub elements: HashMap<EncryptedPassportElementKind, DecryptedPassportValue>,
+}
+
+impl DecryptedPassport {
+    /// Parses data of decrypted elements
+    pub fn parse(data: DecryptedPassportData) -> Result<Self, DecryptError> {
+        let mut elements = HashMap::new();
+        for element in data.elements {
+            let kind = element.element.get_kind();
+            let value = match element.data {
+                Some(data) => Some(PassportElementData::parse(&kind, data)?),
+                None => None,
+            };
+            elements.insert(
+                kind,
+                DecryptedPassportValue {
+                    element: element.element,
+                    data: value,
+                    credentials: element.credentials,
+                },
+            );
+        }
+        Ok(DecryptedPassport {
+            nonce: data.nonce,
+            elements,
+        })
+    }
+
+    /// Returns an element of given type
+    pub fn get(&self, kind: EncryptedPassportElementKind) -> Option<&DecryptedPassportValue> {
+        self.elements.get(&kind)
+    }
+
+    /// Returns personal details
+    pub fn get_personal_details(&self) -> Option<&PersonalDetails> {
+        match self.get(EncryptedPassportElementKind::PersonalDetails)?.data {
+            Some(PassportElementData::PersonalDetails(ref data)) => Some(data),
+            _ => None,
+        }
+    }
+
+    /// Returns data of an identity document
+    ///
+    /// Kind is one of passport, driver_license, identity_card, internal_passport
+    pub fn get_id_document(&self, kind: EncryptedPassportElementKind) -> Option<&IdDocumentData> {
+        match self.get(kind)?.data {
+            Some(PassportElementData::IdDocument(ref data)) => Some(data),
+            _ => None,
+        }
+    }
+
+    /// Returns residential address
+    pub fn get_address(&self) -> Option<&ResidentialAddress> {
+        match self.get(EncryptedPassportElementKind::Address)?.data {
+            Some(PassportElementData::Address(ref data)) => Some(data),
+            _ => None,
+        }
+    }
+}
+
+/// Passport element with typed decrypted data
+#[derive(Clone, Debug)]
+pub struct DecryptedPassportValue {
+    /// Original element
+    pub element: EncryptedPassportElement,
+    /// Decrypted data of the element, if the element has data
+    pub data: Option<PassportElementData>,
+    /// Credentials required to decrypt files of the element
+    pub credentials: SecureValue,
+}
+
+/// Decrypted data of a passport element
+#[derive(Clone, Debug, PartialEq)]
+pub enum PassportElementData {
+    /// Data of personal_details element
+    PersonalDetails(PersonalDetails),
+    /// Data of passport, driver_license, identity_card and internal_passport elements
+    IdDocument(IdDocumentData),
+    /// Data of address element
+    Address(ResidentialAddress),
+}
+
+impl PassportElementData {
+    /// Parses decrypted JSON data of an element with given type
+    pub fn parse(kind: &EncryptedPassportElementKind, data: serde_json::Value) -> Result<Self, DecryptError> {
+        use self::EncryptedPassportElementKind::*;
+        Ok(match kind {
+            PersonalDetails => PassportElementData::PersonalDetails(serde_json::from_value(data)?),
+            Passport | DriverLicense | IdentityCard | InternalPassport => {
+                PassportElementData::IdDocument(serde_json::from_value(data)?)
+            }
+            Address => PassportElementData::Address(serde_json::from_value(data)?),
+            kind => return Err(DecryptError::UnexpectedData(*kind)),
+        })
+    }
+}
+
+/// Personal details
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct PersonalDetails {
+    /// First Name
+    pub first_name: String,
+    /// Last Name
+    pub last_name: String,
+    /// Middle Name
+    #[serde(default, deserialize_with = "deserialize_optional_string")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub middle_name: Option<String>,
+    /// Date of birth
+    #[serde(deserialize_with = "deserialize_date", serialize_with = "serialize_date")]
+    pub birth_date: NaiveDate,
+    /// Gender
+    pub gender: Gender,
+    /// Citizenship
+    pub country_code: CountryCode,
+    /// Country of residence
+    pub residence_country_code: CountryCode,
+    /// First Name in the language of the user's country of residence
+    #[serde(default, deserialize_with = "deserialize_optional_string")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_name_native: Option<String>,
+    /// Last Name in the language of the user's country of residence
+    #[serde(default, deserialize_with = "deserialize_optional_string")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_name_native: Option<String>,
+    /// Middle Name in the language of the user's country of residence
+    #[serde(default, deserialize_with = "deserialize_optional_string")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub middle_name_native: Option<String>,
+}
+
+/// Gender
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Gender {
+    /// Male
+    #[serde(rename = "male")]
+    Male,
+    /// Female
+    #[serde(rename = "female")]
+    Female,
+}
+
+/// Data of an identity document
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct IdDocumentData {
+    /// Document number
+    pub document_no: String,
+    /// Date of expiry
+    #[serde(
+        default,
+        deserialize_with = "deserialize_optional_date",
+        serialize_with = "serialize_optional_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub expiry_date: Option<NaiveDate>,
+}
+
+/// Residential address
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ResidentialAddress {
+    /// First line for the address
+    pub street_line1: String,
+    /// Second line for the address
+    #[serde(default, deserialize_with = "deserialize_optional_string")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub street_line2: Option<String>,
+    /// City
+    pub city: String,
+    /// State
+    #[serde(default, deserialize_with = "deserialize_optional_string")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    /// Country
+    pub country_code: CountryCode,
+    /// Address post code
+    pub post_code: String,
+}
+
+/// ISO 3166-1 alpha-2 country code
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct CountryCode(String);
+
+impl CountryCode {
+    /// Returns the code as an uppercase string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for CountryCode {
+    type Err = ParseCountryCodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() == 2 && s.chars().all(|c| c.is_ascii_alphabetic()) {
+            Ok(CountryCode(s.to_ascii_uppercase()))
+        } else {
+            Err(ParseCountryCodeError(String::from(s)))
+        }
+    }
+}
+
+impl fmt::Display for CountryCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for CountryCode {
+    fn deserialize<D>(deserializer: D) -> Result<CountryCode, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for CountryCode {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+/// An error when parsing country code
+#[derive(Debug, failure::Fail)]
+#[fail(display = "Invalid country code: \"{}\"", _0)]
+pub struct ParseCountryCodeError(String);
+
+fn deserialize_date<'de, D>(deserializer: D) -> Result<NaiveDate, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    NaiveDate::parse_from_str(&value, DATE_FORMAT).map_err(D::Error::custom)
+}
+
+fn serialize_date<S>(date: &NaiveDate, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&date.format(DATE_FORMAT).to_string())
+}
+
+fn deserialize_optional_date<'de, D>(deserializer: D) -> Result<Option<NaiveDate>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match deserialize_optional_string(deserializer)? {
+        Some(value) => NaiveDate::parse_from_str(&value, DATE_FORMAT)
+            .map(Some)
+            .map_err(D::Error::custom),
+        None => Ok(None),
+    }
+}
+
+fn serialize_optional_date<S>(date: &Option<NaiveDate>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match date {
+        Some(date) => serialize_date(date, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Telegram sends empty strings for optional fields which are not filled
+fn deserialize_optional_string<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<String>::deserialize(deserializer)?.filter(|value| !value.is_empty()))
+}
@@ -0,0 +1,272 @@
+use crate::types::{EncryptedCredentials, EncryptedPassportElement, EncryptedPassportElementKind, PassportData};
+use openssl::{
+    base64,
+    error::ErrorStack,
+    memcmp,
+    pkey::Private,
+    rsa::{Padding, Rsa},
+    sha::{sha256, sha512},
+    symm::{Cipher, Crypter, Mode},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Bot's private RSA key used to decrypt Telegram Passport data
+#[derive(Clone, Debug)]
+pub struct PassportKey {
+    rsa: Rsa<Private>,
+}
+
+impl PassportKey {
+    /// Creates a key from PEM-encoded private key
+    pub fn from_pem(pem: &[u8]) -> Result<Self, DecryptError> {
+        Ok(PassportKey {
+            rsa: Rsa::private_key_from_pem(pem)?,
+        })
+    }
+
+    /// Returns PEM-encoded public key
+    pub fn get_public_key_pem(&self) -> Result<String, DecryptError> {
+        let pem = self.rsa.public_key_to_pem()?;
+        Ok(String::from_utf8_lossy(&pem).into_owned())
+    }
+
+    /// Decrypts and verifies credentials
+    pub fn decrypt_credentials(&self, credentials: &EncryptedCredentials) -> Result<Credentials, DecryptError> {
+        let encrypted_secret = base64::decode_block(&credentials.secret)?;
+        let mut secret = vec![0; self.rsa.size() as usize];
+        let len = self
+            .rsa
+            .private_decrypt(&encrypted_secret, &mut secret, Padding::PKCS1_OAEP)?;
+        secret.truncate(len);
+        let hash = base64::decode_block(&credentials.hash)?;
+        let data = base64::decode_block(&credentials.data)?;
+        Ok(serde_json::from_slice(&decrypt_bytes(&secret, &hash, &data)?)?)
+    }
+}
+
+/// Decrypts and verifies all data shared with the bot
+///
+/// Files are not downloaded, use file credentials of an element to decrypt them
+pub fn decrypt(key: &PassportKey, data: &PassportData) -> Result<DecryptedPassportData, DecryptError> {
+    let credentials = key.decrypt_credentials(&data.credentials)?;
+    let mut elements = Vec::new();
+    for element in &data.data {
+        let kind = element.get_kind();
+        let secure_value = credentials.secure_data.get(&kind).cloned().unwrap_or_default();
+        let decrypted_data = match (element.get_data(), &secure_value.data) {
+            (Some(data), Some(data_credentials)) => Some(data_credentials.decrypt(data)?),
+            (Some(_), None) => return Err(DecryptError::MissingCredentials(kind)),
+            (None, _) => None,
+        };
+        elements.push(DecryptedPassportElement {
+            element: element.clone(),
+            data: decrypted_data,
+            credentials: secure_value,
+        });
+    }
+    Ok(DecryptedPassportData {
+        nonce: credentials.nonce,
+        elements,
+    })
+}
+
+/// Passport data decrypted with bot's private key
+#[derive(Clone, Debug)]
+pub struct DecryptedPassportData {
+    /// Nonce from the authorization request
+    pub nonce: String,
+    /// Elements shared with the bot
+    pub elements: Vec<DecryptedPassportElement>,
+}
+
+/// Passport element with decrypted data
+#[derive(Clone, Debug)]
+pub struct DecryptedPassportElement {
+    /// Original element
+    pub element: EncryptedPassportElement,
+    /// Decrypted JSON data of the element, if the element has data
+    pub data: Option<Value>,
+    /// Credentials required to decrypt files of the element
+    pub credentials: SecureValue,
+}
+
+/// Decrypted credentials
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Credentials {
+    /// Credentials for encrypted data
+    pub secure_data: SecureData,
+    /// Nonce from the authorization request
+    #[serde(alias = "payload")]
+    pub nonce: String,
+}
+
+/// Credentials for each type of passport element
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct SecureData {
+    /// Credentials for personal details
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub personal_details: Option<SecureValue>,
+    /// Credentials for passport
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub passport: Option<SecureValue>,
+    /// Credentials for internal passport
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub internal_passport: Option<SecureValue>,
+    /// Credentials for driver license
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub driver_license: Option<SecureValue>,
+    /// Credentials for ID card
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identity_card: Option<SecureValue>,
+    /// Credentials for residential address
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<SecureValue>,
+    /// Credentials for utility bill
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub utility_bill: Option<SecureValue>,
+    /// Credentials for bank statement
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bank_statement: Option<SecureValue>,
+    /// Credentials for rental agreement
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rental_agreement: Option<SecureValue>,
+    /// Credentials for registration page in the internal passport
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub passport_registration: Option<SecureValue>,
+    /// Credentials for temporary registration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temporary_registration: Option<SecureValue>,
+}
+
+impl SecureData {
+    /// Returns credentials for given type of element
+    ///
+    /// Phone number and email are not encrypted, so there are no credentials for them
+    pub fn get(&self, kind: &EncryptedPassportElementKind) -> Option<&SecureValue> {
+        use self::EncryptedPassportElementKind::*;
+        match kind {
+            Address => self.address.as_ref(),
+            BankStatement => self.bank_statement.as_ref(),
+            DriverLicense => self.driver_license.as_ref(),
+            IdentityCard => self.identity_card.as_ref(),
+            InternalPassport => self.internal_passport.as_ref(),
+            Passport => self.passport.as_ref(),
+            PassportRegistration => self.passport_registration.as_ref(),
+            PersonalDetails => self.personal_details.as_ref(),
+            RentalAgreement => self.rental_agreement.as_ref(),
+            TemporaryRegistration => self.temporary_registration.as_ref(),
+            UtilityBill => self.utility_bill.as_ref(),
+            Email | PhoneNumber => None,
+        }
+    }
+}
+
+/// Credentials for data and files of a passport element
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct SecureValue {
+    /// Credentials for encrypted data
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<DataCredentials>,
+    /// Credentials for encrypted document's front side
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub front_side: Option<FileCredentials>,
+    /// Credentials for encrypted document's reverse side
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reverse_side: Option<FileCredentials>,
+    /// Credentials for encrypted selfie of the user with a document
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selfie: Option<FileCredentials>,
+    /// Credentials for encrypted translation of the document
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub translation: Option<Vec<FileCredentials>>,
+    /// Credentials for encrypted files
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub files: Option<Vec<FileCredentials>>,
+}
+
+/// Credentials required to decrypt element data
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct DataCredentials {
+    /// Checksum of encrypted data
+    pub data_hash: String,
+    /// Secret of encrypted data
+    pub secret: String,
+}
+
+impl DataCredentials {
+    /// Decrypts and verifies base64-encoded data of an element
+    pub fn decrypt(&self, data: &str) -> Result<Value, DecryptError> {
+        let data = base64::decode_block(data)?;
+        let secret = base64::decode_block(&self.secret)?;
+        let hash = base64::decode_block(&self.data_hash)?;
+        Ok(serde_json::from_slice(&decrypt_bytes(&secret, &hash, &data)?)?)
+    }
+}
+
+/// Credentials required to decrypt a file
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct FileCredentials {
+    /// Checksum of encrypted file
+    pub file_hash: String,
+    /// Secret of encrypted file
+    pub secret: String,
+}
+
+impl FileCredentials {
+    /// Decrypts and verifies content of a downloaded file
+    pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, DecryptError> {
+        let secret = base64::decode_block(&self.secret)?;
+        let hash = base64::decode_block(&self.file_hash)?;
+        decrypt_bytes(&secret, &hash, data)
+    }
+}
+
+/// An error when decrypting passport data
+#[derive(Debug, failure::Fail, derive_more::From)]
+pub enum DecryptError {
+    /// Base64 decoding or a cryptographic operation failed
+    #[fail(display = "OpenSSL error: {}", _0)]
+    Openssl(#[cause] ErrorStack),
+    /// Checksum of decrypted data does not match
+    #[fail(display = "Hash mismatch")]
+    HashMismatch,
+    /// Decrypted data has invalid padding
+    #[fail(display = "Invalid padding")]
+    BadPadding,
+    /// Decrypted data is not a valid JSON
+    #[fail(display = "Failed to parse JSON: {}", _0)]
+    Json(#[cause] serde_json::Error),
+    /// Credentials for an element are not found
+    #[fail(display = "Credentials for {:?} are not found", _0)]
+    MissingCredentials(EncryptedPassportElementKind),
+    /// Element of given type is not expected to have data
+    #[fail(display = "Unexpected data for {:?}", _0)]
+    UnexpectedData(EncryptedPassportElementKind),
+    /// Nonce was not issued by the bot or has been used already
+    #[fail(display = "Unknown nonce")]
+    UnknownNonce,
+}
+
+/// Decrypts data with AES-256-CBC using a key derived from secret and hash
+///
+/// Checks SHA256 of decrypted data and removes random padding
+pub(crate) fn decrypt_bytes(secret: &[u8], hash: &[u8], data: &[u8]) -> Result<Vec<u8>, DecryptError> {
+    let secret_hash = sha512(&[secret, hash].concat());
+    let (key, iv) = (&secret_hash[..32], &secret_hash[32..48]);
+    let mut crypter = Crypter::new(Cipher::aes_256_cbc(), Mode::Decrypt, key, Some(iv))?;
+    crypter.pad(false);
+    let mut result = vec![0; data.len() + Cipher::aes_256_cbc().block_size()];
+    let mut len = crypter.update(data, &mut result)?;
+    len += crypter.finalize(&mut result[len..])?;
+    result.truncate(len);
+    let actual_hash = sha256(&result);
+    if hash.len() != actual_hash.len() || !memcmp::eq(hash, &actual_hash) {
+        return Err(DecryptError::HashMismatch);
+    }
+    let padding = result.first().map_or(0, |len| *len as usize);
+    if padding < 32 || padding > result.len() {
+        return Err(DecryptError::BadPadding);
+    }
+    Ok(result.split_off(padding))
+}
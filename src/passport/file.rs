@@ -0,0 +1,229 @@
+use crate::{
+    api::{Api, ApiFuture},
+    methods::GetFile,
+    passport::{
+        data::DecryptedPassportValue,
+        decrypt::{decrypt_bytes, DecryptError, DecryptedPassportElement, FileCredentials, SecureValue},
+    },
+    types::{EncryptedPassportElement, EncryptedPassportElementKind, PassportElementError, PassportFile},
+};
+use failure::Error;
+use futures::{future, Future};
+use openssl::base64;
+
+/// Role of a file in a passport element
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PassportFileKind {
+    /// Front side of a document
+    FrontSide,
+    /// Reverse side of a document
+    ReverseSide,
+    /// Selfie of the user with a document
+    Selfie,
+    /// Scan of a document
+    File,
+    /// Translation of a document
+    TranslationFile,
+}
+
+/// Encrypted passport file with credentials required to decrypt it
+#[derive(Clone, Debug)]
+pub struct EncryptedPassportFile {
+    element_kind: EncryptedPassportElementKind,
+    kind: PassportFileKind,
+    file: PassportFile,
+    credentials: FileCredentials,
+    secret: Vec<u8>,
+    hash: Vec<u8>,
+}
+
+impl EncryptedPassportFile {
+    /// Returns type of the element the file belongs to
+    pub fn get_element_kind(&self) -> EncryptedPassportElementKind {
+        self.element_kind
+    }
+
+    /// Returns role of the file in the element
+    pub fn get_kind(&self) -> PassportFileKind {
+        self.kind
+    }
+
+    /// Returns the file
+    pub fn get_file(&self) -> &PassportFile {
+        &self.file
+    }
+
+    /// Returns credentials of the file
+    pub fn get_credentials(&self) -> &FileCredentials {
+        &self.credentials
+    }
+
+    /// Decrypts and verifies content of a downloaded file
+    pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, PassportFileError> {
+        decrypt_bytes(&self.secret, &self.hash, data).map_err(|err| PassportFileError {
+            file: Box::new(self.clone()),
+            cause: err,
+        })
+    }
+
+    /// Downloads the file using GetFile and returns decrypted content
+    ///
+    /// A file which fails decryption or verification results in PassportFileError,
+    /// use PassportFileError::get_element_error() to report it to the user
+    pub fn download(&self, api: &Api) -> ApiFuture<Vec<u8>> {
+        let file = self.clone();
+        let download_api = api.clone();
+        ApiFuture::new(
+            api.execute(&GetFile::new(self.file.file_id.clone()))
+                .and_then(|info| future::result(info.file_path.ok_or_else(|| Error::from(MissingFilePath))))
+                .and_then(move |file_path| download_api.download_file(file_path))
+                .and_then(move |data| file.decrypt(&data).map_err(Error::from)),
+        )
+    }
+
+    /// Returns an error for SetPassportDataErrors which points to this file
+    pub fn get_error<S: Into<String>>(&self, message: S) -> PassportElementError {
+        let (kind, file_hash, message) = (self.element_kind, self.credentials.file_hash.clone(), message.into());
+        match self.kind {
+            PassportFileKind::FrontSide => PassportElementError::front_side(kind, file_hash, message),
+            PassportFileKind::ReverseSide => PassportElementError::reverse_side(kind, file_hash, message),
+            PassportFileKind::Selfie => PassportElementError::selfie(kind, file_hash, message),
+            PassportFileKind::File => PassportElementError::file(kind, file_hash, message),
+            PassportFileKind::TranslationFile => PassportElementError::translation_file(kind, file_hash, message),
+        }
+        .expect("File kind does not match element kind")
+    }
+}
+
+/// Verification of a passport file has failed
+#[derive(Debug, failure::Fail)]
+#[fail(display = "Failed to verify passport file: {}", cause)]
+pub struct PassportFileError {
+    file: Box<EncryptedPassportFile>,
+    #[cause]
+    cause: DecryptError,
+}
+
+impl PassportFileError {
+    /// Returns the file which has failed verification
+    pub fn get_file(&self) -> &EncryptedPassportFile {
+        &self.file
+    }
+
+    /// Returns the reason of failure
+    pub fn get_cause(&self) -> &DecryptError {
+        &self.cause
+    }
+
+    /// Returns an error for SetPassportDataErrors which asks the user to upload the file again
+    pub fn get_element_error<S: Into<String>>(&self, message: S) -> PassportElementError {
+        self.file.get_error(message)
+    }
+}
+
+#[derive(Debug, failure::Fail)]
+#[fail(display = "File path is missing in GetFile response")]
+struct MissingFilePath;
+
+impl DecryptedPassportElement {
+    /// Returns all files of the element along with their credentials
+    pub fn get_files(&self) -> Result<Vec<EncryptedPassportFile>, DecryptError> {
+        get_files(&self.element, &self.credentials)
+    }
+}
+
+impl DecryptedPassportValue {
+    /// Returns all files of the element along with their credentials
+    pub fn get_files(&self) -> Result<Vec<EncryptedPassportFile>, DecryptError> {
+        get_files(&self.element, &self.credentials)
+    }
+}
+
+type ElementFiles<'a> = (
+    Option<&'a PassportFile>,
+    Option<&'a PassportFile>,
+    Option<&'a PassportFile>,
+    Option<&'a Vec<PassportFile>>,
+    Option<&'a Vec<PassportFile>>,
+);
+
+fn get_element_files(element: &EncryptedPassportElement) -> ElementFiles<'_> {
+    use self::EncryptedPassportElement::*;
+    match element {
+        DriverLicense(element) => (
+            Some(&element.front_side),
+            Some(&element.reverse_side),
+            Some(&element.selfie),
+            None,
+            element.translation.as_ref(),
+        ),
+        IdentityCard(element) => (
+            Some(&element.front_side),
+            Some(&element.reverse_side),
+            Some(&element.selfie),
+            None,
+            element.translation.as_ref(),
+        ),
+        InternalPassport(element) => (
+            Some(&element.front_side),
+            None,
+            Some(&element.selfie),
+            None,
+            element.translation.as_ref(),
+        ),
+        Passport(element) => (
+            Some(&element.front_side),
+            None,
+            Some(&element.selfie),
+            None,
+            element.translation.as_ref(),
+        ),
+        BankStatement(element) => (None, None, None, Some(&element.files), element.translation.as_ref()),
+        PassportRegistration(element) => (None, None, None, Some(&element.files), element.translation.as_ref()),
+        RentalAgreement(element) => (None, None, None, Some(&element.files), element.translation.as_ref()),
+        TemporaryRegistration(element) => (None, None, None, Some(&element.files), element.translation.as_ref()),
+        UtilityBill(element) => (None, None, None, Some(&element.files), element.translation.as_ref()),
+        Address(_) | Email(_) | PersonalDetails(_) | PhoneNumber(_) => (None, None, None, None, None),
+    }
+}
+
+fn get_files(
+    element: &EncryptedPassportElement,
+    credentials: &SecureValue,
+) -> Result<Vec<EncryptedPassportFile>, DecryptError> {
+    let element_kind = element.get_kind();
+    let (front_side, reverse_side, selfie, files, translation) = get_element_files(element);
+    let mut result = Vec::new();
+    let mut push = |kind, file: &PassportFile, credentials: Option<&FileCredentials>| match credentials {
+        Some(credentials) => {
+            result.push(EncryptedPassportFile {
+                element_kind,
+                kind,
+                file: file.clone(),
+                credentials: credentials.clone(),
+                secret: base64::decode_block(&credentials.secret)?,
+                hash: base64::decode_block(&credentials.file_hash)?,
+            });
+            Ok(())
+        }
+        None => Err(DecryptError::MissingCredentials(element_kind)),
+    };
+    if let Some(file) = front_side {
+        push(PassportFileKind::FrontSide, file, credentials.front_side.as_ref())?;
+    }
+    if let Some(file) = reverse_side {
+        push(PassportFileKind::ReverseSide, file, credentials.reverse_side.as_ref())?;
+    }
+    if let Some(file) = selfie {
+        push(PassportFileKind::Selfie, file, credentials.selfie.as_ref())?;
+    }
+    for (idx, file) in files.into_iter().flatten().enumerate() {
+        let file_credentials = credentials.files.as_ref().and_then(|files| files.get(idx));
+        push(PassportFileKind::File, file, file_credentials)?;
+    }
+    for (idx, file) in translation.into_iter().flatten().enumerate() {
+        let file_credentials = credentials.translation.as_ref().and_then(|files| files.get(idx));
+        push(PassportFileKind::TranslationFile, file, file_credentials)?;
+    }
+    Ok(result)
+}
@@ -0,0 +1,8 @@
+mod data;
+mod decrypt;
+mod file;
+mod request;
+#[cfg(test)]
+mod tests;
+
+pub use self::{data::*, decrypt::*, file::*, request::*};
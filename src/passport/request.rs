@@ -0,0 +1,341 @@
+use crate::{
+    passport::decrypt::DecryptError,
+    types::{EncryptedPassportElementKind, InlineKeyboardButton, Integer},
+};
+use openssl::{error::ErrorStack, rand::rand_bytes};
+use serde::Serialize;
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Not,
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+use url::percent_encoding::{utf8_percent_encode, EncodeSet};
+
+const SCOPE_VERSION: Integer = 1;
+const DEFAULT_NONCE_TTL: Duration = Duration::from_secs(3600);
+const DEFAULT_MAX_NONCES: usize = 10_000;
+
+/// Telegram Passport authorization request
+///
+/// Send the link or the button to the user to start the passport flow,
+/// bot receives PassportData in a message when the user shares the data
+#[derive(Clone, Debug)]
+pub struct PassportRequest {
+    bot_id: Integer,
+    scope: PassportScope,
+    public_key: String,
+    nonce: String,
+}
+
+impl PassportRequest {
+    /// Creates a new request
+    ///
+    /// # Arguments
+    ///
+    /// * bot_id - Unique identifier of the bot
+    /// * scope - Data which the bot requests
+    /// * public_key - PEM-encoded public key of the bot, see PassportKey::get_public_key_pem()
+    /// * nonce - Unique nonce of the request, see PassportNonces::issue()
+    pub fn new<K, N>(bot_id: Integer, scope: PassportScope, public_key: K, nonce: N) -> Self
+    where
+        K: Into<String>,
+        N: Into<String>,
+    {
+        PassportRequest {
+            bot_id,
+            scope,
+            public_key: public_key.into(),
+            nonce: nonce.into(),
+        }
+    }
+
+    /// Returns scope of the request
+    pub fn get_scope(&self) -> &PassportScope {
+        &self.scope
+    }
+
+    /// Returns nonce of the request
+    pub fn get_nonce(&self) -> &str {
+        &self.nonce
+    }
+
+    /// Returns tg:// link which opens the authorization form
+    pub fn get_link(&self) -> Result<String, PassportRequestError> {
+        let scope = serde_json::to_string(&self.scope)?;
+        Ok(format!(
+            "tg://resolve?domain=telegrampassport&bot_id={}&scope={}&public_key={}&nonce={}",
+            self.bot_id,
+            utf8_percent_encode(&scope, ComponentEncodeSet),
+            utf8_percent_encode(&self.public_key, ComponentEncodeSet),
+            utf8_percent_encode(&self.nonce, ComponentEncodeSet)
+        ))
+    }
+
+    /// Returns an inline keyboard button which opens the authorization form
+    pub fn get_button<S: Into<String>>(&self, text: S) -> Result<InlineKeyboardButton, PassportRequestError> {
+        Ok(InlineKeyboardButton::with_url(text.into(), self.get_link()?))
+    }
+}
+
+/// Data which the bot requests from the user
+#[derive(Clone, Debug, Serialize)]
+pub struct PassportScope {
+    data: Vec<PassportScopeElement>,
+    v: Integer,
+}
+
+impl Default for PassportScope {
+    fn default() -> Self {
+        PassportScope {
+            data: Vec::new(),
+            v: SCOPE_VERSION,
+        }
+    }
+}
+
+impl PassportScope {
+    /// Creates an empty scope
+    pub fn new() -> Self {
+        PassportScope::default()
+    }
+
+    /// Requests an element
+    pub fn element(mut self, element: PassportScopeElement) -> Self {
+        self.data.push(element);
+        self
+    }
+
+    /// Returns requested elements
+    pub fn get_elements(&self) -> &[PassportScopeElement] {
+        &self.data
+    }
+
+    /// Returns required elements which are not satisfied by given types of shared elements
+    pub fn get_missing_elements<I>(&self, kinds: I) -> Vec<&PassportScopeElement>
+    where
+        I: IntoIterator<Item = EncryptedPassportElementKind>,
+    {
+        let kinds: HashSet<EncryptedPassportElementKind> = kinds.into_iter().collect();
+        self.data
+            .iter()
+            .filter(|element| !element.optional && !element.get_kinds().any(|kind| kinds.contains(&kind)))
+            .collect()
+    }
+}
+
+/// Element of passport scope
+///
+/// Either a single type of element or a group where the user chooses one of types
+#[derive(Clone, Debug, Serialize)]
+pub struct PassportScopeElement {
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    kind: Option<EncryptedPassportElementKind>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    one_of: Vec<PassportScopeElementType>,
+    #[serde(skip_serializing_if = "Not::not")]
+    selfie: bool,
+    #[serde(skip_serializing_if = "Not::not")]
+    translation: bool,
+    #[serde(skip_serializing_if = "Not::not")]
+    native_names: bool,
+    #[serde(skip)]
+    optional: bool,
+}
+
+#[derive(Clone, Debug, Serialize)]
+struct PassportScopeElementType {
+    #[serde(rename = "type")]
+    kind: EncryptedPassportElementKind,
+}
+
+impl PassportScopeElement {
+    /// Requests an element of given type
+    pub fn new(kind: EncryptedPassportElementKind) -> Self {
+        PassportScopeElement {
+            kind: Some(kind),
+            one_of: Vec::new(),
+            selfie: false,
+            translation: false,
+            native_names: false,
+            optional: false,
+        }
+    }
+
+    /// Requests one of elements of given types
+    pub fn one_of<I>(kinds: I) -> Self
+    where
+        I: IntoIterator<Item = EncryptedPassportElementKind>,
+    {
+        PassportScopeElement {
+            kind: None,
+            one_of: kinds
+                .into_iter()
+                .map(|kind| PassportScopeElementType { kind })
+                .collect(),
+            selfie: false,
+            translation: false,
+            native_names: false,
+            optional: false,
+        }
+    }
+
+    /// Requests a selfie with the document
+    pub fn selfie(mut self) -> Self {
+        self.selfie = true;
+        self
+    }
+
+    /// Requests a translation of the document
+    pub fn translation(mut self) -> Self {
+        self.translation = true;
+        self
+    }
+
+    /// Requests the name in the language of the user's country of residence
+    ///
+    /// Applies to personal_details only
+    pub fn native_names(mut self) -> Self {
+        self.native_names = true;
+        self
+    }
+
+    /// Marks the element as optional
+    ///
+    /// Optional elements are requested as usual,
+    /// but PassportScope::get_missing_elements() does not report them
+    pub fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+
+    /// Returns whether the element is optional
+    pub fn is_optional(&self) -> bool {
+        self.optional
+    }
+
+    /// Returns types of elements which satisfy this scope element
+    pub fn get_kinds<'a>(&'a self) -> impl Iterator<Item = EncryptedPassportElementKind> + 'a {
+        self.kind
+            .into_iter()
+            .chain(self.one_of.iter().map(|element| element.kind))
+    }
+}
+
+/// Nonces of issued authorization requests
+///
+/// A nonce is accepted within its TTL only,
+/// when the set is full, the nonce which expires first is evicted
+/// Clones share the same set of nonces
+#[derive(Clone, Debug)]
+pub struct PassportNonces {
+    issued: Arc<Mutex<HashMap<String, Instant>>>,
+    ttl: Duration,
+    max_entries: usize,
+}
+
+impl Default for PassportNonces {
+    fn default() -> Self {
+        PassportNonces {
+            issued: Arc::new(Mutex::new(HashMap::new())),
+            ttl: DEFAULT_NONCE_TTL,
+            max_entries: DEFAULT_MAX_NONCES,
+        }
+    }
+}
+
+impl PassportNonces {
+    /// Creates an empty set of nonces
+    pub fn new() -> Self {
+        PassportNonces::default()
+    }
+
+    /// How long a nonce is accepted, defaults to 1 hour
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Maximum number of remembered nonces, defaults to 10000
+    pub fn max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries.max(1);
+        self
+    }
+
+    /// Generates and remembers a random nonce
+    pub fn issue(&self) -> Result<String, PassportRequestError> {
+        let mut buf = [0; 16];
+        rand_bytes(&mut buf)?;
+        let nonce: String = buf.iter().map(|x| format!("{:02x}", x)).collect();
+        self.insert(nonce.clone());
+        Ok(nonce)
+    }
+
+    /// Remembers a nonce generated elsewhere
+    pub fn insert<S: Into<String>>(&self, nonce: S) {
+        let nonce = nonce.into();
+        let now = Instant::now();
+        let mut issued = self.lock();
+        issued.retain(|_, expires_at| *expires_at > now);
+        while issued.len() >= self.max_entries && !issued.contains_key(&nonce) {
+            let oldest = issued
+                .iter()
+                .min_by_key(|(_, expires_at)| **expires_at)
+                .map(|(nonce, _)| nonce.clone());
+            match oldest {
+                Some(oldest) => issued.remove(&oldest),
+                None => break,
+            };
+        }
+        issued.insert(nonce, now + self.ttl);
+    }
+
+    /// Checks that a nonce from decrypted credentials was issued and has not expired
+    ///
+    /// Each nonce is accepted only once
+    pub fn verify(&self, nonce: &str) -> Result<(), DecryptError> {
+        match self.lock().remove(nonce) {
+            Some(expires_at) if expires_at > Instant::now() => Ok(()),
+            _ => Err(DecryptError::UnknownNonce),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, Instant>> {
+        self.issued.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+/// Bytes which are percent-encoded in a query parameter value
+///
+/// Everything except unreserved characters of RFC 3986
+#[derive(Clone, Copy)]
+struct ComponentEncodeSet;
+
+impl EncodeSet for ComponentEncodeSet {
+    fn contains(&self, byte: u8) -> bool {
+        !(byte.is_ascii_alphanumeric() || b"-_.~".contains(&byte))
+    }
+}
+
+/// An error when building an authorization request
+#[derive(Debug, failure::Fail)]
+pub enum PassportRequestError {
+    /// Failed to generate a nonce
+    #[fail(display = "Failed to generate a nonce: {}", _0)]
+    Openssl(#[cause] ErrorStack),
+    /// Failed to serialize the scope
+    #[fail(display = "Failed to serialize scope: {}", _0)]
+    Json(#[cause] serde_json::Error),
+}
+
+impl From<ErrorStack> for PassportRequestError {
+    fn from(err: ErrorStack) -> Self {
+        PassportRequestError::Openssl(err)
+    }
+}
+
+impl From<serde_json::Error> for PassportRequestError {
+    fn from(err: serde_json::Error) -> Self {
+        PassportRequestError::Json(err)
+    }
+}
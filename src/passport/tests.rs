@@ -0,0 +1,495 @@
+use crate::{passport::*, types::*};
+use chrono::NaiveDate;
+use openssl::{
+    base64,
+    pkey::Private,
+    rand::rand_bytes,
+    rsa::{Padding, Rsa},
+    sha::{sha256, sha512},
+    symm::{Cipher, Crypter, Mode},
+};
+use serde_json::{json, Value};
+use std::{thread, time::Duration};
+
+fn encrypt_bytes(data: &[u8]) -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+    let padding = 32 + (16 - (data.len() + 32) % 16) % 16;
+    let mut padded = vec![0; padding];
+    rand_bytes(&mut padded).unwrap();
+    padded[0] = padding as u8;
+    padded.extend_from_slice(data);
+    let hash = sha256(&padded).to_vec();
+    let mut secret = vec![0; 32];
+    rand_bytes(&mut secret).unwrap();
+    let secret_hash = sha512(&[secret.as_slice(), hash.as_slice()].concat());
+    let mut crypter = Crypter::new(
+        Cipher::aes_256_cbc(),
+        Mode::Encrypt,
+        &secret_hash[..32],
+        Some(&secret_hash[32..48]),
+    )
+    .unwrap();
+    crypter.pad(false);
+    let mut encrypted = vec![0; padded.len() + 16];
+    let mut len = crypter.update(&padded, &mut encrypted).unwrap();
+    len += crypter.finalize(&mut encrypted[len..]).unwrap();
+    encrypted.truncate(len);
+    (encrypted, secret, hash)
+}
+
+pub(super) fn encrypt_data(value: &Value) -> (String, DataCredentials) {
+    let (data, secret, hash) = encrypt_bytes(&serde_json::to_vec(value).unwrap());
+    (
+        base64::encode_block(&data),
+        DataCredentials {
+            data_hash: base64::encode_block(&hash),
+            secret: base64::encode_block(&secret),
+        },
+    )
+}
+
+pub(super) fn encrypt_file(data: &[u8]) -> (Vec<u8>, FileCredentials) {
+    let (data, secret, hash) = encrypt_bytes(data);
+    (
+        data,
+        FileCredentials {
+            file_hash: base64::encode_block(&hash),
+            secret: base64::encode_block(&secret),
+        },
+    )
+}
+
+pub(super) fn encrypt_credentials(rsa: &Rsa<Private>, credentials: &Credentials) -> EncryptedCredentials {
+    let (data, secret, hash) = encrypt_bytes(&serde_json::to_vec(credentials).unwrap());
+    let mut encrypted_secret = vec![0; rsa.size() as usize];
+    let len = rsa
+        .public_encrypt(&secret, &mut encrypted_secret, Padding::PKCS1_OAEP)
+        .unwrap();
+    encrypted_secret.truncate(len);
+    EncryptedCredentials {
+        data: base64::encode_block(&data),
+        hash: base64::encode_block(&hash),
+        secret: base64::encode_block(&encrypted_secret),
+    }
+}
+
+pub(super) fn generate_key() -> (PassportKey, Rsa<Private>) {
+    let rsa = Rsa::generate(2048).unwrap();
+    let key = PassportKey::from_pem(&rsa.private_key_to_pem().unwrap()).unwrap();
+    (key, rsa)
+}
+
+pub(super) fn get_passport_file(file_id: &str) -> PassportFile {
+    PassportFile {
+        file_id: String::from(file_id),
+        file_size: 1,
+        file_date: 0,
+    }
+}
+
+#[test]
+fn test_decrypt() {
+    let (key, rsa) = generate_key();
+    let personal_details = json!({"first_name": "John", "last_name": "Doe"});
+    let (personal_details_data, personal_details_credentials) = encrypt_data(&personal_details);
+    let passport = json!({"document_no": "123"});
+    let (passport_data, passport_credentials) = encrypt_data(&passport);
+    let (front_side, front_side_credentials) = encrypt_file(b"front side");
+    let credentials = Credentials {
+        secure_data: SecureData {
+            personal_details: Some(SecureValue {
+                data: Some(personal_details_credentials.clone()),
+                ..Default::default()
+            }),
+            passport: Some(SecureValue {
+                data: Some(passport_credentials),
+                front_side: Some(front_side_credentials.clone()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+        nonce: String::from("nonce"),
+    };
+    let passport_data = PassportData {
+        data: vec![
+            EncryptedPassportElement::PersonalDetails(EncryptedPassportElementPersonalDetails {
+                data: personal_details_data.clone(),
+                hash: String::from("h"),
+            }),
+            EncryptedPassportElement::Passport(EncryptedPassportElementPassport {
+                data: passport_data,
+                front_side: get_passport_file("front"),
+                selfie: get_passport_file("selfie"),
+                translation: None,
+                hash: String::from("h"),
+            }),
+            EncryptedPassportElement::Email(EncryptedPassportElementEmail {
+                email: String::from("u@h.z"),
+                hash: String::from("h"),
+            }),
+        ],
+        credentials: encrypt_credentials(&rsa, &credentials),
+    };
+    assert_eq!(
+        key.decrypt_credentials(&passport_data.credentials).unwrap(),
+        credentials
+    );
+
+    let decrypted = decrypt(&key, &passport_data).unwrap();
+    assert_eq!(decrypted.nonce, "nonce");
+    assert_eq!(decrypted.elements.len(), 3);
+    assert_eq!(decrypted.elements[0].data, Some(personal_details));
+    assert_eq!(decrypted.elements[1].data, Some(passport));
+    assert_eq!(
+        decrypted.elements[1].credentials.front_side,
+        Some(front_side_credentials.clone())
+    );
+    assert_eq!(decrypted.elements[2].data, None);
+    assert_eq!(decrypted.elements[2].credentials, SecureValue::default());
+
+    assert_eq!(front_side_credentials.decrypt(&front_side).unwrap(), b"front side");
+    let mut tampered = front_side.clone();
+    tampered[0] ^= 1;
+    match front_side_credentials.decrypt(&tampered) {
+        Err(DecryptError::HashMismatch) => {}
+        result => panic!("Unexpected result: {:?}", result),
+    }
+    match personal_details_credentials.decrypt("bad base64!") {
+        Err(DecryptError::Openssl(_)) => {}
+        result => panic!("Unexpected result: {:?}", result),
+    }
+
+    let mut passport_data = passport_data;
+    passport_data
+        .data
+        .push(EncryptedPassportElement::Address(EncryptedPassportElementAddress {
+            data: personal_details_data,
+            hash: String::from("h"),
+        }));
+    match decrypt(&key, &passport_data) {
+        Err(DecryptError::MissingCredentials(EncryptedPassportElementKind::Address)) => {}
+        result => panic!("Unexpected result: {:?}", result),
+    }
+
+    let (other_key, _) = generate_key();
+    assert!(other_key.decrypt_credentials(&passport_data.credentials).is_err());
+    assert!(PassportKey::from_pem(b"not a key").is_err());
+}
+
+#[test]
+fn test_deserialize_credentials() {
+    let credentials: Credentials = serde_json::from_value(json!({
+        "secure_data": {
+            "driver_license": {
+                "data": {"data_hash": "dh", "secret": "ds"},
+                "front_side": {"file_hash": "fh", "secret": "fs"},
+                "translation": [{"file_hash": "th", "secret": "ts"}]
+            },
+            "utility_bill": {
+                "files": [{"file_hash": "uh", "secret": "us"}]
+            }
+        },
+        "payload": "nonce"
+    }))
+    .unwrap();
+    assert_eq!(credentials.nonce, "nonce");
+    let driver_license = credentials
+        .secure_data
+        .get(&EncryptedPassportElementKind::DriverLicense)
+        .unwrap();
+    assert_eq!(driver_license.data.as_ref().unwrap().data_hash, "dh");
+    assert_eq!(driver_license.front_side.as_ref().unwrap().secret, "fs");
+    assert_eq!(driver_license.translation.as_ref().unwrap().len(), 1);
+    assert_eq!(
+        credentials
+            .secure_data
+            .get(&EncryptedPassportElementKind::UtilityBill)
+            .unwrap()
+            .files
+            .as_ref()
+            .unwrap()[0]
+            .file_hash,
+        "uh"
+    );
+    assert!(credentials
+        .secure_data
+        .get(&EncryptedPassportElementKind::Email)
+        .is_none());
+}
+
+#[test]
+fn test_parse_typed_data() {
+    let (key, rsa) = generate_key();
+    let personal_details = json!({
+        "first_name": "John",
+        "last_name": "Doe",
+        "middle_name": "",
+        "birth_date": "31.12.1990",
+        "gender": "male",
+        "country_code": "us",
+        "residence_country_code": "GB",
+        "first_name_native": "Джон",
+        "last_name_native": "",
+        "middle_name_native": ""
+    });
+    let (personal_details_data, personal_details_credentials) = encrypt_data(&personal_details);
+    let (passport_data, passport_credentials) = encrypt_data(&json!({"document_no": "123", "expiry_date": ""}));
+    let address = json!({
+        "street_line1": "Baker street, 221b",
+        "street_line2": "",
+        "city": "London",
+        "state": "",
+        "country_code": "GB",
+        "post_code": "NW1 6XE"
+    });
+    let (address_data, address_credentials) = encrypt_data(&address);
+    let credentials = Credentials {
+        secure_data: SecureData {
+            personal_details: Some(SecureValue {
+                data: Some(personal_details_credentials),
+                ..Default::default()
+            }),
+            passport: Some(SecureValue {
+                data: Some(passport_credentials),
+                ..Default::default()
+            }),
+            address: Some(SecureValue {
+                data: Some(address_credentials),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+        nonce: String::from("nonce"),
+    };
+    let passport_data = PassportData {
+        data: vec![
+            EncryptedPassportElement::PersonalDetails(EncryptedPassportElementPersonalDetails {
+                data: personal_details_data,
+                hash: String::from("h"),
+            }),
+            EncryptedPassportElement::Passport(EncryptedPassportElementPassport {
+                data: passport_data,
+                front_side: get_passport_file("front"),
+                selfie: get_passport_file("selfie"),
+                translation: None,
+                hash: String::from("h"),
+            }),
+            EncryptedPassportElement::Address(EncryptedPassportElementAddress {
+                data: address_data,
+                hash: String::from("h"),
+            }),
+        ],
+        credentials: encrypt_credentials(&rsa, &credentials),
+    };
+    let passport = DecryptedPassport::parse(decrypt(&key, &passport_data).unwrap()).unwrap();
+    assert_eq!(passport.nonce, "nonce");
+    assert_eq!(passport.elements.len(), 3);
+
+    let personal_details = passport.get_personal_details().unwrap();
+    assert_eq!(personal_details.first_name, "John");
+    assert_eq!(personal_details.middle_name, None);
+    assert_eq!(
+        personal_details.birth_date,
+        NaiveDate::from_ymd_opt(1990, 12, 31).unwrap()
+    );
+    assert_eq!(personal_details.gender, Gender::Male);
+    assert_eq!(personal_details.country_code.as_str(), "US");
+    assert_eq!(personal_details.residence_country_code.as_str(), "GB");
+    assert_eq!(personal_details.first_name_native, Some(String::from("Джон")));
+    assert_eq!(personal_details.last_name_native, None);
+
+    let document = passport
+        .get_id_document(EncryptedPassportElementKind::Passport)
+        .unwrap();
+    assert_eq!(document.document_no, "123");
+    assert_eq!(document.expiry_date, None);
+    assert!(passport
+        .get_id_document(EncryptedPassportElementKind::DriverLicense)
+        .is_none());
+
+    let address = passport.get_address().unwrap();
+    assert_eq!(address.city, "London");
+    assert_eq!(address.street_line2, None);
+    assert_eq!(address.country_code.to_string(), "GB");
+
+    let document: IdDocumentData =
+        serde_json::from_value(json!({"document_no": "1", "expiry_date": "01.02.2030"})).unwrap();
+    assert_eq!(document.expiry_date, NaiveDate::from_ymd_opt(2030, 2, 1));
+    assert_eq!(
+        serde_json::to_value(&document).unwrap(),
+        json!({"document_no": "1", "expiry_date": "01.02.2030"})
+    );
+    assert!(
+        serde_json::from_value::<IdDocumentData>(json!({"document_no": "1", "expiry_date": "2030-02-01"})).is_err()
+    );
+    let mut address = serde_json::to_value(address).unwrap();
+    address["country_code"] = json!("USA");
+    assert!(serde_json::from_value::<ResidentialAddress>(address).is_err());
+    assert!("U1".parse::<CountryCode>().is_err());
+    match PassportElementData::parse(&EncryptedPassportElementKind::Email, json!({})) {
+        Err(DecryptError::UnexpectedData(EncryptedPassportElementKind::Email)) => {}
+        result => panic!("Unexpected result: {:?}", result),
+    }
+}
+
+#[test]
+fn test_passport_files() {
+    let (front_side, front_side_credentials) = encrypt_file(b"front side");
+    let (_, reverse_side_credentials) = encrypt_file(b"reverse side");
+    let (_, selfie_credentials) = encrypt_file(b"selfie");
+    let (_, translation_credentials) = encrypt_file(b"translation");
+    let element = DecryptedPassportElement {
+        element: EncryptedPassportElement::DriverLicense(EncryptedPassportElementDriverLicense {
+            data: String::from("d"),
+            front_side: get_passport_file("front"),
+            reverse_side: get_passport_file("reverse"),
+            selfie: get_passport_file("selfie"),
+            translation: Some(vec![get_passport_file("translation")]),
+            hash: String::from("h"),
+        }),
+        data: None,
+        credentials: SecureValue {
+            front_side: Some(front_side_credentials.clone()),
+            reverse_side: Some(reverse_side_credentials),
+            selfie: Some(selfie_credentials),
+            translation: Some(vec![translation_credentials]),
+            ..Default::default()
+        },
+    };
+    let files = element.get_files().unwrap();
+    assert_eq!(
+        files.iter().map(|file| file.get_kind()).collect::<Vec<_>>(),
+        vec![
+            PassportFileKind::FrontSide,
+            PassportFileKind::ReverseSide,
+            PassportFileKind::Selfie,
+            PassportFileKind::TranslationFile,
+        ]
+    );
+    assert_eq!(files[1].get_file().file_id, "reverse");
+    assert_eq!(files[0].decrypt(&front_side).unwrap(), b"front side");
+
+    let err = files[1].decrypt(&front_side).unwrap_err();
+    match err.get_cause() {
+        DecryptError::HashMismatch => {}
+        cause => panic!("Unexpected cause: {:?}", cause),
+    }
+    assert_eq!(
+        serde_json::to_value(err.get_element_error("Upload the file again")).unwrap(),
+        json!({
+            "source": "reverse_side",
+            "type": "driver_license",
+            "file_hash": files[1].get_credentials().file_hash,
+            "message": "Upload the file again"
+        })
+    );
+    assert_eq!(
+        serde_json::to_value(files[3].get_error("Bad translation")).unwrap()["source"],
+        "translation_file"
+    );
+
+    let (_, file_credentials) = encrypt_file(b"bill");
+    let mut element = DecryptedPassportElement {
+        element: EncryptedPassportElement::UtilityBill(EncryptedPassportElementUtilityBill {
+            files: vec![get_passport_file("bill")],
+            translation: None,
+            hash: String::from("h"),
+        }),
+        data: None,
+        credentials: SecureValue {
+            files: Some(vec![file_credentials]),
+            ..Default::default()
+        },
+    };
+    let files = element.get_files().unwrap();
+    assert_eq!(files.len(), 1);
+    assert_eq!(
+        serde_json::to_value(files[0].get_error("Blurry")).unwrap()["source"],
+        "file"
+    );
+    element.credentials.files = None;
+    match element.get_files() {
+        Err(DecryptError::MissingCredentials(EncryptedPassportElementKind::UtilityBill)) => {}
+        result => panic!("Unexpected result: {:?}", result),
+    }
+}
+
+#[test]
+fn test_passport_request() {
+    let scope = PassportScope::new()
+        .element(PassportScopeElement::new(EncryptedPassportElementKind::PersonalDetails).native_names())
+        .element(
+            PassportScopeElement::one_of(vec![
+                EncryptedPassportElementKind::Passport,
+                EncryptedPassportElementKind::IdentityCard,
+            ])
+            .selfie()
+            .translation(),
+        )
+        .element(PassportScopeElement::new(EncryptedPassportElementKind::PhoneNumber).optional());
+    assert_eq!(
+        serde_json::to_value(&scope).unwrap(),
+        json!({
+            "data": [
+                {"type": "personal_details", "native_names": true},
+                {"one_of": [{"type": "passport"}, {"type": "identity_card"}], "selfie": true, "translation": true},
+                {"type": "phone_number"}
+            ],
+            "v": 1
+        })
+    );
+
+    let missing = scope.get_missing_elements(vec![EncryptedPassportElementKind::IdentityCard]);
+    assert_eq!(missing.len(), 1);
+    assert_eq!(
+        missing[0].get_kinds().collect::<Vec<_>>(),
+        vec![EncryptedPassportElementKind::PersonalDetails]
+    );
+    assert!(scope
+        .get_missing_elements(vec![
+            EncryptedPassportElementKind::PersonalDetails,
+            EncryptedPassportElementKind::Passport,
+        ])
+        .is_empty());
+
+    let request = PassportRequest::new(
+        123,
+        PassportScope::new().element(PassportScopeElement::new(EncryptedPassportElementKind::Email)),
+        "-----BEGIN PUBLIC KEY-----\nAB+/=\n-----END PUBLIC KEY-----\n",
+        "n&1",
+    );
+    let link = "tg://resolve?domain=telegrampassport&bot_id=123\
+                &scope=%7B%22data%22%3A%5B%7B%22type%22%3A%22email%22%7D%5D%2C%22v%22%3A1%7D\
+                &public_key=-----BEGIN%20PUBLIC%20KEY-----%0AAB%2B%2F%3D%0A-----END%20PUBLIC%20KEY-----%0A\
+                &nonce=n%261";
+    assert_eq!(request.get_link().unwrap(), link);
+    assert_eq!(
+        serde_json::to_value(request.get_button("Share").unwrap()).unwrap(),
+        json!({"text": "Share", "url": link})
+    );
+
+    let nonces = PassportNonces::new();
+    let nonce = nonces.issue().unwrap();
+    assert_eq!(nonce.len(), 32);
+    nonces.clone().insert("custom");
+    assert!(nonces.verify("custom").is_ok());
+    assert!(nonces.verify(&nonce).is_ok());
+    for nonce in &[nonce.as_str(), "custom", "unknown"] {
+        match nonces.verify(nonce) {
+            Err(DecryptError::UnknownNonce) => {}
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    let nonces = PassportNonces::new().ttl(Duration::from_secs(0));
+    nonces.insert("expired");
+    assert!(nonces.verify("expired").is_err());
+
+    let nonces = PassportNonces::new().max_entries(2);
+    for nonce in &["first", "second", "third"] {
+        nonces.insert(*nonce);
+        thread::sleep(Duration::from_millis(1));
+    }
+    assert!(nonces.verify("first").is_err());
+    assert!(nonces.verify("second").is_ok());
+    assert!(nonces.verify("third").is_ok());
+}
@@ -0,0 +1,301 @@
+use crate::{
+    api::{Api, ApiFuture},
+    methods::{AnswerPreCheckoutQuery, AnswerShippingQuery, SendInvoice},
+    types::{
+        Integer, Message, MessageData, PreCheckoutQuery, ShippingAddress, ShippingOption, ShippingQuery,
+        SuccessfulPayment, Update, UpdateKind,
+    },
+};
+use failure::Error;
+use futures::{future, Future, IntoFuture};
+use std::{sync::Arc, time::Duration};
+use tokio_timer::Timeout;
+
+mod store;
+#[cfg(test)]
+mod tests;
+
+pub use self::store::*;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(8);
+const UNKNOWN_ORDER_MESSAGE: &str = "This order is no longer available";
+const ORDER_CHANGED_MESSAGE: &str = "This order has been changed, please try again";
+const NO_SHIPPING_MESSAGE: &str = "Shipping to this address is not available";
+const TIMEOUT_MESSAGE: &str = "Failed to process the order in time, please try again";
+
+type ShippingFuture = Box<dyn Future<Item = Vec<ShippingOption>, Error = String> + Send>;
+type ShippingCallback = dyn Fn(&Order, &ShippingAddress) -> ShippingFuture + Send + Sync;
+type CheckoutFuture = Box<dyn Future<Item = (), Error = String> + Send>;
+type CheckoutCallback = dyn Fn(&Order, &PreCheckoutQuery) -> CheckoutFuture + Send + Sync;
+type AnswerFuture = Box<dyn Future<Item = Result<Vec<ShippingOption>, String>, Error = Error> + Send>;
+
+/// Handles shipping queries, pre-checkout queries and payments for sent invoices
+///
+/// Invoices are correlated with queries by payload, so each invoice must have a unique payload
+/// Queries are answered automatically, an error message is sent to the user
+/// when an order is unknown, a callback rejects it or does not finish within the timeout
+/// Clones share the same store and callbacks
+#[derive(Clone)]
+pub struct Payments {
+    api: Api,
+    store: Arc<dyn OrderStore>,
+    shipping: Option<Arc<ShippingCallback>>,
+    checkout: Option<Arc<CheckoutCallback>>,
+    timeout: Duration,
+}
+
+impl Payments {
+    /// Creates a new Payments
+    ///
+    /// # Arguments
+    ///
+    /// * api - Api to send invoices and answers
+    /// * store - Storage of orders
+    pub fn new<S: OrderStore + 'static>(api: Api, store: S) -> Self {
+        Payments {
+            api,
+            store: Arc::new(store),
+            shipping: None,
+            checkout: None,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Callback which returns shipping options for an address
+    ///
+    /// An error is shown to the user as is
+    /// Without the callback every shipping query is rejected
+    pub fn shipping<F, R>(mut self, callback: F) -> Self
+    where
+        F: Fn(&Order, &ShippingAddress) -> R + Send + Sync + 'static,
+        R: IntoFuture<Item = Vec<ShippingOption>, Error = String>,
+        R::Future: Send + 'static,
+    {
+        self.shipping = Some(Arc::new(move |order: &Order, address: &ShippingAddress| {
+            Box::new(callback(order, address).into_future()) as ShippingFuture
+        }));
+        self
+    }
+
+    /// Callback which approves a checkout, e.g. checks that goods are in stock
+    ///
+    /// An error is shown to the user as is
+    /// Without the callback every checkout of a known order is approved
+    pub fn checkout<F, R>(mut self, callback: F) -> Self
+    where
+        F: Fn(&Order, &PreCheckoutQuery) -> R + Send + Sync + 'static,
+        R: IntoFuture<Item = (), Error = String>,
+        R::Future: Send + 'static,
+    {
+        self.checkout = Some(Arc::new(move |order: &Order, query: &PreCheckoutQuery| {
+            Box::new(callback(order, query).into_future()) as CheckoutFuture
+        }));
+        self
+    }
+
+    /// Maximum time to wait for a callback, defaults to 8 seconds
+    ///
+    /// Telegram requires an answer to a pre-checkout query within 10 seconds
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Returns an order by invoice payload
+    pub fn get_order(&self, payload: &str) -> Option<Order> {
+        self.store.get(payload)
+    }
+
+    /// Removes an order, e.g. when it is delivered
+    pub fn remove_order(&self, payload: &str) {
+        self.store.remove(payload)
+    }
+
+    /// Stores a pending order and sends the invoice
+    ///
+    /// Fails if an order with the same payload exists,
+    /// the order is removed when the invoice can not be sent
+    pub fn send_invoice(&self, invoice: &SendInvoice) -> ApiFuture<Message> {
+        let order = Order::new(invoice);
+        let payload = order.payload.clone();
+        if !self.store.insert(order) {
+            return ApiFuture::new(future::err(PaymentError::DuplicateOrder(payload).into()));
+        }
+        let store = self.store.clone();
+        ApiFuture::new(self.api.execute(invoice).map_err(move |err| {
+            store.remove(&payload);
+            err
+        }))
+    }
+
+    /// Handles payment updates
+    ///
+    /// Returns None if the update is not related to payments
+    pub fn handle_update(&self, update: &Update) -> Option<ApiFuture<PaymentEvent>> {
+        match update.kind {
+            UpdateKind::ShippingQuery(ref query) => Some(self.answer_shipping_query(query)),
+            UpdateKind::PreCheckoutQuery(ref query) => Some(self.answer_pre_checkout_query(query)),
+            UpdateKind::Message(ref message) => match message.data {
+                MessageData::SuccessfulPayment(ref payment) => {
+                    let user_id = message.get_user().map_or(message.get_chat_id(), |user| user.id);
+                    Some(ApiFuture::new(future::result(
+                        self.record_payment(user_id, payment)
+                            .map(|order| PaymentEvent::Paid(Box::new(order)))
+                            .map_err(Error::from),
+                    )))
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Answers a shipping query using the shipping callback
+    pub fn answer_shipping_query(&self, query: &ShippingQuery) -> ApiFuture<PaymentEvent> {
+        let api = self.api.clone();
+        let (id, payload) = (query.id.clone(), query.invoice_payload.clone());
+        ApiFuture::new(self.process_shipping_query(query).and_then(move |result| {
+            let method = match result {
+                Ok(ref options) => AnswerShippingQuery::ok(id, options.clone()),
+                Err(ref message) => AnswerShippingQuery::error(id, message.clone()),
+            };
+            api.execute(&method).map(move |_| PaymentEvent::ShippingAnswered {
+                payload,
+                error: result.err(),
+            })
+        }))
+    }
+
+    /// Answers a pre-checkout query using the checkout callback
+    pub fn answer_pre_checkout_query(&self, query: &PreCheckoutQuery) -> ApiFuture<PaymentEvent> {
+        let api = self.api.clone();
+        let (id, payload) = (query.id.clone(), query.invoice_payload.clone());
+        ApiFuture::new(self.process_pre_checkout_query(query).and_then(move |result| {
+            let method = match result {
+                Ok(()) => AnswerPreCheckoutQuery::ok(id),
+                Err(ref message) => AnswerPreCheckoutQuery::error(id, message.clone()),
+            };
+            api.execute(&method).map(move |_| PaymentEvent::CheckoutAnswered {
+                payload,
+                error: result.err(),
+            })
+        }))
+    }
+
+    /// Records a successful payment and returns the paid order
+    pub fn record_payment(&self, user_id: Integer, payment: &SuccessfulPayment) -> Result<Order, PaymentError> {
+        self.store.set_paid(
+            &payment.invoice_payload,
+            Receipt {
+                user_id,
+                payment: payment.clone(),
+            },
+        )
+    }
+
+    pub(crate) fn process_shipping_query(&self, query: &ShippingQuery) -> AnswerFuture {
+        let order = match self.store.get(&query.invoice_payload) {
+            Some(ref order) if order.status == OrderStatus::Pending => order.clone(),
+            _ => return Box::new(future::ok(Err(String::from(UNKNOWN_ORDER_MESSAGE)))),
+        };
+        let callback = match self.shipping {
+            Some(ref callback) => callback.clone(),
+            None => return Box::new(future::ok(Err(String::from(NO_SHIPPING_MESSAGE)))),
+        };
+        let store = self.store.clone();
+        let options = callback(&order, &query.shipping_address);
+        Box::new(with_timeout(options, self.timeout).map(move |result| {
+            let options = result?;
+            if options.is_empty() {
+                return Err(String::from(NO_SHIPPING_MESSAGE));
+            }
+            // The order could be changed while the callback was running
+            match store.set_shipping_options(&order.payload, options.clone()) {
+                Ok(_) => Ok(options),
+                Err(_) => Err(String::from(UNKNOWN_ORDER_MESSAGE)),
+            }
+        }))
+    }
+
+    pub(crate) fn process_pre_checkout_query(
+        &self,
+        query: &PreCheckoutQuery,
+    ) -> Box<dyn Future<Item = Result<(), String>, Error = Error> + Send> {
+        let order = match self.store.get(&query.invoice_payload) {
+            Some(ref order) if !order.is_paid() => order.clone(),
+            _ => return Box::new(future::ok(Err(String::from(UNKNOWN_ORDER_MESSAGE)))),
+        };
+        let total_amount = order.get_total_amount(query.shipping_option_id.as_deref());
+        if order.currency != query.currency || total_amount != Some(query.total_amount) {
+            return Box::new(future::ok(Err(String::from(ORDER_CHANGED_MESSAGE))));
+        }
+        let approved: CheckoutFuture = match self.checkout {
+            Some(ref callback) => callback(&order, query),
+            None => Box::new(future::ok(())),
+        };
+        let store = self.store.clone();
+        Box::new(with_timeout(approved, self.timeout).map(move |result| {
+            result?;
+            match store.set_approved(&order.payload) {
+                Ok(_) => Ok(()),
+                Err(_) => Err(String::from(UNKNOWN_ORDER_MESSAGE)),
+            }
+        }))
+    }
+}
+
+fn with_timeout<F>(f: F, timeout: Duration) -> impl Future<Item = Result<F::Item, String>, Error = Error>
+where
+    F: Future<Error = String>,
+{
+    Timeout::new(f, timeout).then(|result| {
+        Ok(result.map_err(|err| {
+            if err.is_inner() {
+                err.into_inner().unwrap_or_else(|| String::from(TIMEOUT_MESSAGE))
+            } else {
+                if err.is_timer() {
+                    log::error!("Timer error: {:?}", err);
+                }
+                String::from(TIMEOUT_MESSAGE)
+            }
+        }))
+    })
+}
+
+/// Result of handling a payment update
+#[derive(Clone, Debug)]
+pub enum PaymentEvent {
+    /// Shipping query has been answered
+    ShippingAnswered {
+        /// Invoice payload
+        payload: String,
+        /// Error shown to the user, None if shipping options were sent
+        error: Option<String>,
+    },
+    /// Pre-checkout query has been answered
+    CheckoutAnswered {
+        /// Invoice payload
+        payload: String,
+        /// Error shown to the user, None if the checkout was approved
+        error: Option<String>,
+    },
+    /// Payment has been received and recorded
+    Paid(Box<Order>),
+}
+
+/// An error when storing or updating an order
+#[derive(Debug, failure::Fail)]
+pub enum PaymentError {
+    /// There is no order with such payload
+    #[fail(display = "Unknown order: {}", _0)]
+    UnknownOrder(String),
+    /// The order has been paid already
+    #[fail(display = "Order {} has been paid already", _0)]
+    AlreadyPaid(String),
+    /// An order with the same payload exists
+    #[fail(display = "Order {} already exists", _0)]
+    DuplicateOrder(String),
+    /// The order is approved or paid already
+    #[fail(display = "Order {} is not pending", _0)]
+    NotPending(String),
+}
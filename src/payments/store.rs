@@ -0,0 +1,255 @@
+use crate::{
+    methods::SendInvoice,
+    payments::PaymentError,
+    types::{Integer, LabeledPrice, ShippingOption, SuccessfulPayment},
+};
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+const DEFAULT_ORDER_TTL: Duration = Duration::from_secs(7 * 24 * 3600);
+const DEFAULT_MAX_ORDERS: usize = 10_000;
+
+/// An order created when an invoice is sent
+///
+/// Orders are identified by invoice payload, so the payload must be unique
+#[derive(Clone, Debug)]
+pub struct Order {
+    /// Bot-defined invoice payload
+    pub payload: String,
+    /// Chat the invoice was sent to
+    pub chat_id: Integer,
+    /// Three-letter ISO 4217 currency code
+    pub currency: String,
+    /// Price breakdown of the invoice
+    pub prices: Vec<LabeledPrice>,
+    /// Shipping options offered to the user
+    pub shipping_options: Vec<ShippingOption>,
+    /// Current state of the order
+    pub status: OrderStatus,
+}
+
+impl Order {
+    /// Creates a pending order for an invoice
+    pub fn new(invoice: &SendInvoice) -> Self {
+        Order {
+            payload: String::from(invoice.get_payload()),
+            chat_id: invoice.get_chat_id(),
+            currency: String::from(invoice.get_currency()),
+            prices: invoice.get_prices().to_vec(),
+            shipping_options: Vec::new(),
+            status: OrderStatus::Pending,
+        }
+    }
+
+    /// Returns whether the payment is received
+    pub fn is_paid(&self) -> bool {
+        matches!(self.status, OrderStatus::Paid(_))
+    }
+
+    /// Returns total amount of the order including price of a shipping option
+    ///
+    /// Returns None if the shipping option was not offered
+    pub fn get_total_amount(&self, shipping_option_id: Option<&str>) -> Option<Integer> {
+        let shipping = match shipping_option_id {
+            Some(id) => {
+                let option = self.shipping_options.iter().find(|option| option.id == id)?;
+                option.prices.iter().map(|price| price.amount).sum()
+            }
+            None => 0,
+        };
+        Some(self.prices.iter().map(|price| price.amount).sum::<Integer>() + shipping)
+    }
+}
+
+/// State of an order
+#[derive(Clone, Debug, PartialEq)]
+pub enum OrderStatus {
+    /// Invoice is sent
+    Pending,
+    /// Checkout is approved, waiting for the payment
+    Approved,
+    /// Payment is received
+    Paid(Box<Receipt>),
+}
+
+/// Information about a received payment
+#[derive(Clone, Debug, PartialEq)]
+pub struct Receipt {
+    /// Identifier of the user who paid
+    pub user_id: Integer,
+    /// Payment details
+    pub payment: SuccessfulPayment,
+}
+
+/// Storage of orders
+///
+/// Implement it to keep orders in a database
+pub trait OrderStore: Send + Sync {
+    /// Returns an order by invoice payload
+    fn get(&self, payload: &str) -> Option<Order>;
+
+    /// Inserts or replaces an order
+    fn put(&self, order: Order);
+
+    /// Inserts an order if there is no order with the same payload
+    ///
+    /// Returns false if the payload is already used
+    fn insert(&self, order: Order) -> bool;
+
+    /// Marks an order as paid in a single step, so a payment is never recorded twice
+    ///
+    /// Returns the paid order
+    fn set_paid(&self, payload: &str, receipt: Receipt) -> Result<Order, PaymentError>;
+
+    /// Replaces shipping options of a pending order in a single step
+    ///
+    /// Returns the updated order
+    fn set_shipping_options(&self, payload: &str, options: Vec<ShippingOption>) -> Result<Order, PaymentError>;
+
+    /// Marks an order which is not paid as approved in a single step
+    ///
+    /// Returns the approved order
+    fn set_approved(&self, payload: &str) -> Result<Order, PaymentError>;
+
+    /// Removes an order
+    fn remove(&self, payload: &str);
+}
+
+/// Order store which keeps orders in memory
+///
+/// An order is kept within its TTL since the last change only,
+/// when the store is full, the order which expires first is evicted
+/// Clones share the same orders
+#[derive(Clone, Debug)]
+pub struct MemoryOrderStore {
+    orders: Arc<Mutex<HashMap<String, (Order, Instant)>>>,
+    ttl: Duration,
+    max_entries: usize,
+}
+
+impl Default for MemoryOrderStore {
+    fn default() -> Self {
+        MemoryOrderStore {
+            orders: Arc::new(Mutex::new(HashMap::new())),
+            ttl: DEFAULT_ORDER_TTL,
+            max_entries: DEFAULT_MAX_ORDERS,
+        }
+    }
+}
+
+impl MemoryOrderStore {
+    /// Creates an empty store
+    pub fn new() -> Self {
+        MemoryOrderStore::default()
+    }
+
+    /// How long an order is kept since the last change, defaults to 7 days
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Maximum number of kept orders, defaults to 10000
+    pub fn max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries.max(1);
+        self
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, (Order, Instant)>> {
+        self.orders.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn evict(&self, orders: &mut HashMap<String, (Order, Instant)>, payload: &str) {
+        let now = Instant::now();
+        orders.retain(|_, (_, expires_at)| *expires_at > now);
+        while orders.len() >= self.max_entries && !orders.contains_key(payload) {
+            let oldest = orders
+                .iter()
+                .min_by_key(|(_, (_, expires_at))| *expires_at)
+                .map(|(payload, _)| payload.clone());
+            match oldest {
+                Some(oldest) => orders.remove(&oldest),
+                None => break,
+            };
+        }
+    }
+
+    fn update<F>(&self, payload: &str, f: F) -> Result<Order, PaymentError>
+    where
+        F: FnOnce(&mut Order) -> Result<(), PaymentError>,
+    {
+        let now = Instant::now();
+        let mut orders = self.lock();
+        let (order, expires_at) = match orders.get_mut(payload) {
+            Some((order, expires_at)) if *expires_at > now => (order, expires_at),
+            _ => return Err(PaymentError::UnknownOrder(String::from(payload))),
+        };
+        f(order)?;
+        *expires_at = now + self.ttl;
+        Ok(order.clone())
+    }
+}
+
+impl OrderStore for MemoryOrderStore {
+    fn get(&self, payload: &str) -> Option<Order> {
+        match self.lock().get(payload) {
+            Some((order, expires_at)) if *expires_at > Instant::now() => Some(order.clone()),
+            _ => None,
+        }
+    }
+
+    fn put(&self, order: Order) {
+        let mut orders = self.lock();
+        self.evict(&mut orders, &order.payload);
+        orders.insert(order.payload.clone(), (order, Instant::now() + self.ttl));
+    }
+
+    fn insert(&self, order: Order) -> bool {
+        let mut orders = self.lock();
+        self.evict(&mut orders, &order.payload);
+        match orders.entry(order.payload.clone()) {
+            Entry::Occupied(_) => false,
+            Entry::Vacant(entry) => {
+                entry.insert((order, Instant::now() + self.ttl));
+                true
+            }
+        }
+    }
+
+    fn set_paid(&self, payload: &str, receipt: Receipt) -> Result<Order, PaymentError> {
+        self.update(payload, |order| {
+            if order.is_paid() {
+                return Err(PaymentError::AlreadyPaid(order.payload.clone()));
+            }
+            order.status = OrderStatus::Paid(Box::new(receipt));
+            Ok(())
+        })
+    }
+
+    fn set_shipping_options(&self, payload: &str, options: Vec<ShippingOption>) -> Result<Order, PaymentError> {
+        self.update(payload, |order| {
+            if order.status != OrderStatus::Pending {
+                return Err(PaymentError::NotPending(order.payload.clone()));
+            }
+            order.shipping_options = options;
+            Ok(())
+        })
+    }
+
+    fn set_approved(&self, payload: &str) -> Result<Order, PaymentError> {
+        self.update(payload, |order| {
+            if order.is_paid() {
+                return Err(PaymentError::AlreadyPaid(order.payload.clone()));
+            }
+            order.status = OrderStatus::Approved;
+            Ok(())
+        })
+    }
+
+    fn remove(&self, payload: &str) {
+        self.lock().remove(payload);
+    }
+}
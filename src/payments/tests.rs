@@ -0,0 +1,243 @@
+use crate::{methods::SendInvoice, payments::*, types::*, Api};
+use futures::Future;
+use serde_json::json;
+use std::time::Duration;
+use tokio::runtime::Runtime;
+use tokio_timer::sleep;
+
+fn get_invoice() -> SendInvoice {
+    SendInvoice::new(
+        1,
+        "title",
+        "description",
+        "payload",
+        "token",
+        "start",
+        "USD",
+        vec![LabeledPrice {
+            label: String::from("goods"),
+            amount: 1000,
+        }],
+    )
+}
+
+fn get_payments() -> (Payments, MemoryOrderStore) {
+    let store = MemoryOrderStore::new();
+    let api = Api::new("token", None::<&str>).unwrap();
+    store.put(Order::new(&get_invoice()));
+    (Payments::new(api, store.clone()), store)
+}
+
+fn get_shipping_query(payload: &str) -> ShippingQuery {
+    serde_json::from_value(json!({
+        "id": "query-id",
+        "from": {"id": 1, "is_bot": false, "first_name": "test"},
+        "invoice_payload": payload,
+        "shipping_address": {
+            "country_code": "US",
+            "state": "",
+            "city": "City",
+            "street_line1": "Line 1",
+            "street_line2": "",
+            "post_code": "00000"
+        }
+    }))
+    .unwrap()
+}
+
+fn get_pre_checkout_query(total_amount: Integer, shipping_option_id: Option<&str>) -> PreCheckoutQuery {
+    serde_json::from_value(json!({
+        "id": "query-id",
+        "from": {"id": 1, "is_bot": false, "first_name": "test"},
+        "currency": "USD",
+        "total_amount": total_amount,
+        "invoice_payload": "payload",
+        "shipping_option_id": shipping_option_id
+    }))
+    .unwrap()
+}
+
+fn get_shipping_option(id: &str, amount: Integer) -> ShippingOption {
+    ShippingOption {
+        id: String::from(id),
+        title: String::from(id),
+        prices: vec![LabeledPrice {
+            label: String::from("shipping"),
+            amount,
+        }],
+    }
+}
+
+#[test]
+fn test_shipping_and_checkout() {
+    let mut rt = Runtime::new().unwrap();
+    let (payments, store) = get_payments();
+    assert_eq!(
+        rt.block_on(payments.process_shipping_query(&get_shipping_query("payload")))
+            .unwrap()
+            .unwrap_err(),
+        "Shipping to this address is not available"
+    );
+
+    let payments = payments.shipping(|order: &Order, address: &ShippingAddress| {
+        assert_eq!(order.payload, "payload");
+        if address.country_code == "US" {
+            Ok(vec![get_shipping_option("fast", 500), get_shipping_option("slow", 100)])
+        } else {
+            Err(String::from("Not available"))
+        }
+    });
+    let options = rt
+        .block_on(payments.process_shipping_query(&get_shipping_query("payload")))
+        .unwrap()
+        .unwrap();
+    assert_eq!(options.len(), 2);
+    assert_eq!(store.get("payload").unwrap().shipping_options.len(), 2);
+    assert_eq!(
+        rt.block_on(payments.process_shipping_query(&get_shipping_query("unknown")))
+            .unwrap()
+            .unwrap_err(),
+        "This order is no longer available"
+    );
+
+    for (total_amount, shipping_option_id) in &[(1000, Some("fast")), (1500, None), (1500, Some("other"))] {
+        let query = get_pre_checkout_query(*total_amount, *shipping_option_id);
+        assert_eq!(
+            rt.block_on(payments.process_pre_checkout_query(&query)).unwrap(),
+            Err(String::from("This order has been changed, please try again"))
+        );
+    }
+    let query = get_pre_checkout_query(1500, Some("fast"));
+    let payments = payments.checkout(|order: &Order, query: &PreCheckoutQuery| {
+        if order.status == OrderStatus::Pending && query.total_amount < 2000 {
+            Ok(())
+        } else {
+            Err(String::from("Out of stock"))
+        }
+    });
+    assert_eq!(
+        rt.block_on(payments.process_pre_checkout_query(&query)).unwrap(),
+        Ok(())
+    );
+    assert_eq!(store.get("payload").unwrap().status, OrderStatus::Approved);
+    assert_eq!(
+        rt.block_on(payments.process_pre_checkout_query(&query)).unwrap(),
+        Err(String::from("Out of stock"))
+    );
+
+    let payment: SuccessfulPayment = serde_json::from_value(json!({
+        "currency": "USD",
+        "total_amount": 1500,
+        "invoice_payload": "payload",
+        "shipping_option_id": "fast",
+        "telegram_payment_charge_id": "tg-id",
+        "provider_payment_charge_id": "provider-id"
+    }))
+    .unwrap();
+    let order = payments.record_payment(1, &payment).unwrap();
+    assert!(order.is_paid());
+    assert_eq!(
+        payments.get_order("payload").unwrap().status,
+        OrderStatus::Paid(Box::new(Receipt {
+            user_id: 1,
+            payment: payment.clone()
+        }))
+    );
+    match payments.record_payment(1, &payment) {
+        Err(PaymentError::AlreadyPaid(_)) => {}
+        result => panic!("Unexpected result: {:?}", result),
+    }
+    assert_eq!(
+        rt.block_on(payments.process_pre_checkout_query(&query)).unwrap(),
+        Err(String::from("This order is no longer available"))
+    );
+}
+
+#[test]
+fn test_timeout() {
+    let mut rt = Runtime::new().unwrap();
+    let (payments, store) = get_payments();
+    let payments = payments
+        .timeout(Duration::from_millis(10))
+        .checkout(|_: &Order, _: &PreCheckoutQuery| sleep(Duration::from_secs(1)).then(|_| Ok(())));
+    assert_eq!(
+        rt.block_on(payments.process_pre_checkout_query(&get_pre_checkout_query(1000, None)))
+            .unwrap(),
+        Err(String::from("Failed to process the order in time, please try again"))
+    );
+    assert_eq!(store.get("payload").unwrap().status, OrderStatus::Pending);
+}
+
+#[test]
+fn test_duplicate_payload() {
+    let mut rt = Runtime::new().unwrap();
+    let (payments, store) = get_payments();
+    let mut order = store.get("payload").unwrap();
+    order.status = OrderStatus::Approved;
+    store.put(order);
+    let err = rt.block_on(payments.send_invoice(&get_invoice())).unwrap_err();
+    match err.downcast::<PaymentError>() {
+        Ok(PaymentError::DuplicateOrder(ref payload)) if payload == "payload" => {}
+        result => panic!("Unexpected result: {:?}", result),
+    }
+    assert_eq!(store.get("payload").unwrap().status, OrderStatus::Approved);
+}
+
+#[test]
+fn test_paid_during_checkout() {
+    let mut rt = Runtime::new().unwrap();
+    let (payments, store) = get_payments();
+    let receipt = Receipt {
+        user_id: 1,
+        payment: serde_json::from_value(json!({
+            "currency": "USD",
+            "total_amount": 1000,
+            "invoice_payload": "payload",
+            "telegram_payment_charge_id": "tg-id",
+            "provider_payment_charge_id": "provider-id"
+        }))
+        .unwrap(),
+    };
+    let paying_store = store.clone();
+    let payments = payments.checkout(move |order: &Order, _: &PreCheckoutQuery| {
+        paying_store
+            .set_paid(&order.payload, receipt.clone())
+            .map(|_| ())
+            .map_err(|err| err.to_string())
+    });
+    assert_eq!(
+        rt.block_on(payments.process_pre_checkout_query(&get_pre_checkout_query(1000, None)))
+            .unwrap(),
+        Err(String::from("This order is no longer available"))
+    );
+    assert!(store.get("payload").unwrap().is_paid());
+    match store.set_shipping_options("payload", vec![get_shipping_option("fast", 500)]) {
+        Err(PaymentError::NotPending(ref payload)) if payload == "payload" => {}
+        result => panic!("Unexpected result: {:?}", result),
+    }
+    payments.remove_order("payload");
+    assert!(payments.get_order("payload").is_none());
+}
+
+#[test]
+fn test_memory_order_store_eviction() {
+    let store = MemoryOrderStore::new().max_entries(2);
+    for payload in &["a", "b", "c"] {
+        let mut order = Order::new(&get_invoice());
+        order.payload = String::from(*payload);
+        assert!(store.insert(order));
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    assert!(store.get("a").is_none());
+    assert!(store.get("b").is_some());
+    assert!(store.get("c").is_some());
+
+    let store = MemoryOrderStore::new().ttl(Duration::from_millis(0));
+    store.put(Order::new(&get_invoice()));
+    assert!(store.get("payload").is_none());
+    match store.set_approved("payload") {
+        Err(PaymentError::UnknownOrder(_)) => {}
+        result => panic!("Unexpected result: {:?}", result),
+    }
+    assert!(store.insert(Order::new(&get_invoice())));
+}
@@ -0,0 +1,181 @@
+use crate::{
+    stickers::{
+        png::{validate_sticker_image, PngInfo},
+        sync::StickerSyncError,
+    },
+    types::{check_emojis, check_mask_position, MaskPosition},
+};
+use openssl::sha::sha256;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, fmt, fs, path::Path};
+
+/// Name of the manifest file in a sticker set directory
+pub const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// Description of a sticker set kept in a local directory
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct StickerManifest {
+    /// Short name of the sticker set
+    pub name: String,
+    /// Title of the sticker set
+    pub title: String,
+    /// True, if the set contains masks
+    #[serde(default)]
+    pub contains_masks: bool,
+    /// Stickers in the order they should appear in the set
+    pub stickers: Vec<ManifestSticker>,
+}
+
+/// Sticker in a manifest
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ManifestSticker {
+    /// Path to a PNG file relative to the manifest
+    pub file: String,
+    /// One or more emoji corresponding to the sticker
+    pub emojis: String,
+    /// Position where the mask should be placed on faces
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mask_position: Option<MaskPosition>,
+}
+
+/// Sticker set loaded from a local directory
+#[derive(Clone, Debug)]
+pub struct LocalStickerSet {
+    /// Short name of the sticker set
+    pub name: String,
+    /// Title of the sticker set
+    pub title: String,
+    /// True, if the set contains masks
+    pub contains_masks: bool,
+    /// Stickers in the order they should appear in the set
+    pub stickers: Vec<LocalSticker>,
+}
+
+impl LocalStickerSet {
+    /// Loads a set from a directory containing manifest.json and PNG files
+    pub fn from_dir<P: AsRef<Path>>(path: P) -> Result<Self, StickerSyncError> {
+        let path = path.as_ref();
+        let manifest: StickerManifest = serde_json::from_slice(&fs::read(path.join(MANIFEST_FILE_NAME))?)?;
+        LocalStickerSet::from_manifest(manifest, path)
+    }
+
+    /// Loads PNG files listed in a manifest
+    ///
+    /// # Arguments
+    ///
+    /// * manifest - Description of the set
+    /// * path - Directory which file paths are relative to
+    pub fn from_manifest<P: AsRef<Path>>(manifest: StickerManifest, path: P) -> Result<Self, StickerSyncError> {
+        let path = path.as_ref();
+        let mut stickers = Vec::with_capacity(manifest.stickers.len());
+        for sticker in manifest.stickers {
+            let data = fs::read(path.join(&sticker.file))?;
+            stickers.push(LocalSticker::new(
+                sticker.file,
+                data,
+                sticker.emojis,
+                sticker.mask_position,
+            ));
+        }
+        LocalStickerSet::new(manifest.name, manifest.title, manifest.contains_masks, stickers)
+    }
+
+    /// Creates a set from already loaded stickers
+    ///
+    /// Returns an error if the set is empty or the same image is used twice
+    pub fn new<N, T>(
+        name: N,
+        title: T,
+        contains_masks: bool,
+        stickers: Vec<LocalSticker>,
+    ) -> Result<Self, StickerSyncError>
+    where
+        N: Into<String>,
+        T: Into<String>,
+    {
+        if stickers.is_empty() {
+            return Err(StickerSyncError::EmptySet);
+        }
+        let mut hashes = HashSet::new();
+        for sticker in &stickers {
+            if !hashes.insert(&sticker.hash) {
+                return Err(StickerSyncError::DuplicateSticker(sticker.file_name.clone()));
+            }
+        }
+        Ok(LocalStickerSet {
+            name: name.into(),
+            title: title.into(),
+            contains_masks,
+            stickers,
+        })
+    }
+
+    /// Checks that all stickers can be uploaded
+    pub fn validate(&self) -> Result<(), StickerSyncError> {
+        for sticker in &self.stickers {
+            sticker.validate()?;
+        }
+        Ok(())
+    }
+}
+
+/// Sticker loaded from a local file
+#[derive(Clone)]
+pub struct LocalSticker {
+    /// Name of the file
+    pub file_name: String,
+    /// Contents of the PNG file
+    pub data: Vec<u8>,
+    /// Hex-encoded SHA-256 of the contents
+    pub hash: String,
+    /// One or more emoji corresponding to the sticker
+    pub emojis: String,
+    /// Position where the mask should be placed on faces
+    pub mask_position: Option<MaskPosition>,
+}
+
+impl LocalSticker {
+    /// Creates a new sticker
+    pub fn new<N, E>(file_name: N, data: Vec<u8>, emojis: E, mask_position: Option<MaskPosition>) -> Self
+    where
+        N: Into<String>,
+        E: Into<String>,
+    {
+        let hash = sha256(&data).iter().map(|x| format!("{:02x}", x)).collect();
+        LocalSticker {
+            file_name: file_name.into(),
+            data,
+            hash,
+            emojis: emojis.into(),
+            mask_position,
+        }
+    }
+
+    /// Checks image, emoji and mask position of the sticker
+    pub fn validate(&self) -> Result<PngInfo, StickerSyncError> {
+        let invalid_sticker = |cause| StickerSyncError::InvalidSticker {
+            file_name: self.file_name.clone(),
+            cause,
+        };
+        check_emojis("emojis", &self.emojis).map_err(invalid_sticker)?;
+        if let Some(ref mask_position) = self.mask_position {
+            check_mask_position("mask_position", mask_position).map_err(invalid_sticker)?;
+        }
+        validate_sticker_image(&self.data).map_err(|cause| StickerSyncError::InvalidImage {
+            file_name: self.file_name.clone(),
+            cause,
+        })
+    }
+}
+
+impl fmt::Debug for LocalSticker {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        out.debug_struct("LocalSticker")
+            .field("file_name", &self.file_name)
+            .field("data", &format_args!("{} bytes", self.data.len()))
+            .field("hash", &self.hash)
+            .field("emojis", &self.emojis)
+            .field("mask_position", &self.mask_position)
+            .finish()
+    }
+}
@@ -0,0 +1,7 @@
+mod manifest;
+mod png;
+mod sync;
+#[cfg(test)]
+mod tests;
+
+pub use self::{manifest::*, png::*, sync::*};
@@ -0,0 +1,154 @@
+use std::convert::TryInto;
+
+/// Maximum size of a sticker file in bytes
+pub const MAX_STICKER_FILE_SIZE: usize = 512 * 1024;
+
+/// Required size of the longest side of a sticker in pixels
+pub const STICKER_SIDE: u32 = 512;
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Dimensions and size of a PNG image
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PngInfo {
+    /// Width in pixels
+    pub width: u32,
+    /// Height in pixels
+    pub height: u32,
+    /// Size of the file in bytes
+    pub size: usize,
+}
+
+impl PngInfo {
+    /// Reads dimensions from the PNG header without decoding the image
+    pub fn parse(data: &[u8]) -> Result<Self, StickerImageError> {
+        if data.len() < 24 || !data.starts_with(PNG_SIGNATURE) || &data[12..16] != b"IHDR" {
+            return Err(StickerImageError::NotPng);
+        }
+        let read_u32 = |offset: usize| u32::from_be_bytes(data[offset..offset + 4].try_into().unwrap());
+        Ok(PngInfo {
+            width: read_u32(16),
+            height: read_u32(20),
+            size: data.len(),
+        })
+    }
+}
+
+/// Checks that a PNG image can be uploaded as a sticker
+///
+/// The file must be up to 512 kilobytes in size,
+/// dimensions must not exceed 512px, and either width or height must be exactly 512px
+pub fn validate_sticker_image(data: &[u8]) -> Result<PngInfo, StickerImageError> {
+    let info = PngInfo::parse(data)?;
+    if info.size > MAX_STICKER_FILE_SIZE {
+        return Err(StickerImageError::TooLarge(info.size));
+    }
+    if info.width.max(info.height) != STICKER_SIDE {
+        return Err(StickerImageError::InvalidDimensions {
+            width: info.width,
+            height: info.height,
+        });
+    }
+    Ok(info)
+}
+
+/// Resizes images to conform sticker requirements
+///
+/// The longest side of an image is scaled to 512px preserving aspect ratio
+#[cfg(feature = "sticker-normalizer")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StickerNormalizer {
+    square: bool,
+}
+
+#[cfg(feature = "sticker-normalizer")]
+impl StickerNormalizer {
+    /// Creates a new normalizer
+    pub fn new() -> Self {
+        StickerNormalizer::default()
+    }
+
+    /// Pad images with transparent pixels to 512x512
+    pub fn square(mut self, square: bool) -> Self {
+        self.square = square;
+        self
+    }
+
+    /// Returns a PNG image which passes validate_sticker_image()
+    ///
+    /// Any format supported by enabled image codecs is accepted,
+    /// a valid image which does not need padding is returned as is
+    pub fn normalize(&self, data: &[u8]) -> Result<Vec<u8>, StickerImageError> {
+        use image::{imageops, DynamicImage, FilterType, GenericImageView, ImageOutputFormat, RgbaImage};
+
+        if let Ok(info) = validate_sticker_image(data) {
+            if !self.square || info.width == info.height {
+                return Ok(data.to_vec());
+            }
+        }
+        let source = image::load_from_memory(data)?;
+        let (width, height) = source.dimensions();
+        if width == 0 || height == 0 {
+            return Err(StickerImageError::InvalidDimensions { width, height });
+        }
+        let scale = |side: u32| ((u64::from(side) * u64::from(STICKER_SIDE)) / u64::from(width.max(height))).max(1);
+        let (new_width, new_height) = (scale(width) as u32, scale(height) as u32);
+        let resized = if (new_width, new_height) == (width, height) {
+            source.to_rgba()
+        } else {
+            imageops::resize(&source, new_width, new_height, FilterType::Lanczos3)
+        };
+        let (canvas_width, canvas_height) = if self.square {
+            (STICKER_SIDE, STICKER_SIDE)
+        } else {
+            (new_width, new_height)
+        };
+        let mut canvas = RgbaImage::new(canvas_width, canvas_height);
+        imageops::overlay(
+            &mut canvas,
+            &resized,
+            (canvas_width - new_width) / 2,
+            (canvas_height - new_height) / 2,
+        );
+        let mut result = Vec::new();
+        DynamicImage::ImageRgba8(canvas).write_to(&mut result, ImageOutputFormat::PNG)?;
+        validate_sticker_image(&result)?;
+        Ok(result)
+    }
+}
+
+/// Sticker image does not conform requirements
+///
+/// Set of variants depends on enabled features
+#[derive(Debug, failure::Fail)]
+#[non_exhaustive]
+pub enum StickerImageError {
+    /// File is not a PNG image
+    #[fail(display = "File is not a PNG image")]
+    NotPng,
+    /// File is larger than 512 kilobytes
+    #[fail(display = "File size is {} bytes, expected up to 524288", _0)]
+    TooLarge(usize),
+    /// Neither width nor height is 512px or one of them exceeds 512px
+    #[fail(
+        display = "Image size is {}x{}, expected one side to be 512px and another one to not exceed it",
+        width, height
+    )]
+    InvalidDimensions {
+        /// Width in pixels
+        width: u32,
+        /// Height in pixels
+        height: u32,
+    },
+    /// Failed to decode or encode an image
+    #[cfg(feature = "sticker-normalizer")]
+    #[fail(display = "Image error: {}", _0)]
+    Image(#[cause] image::ImageError),
+}
+
+#[cfg(feature = "sticker-normalizer")]
+impl From<image::ImageError> for StickerImageError {
+    fn from(err: image::ImageError) -> Self {
+        StickerImageError::Image(err)
+    }
+}
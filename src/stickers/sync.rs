@@ -0,0 +1,482 @@
+use crate::{
+    api::{Api, ApiFuture},
+    methods::{
+        AddStickerToSet, CreateNewStickerSet, DeleteStickerFromSet, GetStickerSet, SetStickerPositionInSet,
+        UploadStickerFile,
+    },
+    stickers::{
+        manifest::{LocalSticker, LocalStickerSet},
+        png::StickerImageError,
+    },
+    types::{InputFile, Integer, MaskPosition, ResponseError, StickerSet, ValidationError},
+};
+use failure::Error;
+use futures::{future, stream, Future, Stream};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, fmt, fs, io, path::Path, sync::Arc};
+
+const SET_INVALID_ERROR: &str = "STICKERSET_INVALID";
+
+/// Stickers added to a set by previous synchronizations
+///
+/// Telegram does not return contents of stickers,
+/// so the state is required to match stickers of a set with local files
+/// A sticker of the set which is not in the state is deleted
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct SyncState {
+    stickers: Vec<SyncedSticker>,
+}
+
+/// Sticker added to a set by synchronization
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SyncedSticker {
+    /// Identifier of the sticker in the set
+    pub file_id: String,
+    /// Hex-encoded SHA-256 of the uploaded file
+    pub hash: String,
+    /// Emoji of the sticker
+    pub emojis: String,
+    /// Position where the mask should be placed on faces
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mask_position: Option<MaskPosition>,
+}
+
+impl SyncedSticker {
+    fn is_same(&self, sticker: &LocalSticker) -> bool {
+        self.hash == sticker.hash && self.emojis == sticker.emojis && self.mask_position == sticker.mask_position
+    }
+}
+
+impl SyncState {
+    /// Creates an empty state
+    pub fn new() -> Self {
+        SyncState::default()
+    }
+
+    /// Loads a state from a JSON file, a missing file results in an empty state
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, StickerSyncError> {
+        match fs::read(path) {
+            Ok(data) => Ok(serde_json::from_slice(&data)?),
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => Ok(SyncState::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Saves the state to a JSON file
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), StickerSyncError> {
+        fs::write(path, serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Returns synchronized stickers
+    pub fn get_stickers(&self) -> &[SyncedSticker] {
+        &self.stickers
+    }
+
+    fn find(&self, sticker: &LocalSticker) -> Option<&SyncedSticker> {
+        self.stickers.iter().find(|synced| synced.is_same(sticker))
+    }
+}
+
+/// A change required to converge a sticker set with local files
+#[derive(Clone, Debug, PartialEq)]
+pub enum SyncAction {
+    /// Upload a local sticker and create the set with it
+    CreateSet {
+        /// Index of the local sticker
+        index: usize,
+    },
+    /// Upload a local sticker and add it to the set
+    Add {
+        /// Index of the local sticker
+        index: usize,
+    },
+    /// Delete a sticker from the set
+    Delete {
+        /// Identifier of the sticker
+        file_id: String,
+    },
+    /// Move a local sticker to a position in the set
+    Move {
+        /// Index of the local sticker
+        index: usize,
+        /// Zero-based position in the set
+        position: usize,
+    },
+}
+
+/// Changes required to converge a sticker set with local files
+///
+/// Display the plan to get a dry-run report
+#[derive(Clone, Debug)]
+pub struct SyncPlan {
+    set: Arc<LocalStickerSet>,
+    state: SyncState,
+    actions: Vec<SyncAction>,
+}
+
+impl SyncPlan {
+    /// Computes changes
+    ///
+    /// # Arguments
+    ///
+    /// * set - Local stickers
+    /// * remote - Current set, None if it does not exist
+    /// * state - State saved after the previous synchronization
+    /// * force - Replace stickers of an existing set when the state is empty
+    ///
+    /// Without force an existing set with stickers and an empty state result in an error,
+    /// since all stickers of the set would be deleted and uploaded again
+    pub fn new(
+        set: LocalStickerSet,
+        remote: Option<&StickerSet>,
+        state: &SyncState,
+        force: bool,
+    ) -> Result<Self, StickerSyncError> {
+        if set.stickers.is_empty() {
+            return Err(StickerSyncError::EmptySet);
+        }
+        let mut actions = Vec::new();
+        let mut kept = SyncState::default();
+        let remote = match remote {
+            Some(remote) => remote,
+            None => {
+                actions.push(SyncAction::CreateSet { index: 0 });
+                actions.extend((1..set.stickers.len()).map(|index| SyncAction::Add { index }));
+                return Ok(SyncPlan {
+                    set: Arc::new(set),
+                    state: kept,
+                    actions,
+                });
+            }
+        };
+        if state.stickers.is_empty() && !remote.stickers.is_empty() && !force {
+            return Err(StickerSyncError::UnknownSet(set.name));
+        }
+        let mut used = HashSet::new();
+        let mut order = Vec::new();
+        let mut deleted = Vec::new();
+        for sticker in &remote.stickers {
+            let synced = state.stickers.iter().find(|synced| synced.file_id == sticker.file_id);
+            let index = synced.and_then(|synced| {
+                set.stickers
+                    .iter()
+                    .enumerate()
+                    .position(|(index, local)| !used.contains(&index) && synced.is_same(local))
+            });
+            match (synced, index) {
+                (Some(synced), Some(index)) => {
+                    used.insert(index);
+                    order.push(index);
+                    kept.stickers.push(synced.clone());
+                }
+                _ => deleted.push(SyncAction::Delete {
+                    file_id: sticker.file_id.clone(),
+                }),
+            }
+        }
+        // Stickers are added before deletion, so the set never becomes empty
+        for index in (0..set.stickers.len()).filter(|index| !used.contains(index)) {
+            actions.push(SyncAction::Add { index });
+            order.push(index);
+        }
+        actions.extend(deleted);
+        for position in 0..order.len() {
+            let current = order.iter().position(|&index| index == position).unwrap_or(position);
+            if current != position {
+                let index = order.remove(current);
+                order.insert(position, index);
+                actions.push(SyncAction::Move { index, position });
+            }
+        }
+        Ok(SyncPlan {
+            set: Arc::new(set),
+            state: kept,
+            actions,
+        })
+    }
+
+    /// Returns local stickers
+    pub fn get_set(&self) -> &LocalStickerSet {
+        &self.set
+    }
+
+    /// Returns changes in order of execution
+    pub fn get_actions(&self) -> &[SyncAction] {
+        &self.actions
+    }
+
+    /// Returns true if the set is up to date
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+}
+
+impl fmt::Display for SyncPlan {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        if self.actions.is_empty() {
+            return writeln!(out, "Sticker set {} is up to date", self.set.name);
+        }
+        writeln!(out, "Sticker set {}: {} change(s)", self.set.name, self.actions.len())?;
+        for action in &self.actions {
+            match action {
+                SyncAction::CreateSet { index } => {
+                    let sticker = &self.set.stickers[*index];
+                    writeln!(out, "create set with {} {}", sticker.file_name, sticker.emojis)?
+                }
+                SyncAction::Add { index } => {
+                    let sticker = &self.set.stickers[*index];
+                    writeln!(out, "add {} {}", sticker.file_name, sticker.emojis)?
+                }
+                SyncAction::Delete { file_id } => writeln!(out, "delete {}", file_id)?,
+                SyncAction::Move { index, position } => {
+                    writeln!(out, "move {} to {}", self.set.stickers[*index].file_name, position)?
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Synchronizes sticker sets with local files
+#[derive(Clone)]
+pub struct StickerSync {
+    api: Api,
+    user_id: Integer,
+    force: bool,
+}
+
+impl StickerSync {
+    /// Creates a new StickerSync
+    ///
+    /// # Arguments
+    ///
+    /// * api - Api to manage sticker sets
+    /// * user_id - Identifier of the user who owns sticker sets
+    pub fn new(api: Api, user_id: Integer) -> Self {
+        StickerSync {
+            api,
+            user_id,
+            force: false,
+        }
+    }
+
+    /// Replace stickers of an existing set when the state is empty
+    ///
+    /// Disabled by default, so a lost state does not result in re-uploading the whole set
+    pub fn force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+
+    /// Validates local stickers, gets the current set and computes changes without applying them
+    pub fn get_plan(&self, set: LocalStickerSet, state: SyncState) -> ApiFuture<SyncPlan> {
+        if let Err(err) = set.validate() {
+            return ApiFuture::new(future::err(err.into()));
+        }
+        let force = self.force;
+        ApiFuture::new(
+            self.api
+                .execute(&GetStickerSet::new(set.name.clone()))
+                .then(move |result| match result {
+                    Ok(remote) => Ok(SyncPlan::new(set, Some(&remote), &state, force)?),
+                    Err(ref err) if is_set_invalid(err) => Ok(SyncPlan::new(set, None, &state, force)?),
+                    Err(err) => Err(err),
+                }),
+        )
+    }
+
+    /// Applies changes and returns a new state
+    ///
+    /// The state must be saved and passed to the next synchronization
+    /// When a change fails, the error is StickerSyncError::Interrupted
+    /// with the state of changes applied before it, which must be saved as well
+    pub fn execute(&self, plan: SyncPlan) -> ApiFuture<SyncState> {
+        let sync = self.clone();
+        let set = plan.set;
+        ApiFuture::new(
+            stream::iter_ok(plan.actions).fold(plan.state, move |state, action| -> StateFuture {
+                let applied = state.clone();
+                Box::new(sync.apply(&set, action, state).map_err(move |err| {
+                    Error::from(StickerSyncError::Interrupted {
+                        state: applied,
+                        error: err,
+                    })
+                }))
+            }),
+        )
+    }
+
+    fn apply(&self, set: &Arc<LocalStickerSet>, action: SyncAction, state: SyncState) -> StateFuture {
+        match action {
+            SyncAction::CreateSet { index } => self.add(set, index, true, state),
+            SyncAction::Add { index } => self.add(set, index, false, state),
+            SyncAction::Delete { file_id } => {
+                Box::new(self.api.execute(&DeleteStickerFromSet::new(file_id)).map(|_| state))
+            }
+            SyncAction::Move { index, position } => {
+                let file_id = match state.find(&set.stickers[index]) {
+                    Some(synced) => synced.file_id.clone(),
+                    None => {
+                        let err = StickerSyncError::MissingSticker(set.stickers[index].file_name.clone());
+                        return Box::new(future::err(err.into()));
+                    }
+                };
+                let method = SetStickerPositionInSet::new(file_id, position as Integer);
+                Box::new(self.api.execute(&method).map(|_| state))
+            }
+        }
+    }
+
+    fn add(&self, set: &Arc<LocalStickerSet>, index: usize, create: bool, state: SyncState) -> StateFuture {
+        let (api, user_id, set) = (self.api.clone(), self.user_id, set.clone());
+        // Stickers of the set before adding, the added sticker is the only one which is not among them
+        let existing: StateFuture<HashSet<String>> = if create {
+            Box::new(future::ok(HashSet::new()))
+        } else {
+            Box::new(
+                self.api
+                    .execute(&GetStickerSet::new(set.name.clone()))
+                    .map(|remote| get_file_ids(&remote)),
+            )
+        };
+        Box::new(
+            existing
+                .and_then(move |existing| {
+                    let sticker = &set.stickers[index];
+                    let upload = UploadStickerFile::new(
+                        user_id,
+                        InputFile::bytes(sticker.file_name.clone(), sticker.data.clone()),
+                    );
+                    api.execute(&upload).and_then(move |file| {
+                        let sticker = &set.stickers[index];
+                        let added = if create {
+                            let mut method = CreateNewStickerSet::new(
+                                user_id,
+                                set.name.clone(),
+                                set.title.clone(),
+                                file.file_id,
+                                sticker.emojis.clone(),
+                            )
+                            .contains_masks(set.contains_masks);
+                            if let Some(mask_position) = sticker.mask_position.clone() {
+                                method = method.mask_position(mask_position);
+                            }
+                            api.execute(&method)
+                        } else {
+                            let mut method =
+                                AddStickerToSet::new(user_id, set.name.clone(), file.file_id, sticker.emojis.clone());
+                            if let Some(mask_position) = sticker.mask_position.clone() {
+                                method = method.mask_position(mask_position);
+                            }
+                            api.execute(&method)
+                        };
+                        let get_set = GetStickerSet::new(set.name.clone());
+                        added
+                            .and_then(move |_| api.execute(&get_set))
+                            .map(move |remote| (set, existing, remote))
+                    })
+                })
+                .and_then(move |(set, existing, remote)| -> Result<SyncState, Error> {
+                    let sticker = &set.stickers[index];
+                    let mut added = get_file_ids(&remote)
+                        .into_iter()
+                        .filter(|file_id| !existing.contains(file_id));
+                    let file_id = match (added.next(), added.next()) {
+                        (Some(file_id), None) => file_id,
+                        (None, _) => return Err(StickerSyncError::MissingSticker(sticker.file_name.clone()).into()),
+                        (Some(_), Some(_)) => {
+                            return Err(StickerSyncError::AmbiguousSticker(sticker.file_name.clone()).into())
+                        }
+                    };
+                    let mut state = state;
+                    state.stickers.push(SyncedSticker {
+                        file_id,
+                        hash: sticker.hash.clone(),
+                        emojis: sticker.emojis.clone(),
+                        mask_position: sticker.mask_position.clone(),
+                    });
+                    Ok(state)
+                }),
+        )
+    }
+}
+
+type StateFuture<T = SyncState> = Box<dyn Future<Item = T, Error = Error> + Send>;
+
+fn get_file_ids(set: &StickerSet) -> HashSet<String> {
+    set.stickers.iter().map(|sticker| sticker.file_id.clone()).collect()
+}
+
+fn is_set_invalid(err: &Error) -> bool {
+    err.downcast_ref::<ResponseError>()
+        .map(|err| err.description.contains(SET_INVALID_ERROR))
+        .unwrap_or(false)
+}
+
+/// An error when synchronizing a sticker set
+#[derive(Debug, failure::Fail)]
+pub enum StickerSyncError {
+    /// Failed to read or write a file
+    #[fail(display = "IO error: {}", _0)]
+    Io(#[cause] io::Error),
+    /// Failed to parse or serialize JSON
+    #[fail(display = "JSON error: {}", _0)]
+    Json(#[cause] serde_json::Error),
+    /// Set has no stickers
+    #[fail(display = "Sticker set is empty")]
+    EmptySet,
+    /// Same image is used for several stickers
+    #[fail(display = "Duplicate sticker: {}", _0)]
+    DuplicateSticker(String),
+    /// Set exists, but the state is empty
+    #[fail(
+        display = "Sticker set {} is not in the state, use force to replace its stickers",
+        _0
+    )]
+    UnknownSet(String),
+    /// Sticker was not found in the set after it had been added
+    #[fail(display = "Sticker is missing in the set: {}", _0)]
+    MissingSticker(String),
+    /// Several stickers appeared in the set while adding one, so the added one is unknown
+    #[fail(display = "Added sticker is ambiguous: {}", _0)]
+    AmbiguousSticker(String),
+    /// A change failed
+    #[fail(display = "Synchronization is interrupted: {}", error)]
+    Interrupted {
+        /// State of changes applied before the failed one
+        state: SyncState,
+        /// Reason
+        error: Error,
+    },
+    /// Image of a sticker does not conform requirements
+    #[fail(display = "Invalid image {}: {}", file_name, cause)]
+    InvalidImage {
+        /// Name of the file
+        file_name: String,
+        /// Reason
+        #[cause]
+        cause: StickerImageError,
+    },
+    /// Emoji or mask position of a sticker is not valid
+    #[fail(display = "Invalid sticker {}: {}", file_name, cause)]
+    InvalidSticker {
+        /// Name of the file
+        file_name: String,
+        /// Reason
+        #[cause]
+        cause: ValidationError,
+    },
+}
+
+impl From<io::Error> for StickerSyncError {
+    fn from(err: io::Error) -> Self {
+        StickerSyncError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for StickerSyncError {
+    fn from(err: serde_json::Error) -> Self {
+        StickerSyncError::Json(err)
+    }
+}
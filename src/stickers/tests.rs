@@ -0,0 +1,246 @@
+use crate::{stickers::*, types::*};
+use serde_json::json;
+use std::{env, fs};
+
+fn get_set(names: &[&str]) -> LocalStickerSet {
+    let stickers = names
+        .iter()
+        .map(|name| LocalSticker::new(format!("{}.png", name), name.as_bytes().to_vec(), "😀", None))
+        .collect();
+    LocalStickerSet::new("set_by_bot", "Set", false, stickers).unwrap()
+}
+
+fn get_remote(file_ids: &[&str]) -> StickerSet {
+    let stickers: Vec<_> = file_ids
+        .iter()
+        .map(|file_id| json!({"file_id": file_id, "width": 512, "height": 512, "emoji": "😀"}))
+        .collect();
+    serde_json::from_value(json!({
+        "name": "set_by_bot",
+        "title": "Set",
+        "contains_masks": false,
+        "stickers": stickers
+    }))
+    .unwrap()
+}
+
+fn get_state(set: &LocalStickerSet, file_ids: &[(&str, usize)]) -> SyncState {
+    let stickers: Vec<_> = file_ids
+        .iter()
+        .map(|(file_id, index)| {
+            let sticker = &set.stickers[*index];
+            json!({"file_id": file_id, "hash": sticker.hash, "emojis": sticker.emojis})
+        })
+        .collect();
+    serde_json::from_value(json!({ "stickers": stickers })).unwrap()
+}
+
+#[test]
+fn test_sync_plan() {
+    let plan = SyncPlan::new(get_set(&["a", "b"]), None, &SyncState::new(), false).unwrap();
+    assert_eq!(
+        plan.get_actions(),
+        &[SyncAction::CreateSet { index: 0 }, SyncAction::Add { index: 1 }]
+    );
+    assert_eq!(
+        plan.to_string(),
+        "Sticker set set_by_bot: 2 change(s)\ncreate set with a.png 😀\nadd b.png 😀\n"
+    );
+
+    let set = get_set(&["a", "b", "c"]);
+    let state = get_state(&set, &[("id-a", 0), ("id-b", 1), ("id-c", 2)]);
+    let plan = SyncPlan::new(set.clone(), Some(&get_remote(&["id-a", "id-b", "id-c"])), &state, false).unwrap();
+    assert!(plan.is_empty());
+    assert_eq!(plan.to_string(), "Sticker set set_by_bot is up to date\n");
+
+    // c is moved to the beginning, b is removed, d is new, x is unknown
+    let plan = SyncPlan::new(
+        get_set(&["c", "a", "d"]),
+        Some(&get_remote(&["id-a", "id-b", "id-c", "id-x"])),
+        &state,
+        false,
+    )
+    .unwrap();
+    assert_eq!(
+        plan.get_actions(),
+        &[
+            SyncAction::Add { index: 2 },
+            SyncAction::Delete {
+                file_id: String::from("id-b")
+            },
+            SyncAction::Delete {
+                file_id: String::from("id-x")
+            },
+            SyncAction::Move { index: 0, position: 0 },
+        ]
+    );
+
+    // changed emoji requires the sticker to be added again
+    let mut changed = set.clone();
+    changed.stickers[1].emojis = String::from("😎");
+    let plan = SyncPlan::new(changed, Some(&get_remote(&["id-a", "id-b", "id-c"])), &state, false).unwrap();
+    assert_eq!(
+        plan.get_actions(),
+        &[
+            SyncAction::Add { index: 1 },
+            SyncAction::Delete {
+                file_id: String::from("id-b")
+            },
+            SyncAction::Move { index: 1, position: 1 },
+        ]
+    );
+
+    // existing set is not replaced without state unless forced
+    let remote = get_remote(&["id-a"]);
+    assert!(matches!(
+        SyncPlan::new(set.clone(), Some(&remote), &SyncState::new(), false),
+        Err(StickerSyncError::UnknownSet(ref name)) if name == "set_by_bot"
+    ));
+    let plan = SyncPlan::new(set, Some(&remote), &SyncState::new(), true).unwrap();
+    assert_eq!(plan.get_actions().len(), 4);
+
+    // empty set can not be created and would delete all stickers of an existing set
+    let mut empty = get_set(&["a"]);
+    empty.stickers.clear();
+    assert!(matches!(
+        SyncPlan::new(empty.clone(), None, &SyncState::new(), false),
+        Err(StickerSyncError::EmptySet)
+    ));
+    assert!(matches!(
+        SyncPlan::new(empty, Some(&remote), &SyncState::new(), true),
+        Err(StickerSyncError::EmptySet)
+    ));
+}
+
+#[test]
+fn test_local_sticker_set() {
+    assert!(matches!(
+        LocalStickerSet::new("name", "title", false, vec![]),
+        Err(StickerSyncError::EmptySet)
+    ));
+    let stickers = vec![
+        LocalSticker::new("a.png", vec![1], "😀", None),
+        LocalSticker::new("b.png", vec![1], "😀", None),
+    ];
+    assert!(matches!(
+        LocalStickerSet::new("name", "title", false, stickers),
+        Err(StickerSyncError::DuplicateSticker(ref name)) if name == "b.png"
+    ));
+
+    let dir = env::temp_dir().join(format!("tgbot-stickers-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("a.png"), b"a").unwrap();
+    fs::write(
+        dir.join(MANIFEST_FILE_NAME),
+        json!({
+            "name": "set_by_bot",
+            "title": "Set",
+            "stickers": [{"file": "a.png", "emojis": "😀"}]
+        })
+        .to_string(),
+    )
+    .unwrap();
+    let set = LocalStickerSet::from_dir(&dir).unwrap();
+    assert_eq!(set.name, "set_by_bot");
+    assert!(!set.contains_masks);
+    assert_eq!(set.stickers[0].data, b"a");
+    assert_eq!(
+        set.stickers[0].hash,
+        "ca978112ca1bbdcafac231b39a23dc4da786eff8147c4e72b9807785afee48bb"
+    );
+
+    let state_path = dir.join("state.json");
+    assert_eq!(SyncState::load(&state_path).unwrap(), SyncState::new());
+    let state = get_state(&set, &[("id-a", 0)]);
+    state.save(&state_path).unwrap();
+    assert_eq!(SyncState::load(&state_path).unwrap(), state);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+fn get_png_header(width: u32, height: u32) -> Vec<u8> {
+    let mut data = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+    data.extend_from_slice(&width.to_be_bytes());
+    data.extend_from_slice(&height.to_be_bytes());
+    data.extend_from_slice(&[8, 6, 0, 0, 0]);
+    data
+}
+
+#[test]
+fn test_validate_sticker_image() {
+    assert_eq!(
+        validate_sticker_image(&get_png_header(512, 300)).unwrap(),
+        PngInfo {
+            width: 512,
+            height: 300,
+            size: 29
+        }
+    );
+    assert!(validate_sticker_image(&get_png_header(100, 512)).is_ok());
+    assert!(matches!(
+        validate_sticker_image(b"GIF89a"),
+        Err(StickerImageError::NotPng)
+    ));
+    assert_eq!(
+        validate_sticker_image(&get_png_header(513, 512))
+            .unwrap_err()
+            .to_string(),
+        "Image size is 513x512, expected one side to be 512px and another one to not exceed it"
+    );
+    assert!(matches!(
+        validate_sticker_image(&get_png_header(511, 100)),
+        Err(StickerImageError::InvalidDimensions {
+            width: 511,
+            height: 100
+        })
+    ));
+    let mut data = get_png_header(512, 512);
+    data.resize(MAX_STICKER_FILE_SIZE + 1, 0);
+    assert!(matches!(
+        validate_sticker_image(&data),
+        Err(StickerImageError::TooLarge(size)) if size == MAX_STICKER_FILE_SIZE + 1
+    ));
+
+    let sticker = LocalSticker::new("a.png", get_png_header(512, 512), "😀", None);
+    assert!(sticker.validate().is_ok());
+    let sticker = LocalSticker::new("a.png", get_png_header(512, 512), "a", None);
+    assert!(matches!(
+        sticker.validate(),
+        Err(StickerSyncError::InvalidSticker { .. })
+    ));
+    let sticker = LocalSticker::new("a.png", get_png_header(100, 100), "😀", None);
+    assert_eq!(
+        sticker.validate().unwrap_err().to_string(),
+        "Invalid image a.png: Image size is 100x100, expected one side to be 512px and another one to not exceed it"
+    );
+    assert!(get_set(&["a"]).validate().is_err());
+}
+
+#[cfg(feature = "sticker-normalizer")]
+#[test]
+fn test_normalize_sticker_image() {
+    use image::{DynamicImage, ImageOutputFormat, Rgba, RgbaImage};
+
+    let mut data = Vec::new();
+    DynamicImage::ImageRgba8(RgbaImage::from_pixel(200, 100, Rgba([255, 0, 0, 255])))
+        .write_to(&mut data, ImageOutputFormat::PNG)
+        .unwrap();
+    assert!(validate_sticker_image(&data).is_err());
+
+    let normalized = StickerNormalizer::new().normalize(&data).unwrap();
+    let info = validate_sticker_image(&normalized).unwrap();
+    assert_eq!((info.width, info.height), (512, 256));
+    assert_eq!(StickerNormalizer::new().normalize(&normalized).unwrap(), normalized);
+
+    let squared = StickerNormalizer::new().square(true).normalize(&normalized).unwrap();
+    let info = validate_sticker_image(&squared).unwrap();
+    assert_eq!((info.width, info.height), (512, 512));
+    let image = image::load_from_memory(&squared).unwrap().to_rgba();
+    assert_eq!(image.get_pixel(0, 0), &Rgba([0, 0, 0, 0]));
+    assert_ne!(image.get_pixel(256, 256)[3], 0);
+    assert_eq!(image.get_pixel(256, 127), &Rgba([0, 0, 0, 0]));
+
+    assert!(matches!(
+        StickerNormalizer::new().normalize(b"data"),
+        Err(StickerImageError::Image(_))
+    ));
+}
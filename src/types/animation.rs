@@ -1,8 +1,8 @@
 use crate::types::{photo_size::PhotoSize, primitive::Integer};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// An animation file (GIF or H.264/MPEG-4 AVC video without sound)
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Animation {
     /// Unique file identifier
     pub file_id: String,
@@ -13,11 +13,15 @@ pub struct Animation {
     /// Duration of the video in seconds as defined by sender
     pub duration: Integer,
     /// Animation thumbnail as defined by sender
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub thumb: Option<PhotoSize>,
     /// Original animation filename as defined by sender
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub file_name: Option<String>,
     /// MIME type of the file as defined by sender
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub mime_type: Option<String>,
     /// File size
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub file_size: Option<Integer>,
 }
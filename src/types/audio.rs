@@ -1,21 +1,26 @@
 use crate::types::{photo_size::PhotoSize, primitive::Integer};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// Audio file to be treated as music by the Telegram clients
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Audio {
     /// Unique identifier for this file
     pub file_id: String,
     /// Duration of the audio in seconds as defined by sender
     pub duration: Integer,
     /// Performer of the audio as defined by sender or by audio tags
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub performer: Option<String>,
     /// Title of the audio as defined by sender or by audio tags
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
     /// MIME type of the file as defined by sender
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub mime_type: Option<String>,
     /// File size
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub file_size: Option<Integer>,
     /// Thumbnail of the album cover to which the music file belongs
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub thumb: Option<PhotoSize>,
 }
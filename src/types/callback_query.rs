@@ -1,5 +1,5 @@
 use crate::types::{message::Message, user::User};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// Incoming callback query from a callback button in an inline keyboard
 ///
@@ -8,7 +8,7 @@ use serde::Deserialize;
 /// If the button was attached to a message sent via the bot (in inline mode),
 /// the field inline_message_id will be present
 /// Exactly one of the fields data or game_short_name will be present
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct CallbackQuery {
     /// Unique identifier for this query
     pub id: String,
@@ -17,19 +17,24 @@ pub struct CallbackQuery {
     /// Message with the callback button that originated the query
     /// Note that message content and message date
     /// will not be available if the message is too old
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub message: Option<Message>,
     /// Identifier of the message sent via the bot
     /// in inline mode, that originated the query
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub inline_message_id: Option<String>,
     /// Global identifier, uniquely corresponding
     /// to the chat to which the message with the
     /// callback button was sent
     /// Useful for high scores in games
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub chat_instance: Option<String>,
     /// Data associated with the callback button.
     /// Be aware that a bad client can send arbitrary data in this field
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<String>,
     /// Short name of a Game to be returned,
     /// serves as the unique identifier for the game
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub game_short_name: Option<String>,
 }
@@ -3,10 +3,13 @@ use crate::types::{
     primitive::Integer,
     user::User,
 };
-use serde::de::{Deserialize, Deserializer, Error};
+use serde::{
+    de::{Deserialize, Deserializer, Error},
+    ser::{Serialize, Serializer},
+};
 
 /// Information about one member of a chat
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum ChatMember {
     /// Chat admin
     Administrator(ChatMemberAdministrator),
@@ -83,8 +86,64 @@ impl<'de> Deserialize<'de> for ChatMember {
     }
 }
 
+impl Serialize for ChatMember {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut raw = RawChatMember {
+            user: self.user().clone(),
+            status: RawChatMemberStatus::Member,
+            until_date: None,
+            can_be_edited: None,
+            can_change_info: None,
+            can_post_messages: None,
+            can_edit_messages: None,
+            can_delete_messages: None,
+            can_invite_users: None,
+            can_restrict_members: None,
+            can_pin_messages: None,
+            can_promote_members: None,
+            can_send_messages: None,
+            can_send_media_messages: None,
+            can_send_other_messages: None,
+            can_add_web_page_previews: None,
+        };
+        match self {
+            ChatMember::Administrator(admin) => {
+                raw.status = RawChatMemberStatus::Administrator;
+                raw.can_be_edited = Some(admin.can_be_edited);
+                raw.can_change_info = Some(admin.can_change_info);
+                raw.can_post_messages = Some(admin.can_post_messages);
+                raw.can_edit_messages = Some(admin.can_edit_messages);
+                raw.can_delete_messages = Some(admin.can_delete_messages);
+                raw.can_invite_users = Some(admin.can_invite_users);
+                raw.can_restrict_members = Some(admin.can_restrict_members);
+                raw.can_pin_messages = Some(admin.can_pin_messages);
+                raw.can_promote_members = Some(admin.can_promote_members);
+            }
+            ChatMember::Creator(_) => raw.status = RawChatMemberStatus::Creator,
+            ChatMember::Kicked(kicked) => {
+                raw.status = RawChatMemberStatus::Kicked;
+                raw.until_date = Some(kicked.until_date);
+            }
+            ChatMember::Left(_) => raw.status = RawChatMemberStatus::Left,
+            ChatMember::Member(_) => raw.status = RawChatMemberStatus::Member,
+            ChatMember::Restricted(restricted) => {
+                raw.status = RawChatMemberStatus::Restricted;
+                raw.until_date = Some(restricted.until_date);
+                raw.can_send_messages = Some(restricted.can_send_messages);
+                raw.can_send_media_messages = Some(restricted.can_send_media_messages);
+                raw.can_send_other_messages = Some(restricted.can_send_other_messages);
+                raw.can_add_web_page_previews = Some(restricted.can_add_web_page_previews);
+            }
+        }
+        raw.serialize(serializer)
+    }
+}
+
 /// Chat admin
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct ChatMemberAdministrator {
     /// Information about the user
     pub user: User,
@@ -118,7 +177,7 @@ pub struct ChatMemberAdministrator {
 }
 
 /// Kicked user
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct ChatMemberKicked {
     /// Information about the user
     pub user: User,
@@ -127,7 +186,7 @@ pub struct ChatMemberKicked {
 }
 
 /// Restricted user
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct ChatMemberRestricted {
     /// Information about the user
     pub user: User,
@@ -17,7 +17,7 @@ pub use self::{
 };
 
 /// Chat
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Chat {
     /// Channel
     Channel(ChannelChat),
@@ -83,8 +83,72 @@ impl<'de> Deserialize<'de> for Chat {
     }
 }
 
+impl Serialize for Chat {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut raw_chat = RawChat {
+            id: 0,
+            kind: RawChatKind::Private,
+            title: None,
+            username: None,
+            first_name: None,
+            last_name: None,
+            all_members_are_administrators: None,
+            photo: None,
+            description: None,
+            invite_link: None,
+            pinned_message: None,
+            sticker_set_name: None,
+            can_set_sticker_set: None,
+        };
+        match self {
+            Chat::Channel(chat) => {
+                raw_chat.id = chat.id;
+                raw_chat.kind = RawChatKind::Channel;
+                raw_chat.title = Some(chat.title.clone());
+                raw_chat.username = chat.username.clone();
+                raw_chat.description = chat.description.clone();
+                raw_chat.photo = chat.photo.clone();
+                raw_chat.pinned_message = chat.pinned_message.clone();
+                raw_chat.invite_link = chat.invite_link.clone();
+            }
+            Chat::Group(chat) => {
+                raw_chat.id = chat.id;
+                raw_chat.kind = RawChatKind::Group;
+                raw_chat.title = Some(chat.title.clone());
+                raw_chat.all_members_are_administrators = Some(chat.all_members_are_administrators);
+                raw_chat.photo = chat.photo.clone();
+                raw_chat.pinned_message = chat.pinned_message.clone();
+                raw_chat.invite_link = chat.invite_link.clone();
+            }
+            Chat::Private(chat) => {
+                raw_chat.id = chat.id;
+                raw_chat.username = chat.username.clone();
+                raw_chat.first_name = Some(chat.first_name.clone());
+                raw_chat.last_name = chat.last_name.clone();
+                raw_chat.photo = chat.photo.clone();
+            }
+            Chat::Supergroup(chat) => {
+                raw_chat.id = chat.id;
+                raw_chat.kind = RawChatKind::Supergroup;
+                raw_chat.title = Some(chat.title.clone());
+                raw_chat.username = chat.username.clone();
+                raw_chat.description = chat.description.clone();
+                raw_chat.photo = chat.photo.clone();
+                raw_chat.pinned_message = chat.pinned_message.clone();
+                raw_chat.invite_link = chat.invite_link.clone();
+                raw_chat.sticker_set_name = chat.sticker_set_name.clone();
+                raw_chat.can_set_sticker_set = chat.can_set_sticker_set;
+            }
+        }
+        raw_chat.serialize(serializer)
+    }
+}
+
 /// Channel chat
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct ChannelChat {
     /// Unique identifier for this chat
     pub id: Integer,
@@ -107,7 +171,7 @@ pub struct ChannelChat {
 }
 
 /// Group chat
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct GroupChat {
     /// Unique identifier for this chat
     pub id: Integer,
@@ -127,7 +191,7 @@ pub struct GroupChat {
 }
 
 /// Private chat
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct PrivateChat {
     /// Unique identifier for this chat
     pub id: Integer,
@@ -143,7 +207,7 @@ pub struct PrivateChat {
 }
 
 /// Supergroup chat
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct SupergroupChat {
     /// Unique identifier for this chat
     pub id: Integer,
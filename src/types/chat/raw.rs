@@ -1,25 +1,36 @@
 use crate::types::{chat::photo::ChatPhoto, message::Message, primitive::Integer, user::User};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct RawChat {
     pub id: Integer,
     #[serde(rename = "type")]
     pub kind: RawChatKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub first_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub last_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub all_members_are_administrators: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub photo: Option<ChatPhoto>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub invite_link: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub pinned_message: Option<Box<Message>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub sticker_set_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub can_set_sticker_set: Option<bool>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub enum RawChatKind {
     #[serde(rename = "private")]
     Private,
@@ -31,27 +42,41 @@ pub enum RawChatKind {
     Channel,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct RawChatMember {
     pub user: User,
     pub status: RawChatMemberStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub until_date: Option<Integer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub can_be_edited: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub can_change_info: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub can_post_messages: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub can_edit_messages: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub can_delete_messages: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub can_invite_users: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub can_restrict_members: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub can_pin_messages: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub can_promote_members: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub can_send_messages: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub can_send_media_messages: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub can_send_other_messages: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub can_add_web_page_previews: Option<bool>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub enum RawChatMemberStatus {
     #[serde(rename = "administrator")]
     Administrator,
@@ -1,4 +1,4 @@
-use crate::types::chat::Chat;
+use crate::types::{chat::Chat, tests::assert_round_trip};
 
 use serde_json::json;
 
@@ -27,6 +27,7 @@ fn test_deserialize_channel() {
         }
     });
     let chat: Chat = serde_json::from_value(input).unwrap();
+    assert_round_trip(&chat);
     if let Chat::Channel(chat) = chat {
         assert_eq!(chat.id, 1);
         assert_eq!(chat.title, String::from("channeltitle"));
@@ -46,6 +47,7 @@ fn test_deserialize_channel() {
         "title": "channeltitle"
     });
     let chat: Chat = serde_json::from_value(input).unwrap();
+    assert_round_trip(&chat);
     if let Chat::Channel(chat) = chat {
         assert_eq!(chat.id, 1);
         assert_eq!(chat.title, String::from("channeltitle"));
@@ -89,6 +91,7 @@ fn test_deserialize_group() {
         }
     });
     let chat: Chat = serde_json::from_value(input).unwrap();
+    assert_round_trip(&chat);
     if let Chat::Group(chat) = chat {
         assert_eq!(chat.id, 1);
         assert_eq!(chat.title, String::from("grouptitle"));
@@ -108,6 +111,7 @@ fn test_deserialize_group() {
         "all_members_are_administrators": false
     });
     let chat: Chat = serde_json::from_value(input).unwrap();
+    assert_round_trip(&chat);
     if let Chat::Group(chat) = chat {
         assert_eq!(chat.id, 1);
         assert_eq!(chat.title, String::from("grouptitle"));
@@ -134,6 +138,7 @@ fn test_deserialize_private() {
         }
     });
     let chat: Chat = serde_json::from_value(input).unwrap();
+    assert_round_trip(&chat);
     if let Chat::Private(chat) = chat {
         assert_eq!(chat.id, 1);
         assert_eq!(chat.username, Some(String::from("testusername")));
@@ -152,6 +157,7 @@ fn test_deserialize_private() {
         "first_name": "testfirstname"
     });
     let chat: Chat = serde_json::from_value(input).unwrap();
+    assert_round_trip(&chat);
     if let Chat::Private(chat) = chat {
         assert_eq!(chat.id, 1);
         assert_eq!(chat.username.is_none(), true);
@@ -196,6 +202,7 @@ fn test_deserialize_supergroup() {
         }
     });
     let chat: Chat = serde_json::from_value(input).unwrap();
+    assert_round_trip(&chat);
     if let Chat::Supergroup(chat) = chat {
         assert_eq!(chat.id, 1);
         assert_eq!(chat.title, String::from("supergrouptitle"));
@@ -218,6 +225,7 @@ fn test_deserialize_supergroup() {
         "username": "supergroupusername"
     });
     let chat: Chat = serde_json::from_value(input).unwrap();
+    assert_round_trip(&chat);
     if let Chat::Supergroup(chat) = chat {
         assert_eq!(chat.id, 1);
         assert_eq!(chat.title, String::from("supergrouptitle"));
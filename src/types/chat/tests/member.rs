@@ -1,4 +1,4 @@
-use crate::types::chat::member::ChatMember;
+use crate::types::{chat::member::ChatMember, tests::assert_round_trip};
 
 use serde_json::json;
 
@@ -25,6 +25,7 @@ fn test_deserialize_chat_member_admin() {
         "can_promote_members": true
     });
     let admin: ChatMember = serde_json::from_value(admin).unwrap();
+    assert_round_trip(&admin);
     if let ChatMember::Administrator(ref admin) = admin {
         assert_eq!(admin.user.id, 1);
         assert_eq!(admin.user.is_bot, false);
@@ -57,6 +58,7 @@ fn test_deserialize_chat_member_creator() {
         }
     });
     let creator: ChatMember = serde_json::from_value(creator).unwrap();
+    assert_round_trip(&creator);
     if let ChatMember::Creator(ref creator) = creator {
         assert_eq!(creator.id, 1);
         assert_eq!(creator.is_bot, false);
@@ -83,6 +85,7 @@ fn test_deserialize_chat_member_kicked() {
         "until_date": 0
     });
     let kicked: ChatMember = serde_json::from_value(kicked).unwrap();
+    assert_round_trip(&kicked);
     if let ChatMember::Kicked(ref kicked) = kicked {
         assert_eq!(kicked.user.id, 1);
         assert_eq!(kicked.user.is_bot, true);
@@ -107,6 +110,7 @@ fn test_deserialize_chat_member_left() {
         }
     });
     let left: ChatMember = serde_json::from_value(left).unwrap();
+    assert_round_trip(&left);
     if let ChatMember::Left(ref left) = left {
         assert_eq!(left.id, 1);
         assert_eq!(left.is_bot, true);
@@ -130,6 +134,7 @@ fn test_deserialize_chat_member_plain() {
         }
     });
     let plain: ChatMember = serde_json::from_value(plain).unwrap();
+    assert_round_trip(&plain);
     if let ChatMember::Member(ref plain) = plain {
         assert_eq!(plain.id, 1);
         assert_eq!(plain.is_bot, false);
@@ -158,6 +163,7 @@ fn test_deserialize_chat_member_restricted() {
         "can_add_web_page_previews": false
     });
     let restricted: ChatMember = serde_json::from_value(restricted).unwrap();
+    assert_round_trip(&restricted);
     if let ChatMember::Restricted(ref restricted) = restricted {
         assert_eq!(restricted.user.id, 1);
         assert_eq!(restricted.user.is_bot, true);
@@ -1,5 +1,5 @@
 use crate::types::primitive::Integer;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// File ready to be downloaded
 ///
@@ -7,13 +7,15 @@ use serde::Deserialize;
 /// It is guaranteed that the link will be valid for at least 1 hour
 /// When the link expires, a new one can be requested by calling getFile
 /// Maximum file size to download is 20 MB
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct File {
     /// Unique identifier for this file
     pub file_id: String,
     /// File size, if known
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub file_size: Option<Integer>,
     /// File path
     /// Use https://api.telegram.org/file/bot<token>/<file_path> to get the file
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub file_path: Option<String>,
 }
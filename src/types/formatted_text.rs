@@ -0,0 +1,168 @@
+use crate::types::{
+    message::{Text, TextEntity, TextEntityData, TextLink, TextMention},
+    primitive::ParseMode,
+    user::User,
+};
+
+/// Builder of formatted text
+///
+/// Given strings are escaped according to parse mode, so user input can be formatted safely
+/// Markdown does not support escaping inside of entities,
+/// so an entity containing its own markup characters is sent as plain text
+#[derive(Clone, Debug)]
+pub struct FormattedText {
+    parse_mode: ParseMode,
+    text: Text,
+    len: usize,
+}
+
+impl FormattedText {
+    /// Creates an empty text for given parse mode
+    pub fn new(parse_mode: ParseMode) -> Self {
+        FormattedText {
+            parse_mode,
+            text: Text {
+                data: String::new(),
+                entities: None,
+                warnings: Vec::new(),
+            },
+            len: 0,
+        }
+    }
+
+    /// Appends a plain text
+    pub fn plain<S: AsRef<str>>(mut self, data: S) -> Self {
+        self.push(data.as_ref());
+        self
+    }
+
+    /// Appends a bold text
+    pub fn bold<S: AsRef<str>>(self, data: S) -> Self {
+        self.push_entity(data.as_ref(), TextEntity::Bold)
+    }
+
+    /// Appends an italic text
+    pub fn italic<S: AsRef<str>>(self, data: S) -> Self {
+        self.push_entity(data.as_ref(), TextEntity::Italic)
+    }
+
+    /// Appends a monowidth string
+    pub fn code<S: AsRef<str>>(self, data: S) -> Self {
+        self.push_entity(data.as_ref(), TextEntity::Code)
+    }
+
+    /// Appends a monowidth block
+    pub fn pre<S: AsRef<str>>(self, data: S) -> Self {
+        self.push_entity(data.as_ref(), TextEntity::Pre)
+    }
+
+    /// Appends a clickable text URL
+    pub fn link<S: AsRef<str>, U: Into<String>>(self, data: S, url: U) -> Self {
+        let url = url.into();
+        self.push_entity(data.as_ref(), |data| TextEntity::TextLink(TextLink { data, url }))
+    }
+
+    /// Appends a mention of a user using full name of the user
+    ///
+    /// Works for users without username
+    pub fn mention(self, user: &User) -> Self {
+        let user = user.clone();
+        self.push_entity(&user.get_full_name(), |data| {
+            TextEntity::TextMention(TextMention { data, user })
+        })
+    }
+
+    /// Returns parse mode of the text
+    pub fn get_parse_mode(&self) -> ParseMode {
+        self.parse_mode
+    }
+
+    /// Returns text without markup and its entities
+    pub fn get_text(&self) -> &Text {
+        &self.text
+    }
+
+    /// Returns text with escaped markup for the parse mode
+    pub fn render(&self) -> String {
+        match self.parse_mode {
+            ParseMode::Html => self.text.to_html(),
+            ParseMode::Markdown => self.text.to_markdown(),
+        }
+    }
+
+    fn push(&mut self, data: &str) -> TextEntityData {
+        let length = data.encode_utf16().count();
+        let entity_data = TextEntityData {
+            offset: self.len,
+            length,
+            data: String::from(data),
+        };
+        self.text.data += data;
+        self.len += length;
+        entity_data
+    }
+
+    fn push_entity<F>(mut self, data: &str, entity: F) -> Self
+    where
+        F: FnOnce(TextEntityData) -> TextEntity,
+    {
+        let entity = entity(self.push(data));
+        self.text.entities.get_or_insert_with(Vec::new).push(entity);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(parse_mode: ParseMode) -> String {
+        let user = User {
+            id: 1,
+            is_bot: false,
+            first_name: String::from("First"),
+            last_name: Some(String::from("<Last>")),
+            username: None,
+            language_code: None,
+        };
+        FormattedText::new(parse_mode)
+            .plain("1 < 2 & *a_b* ")
+            .bold("bold")
+            .plain(" ")
+            .italic("😀 italic")
+            .plain(" ")
+            .code("a < b")
+            .plain(" ")
+            .link("link", "https://example.com/?a=1&b=2")
+            .plain(" ")
+            .mention(&user)
+            .plain(" ")
+            .bold("*x*")
+            .render()
+    }
+
+    #[test]
+    fn test_formatted_text() {
+        assert_eq!(
+            build(ParseMode::Html),
+            "1 &lt; 2 &amp; *a_b* <b>bold</b> <i>😀 italic</i> <code>a &lt; b</code> \
+             <a href=\"https://example.com/?a=1&amp;b=2\">link</a> \
+             <a href=\"tg://user?id=1\">First &lt;Last&gt;</a> <b>*x*</b>"
+        );
+        assert_eq!(
+            build(ParseMode::Markdown),
+            "1 < 2 & \\*a\\_b\\* *bold* _😀 italic_ `a < b` [link](https://example.com/?a=1&b=2) \
+             [First <Last>](tg://user?id=1) \\*x\\*"
+        );
+
+        let text = FormattedText::new(ParseMode::Html).plain("😀 ").bold("b");
+        assert_eq!(
+            text.get_text().entities,
+            Some(vec![TextEntity::Bold(TextEntityData {
+                offset: 3,
+                length: 1,
+                data: String::from("b")
+            })])
+        );
+    }
+}
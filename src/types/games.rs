@@ -5,13 +5,13 @@ use crate::types::{
     primitive::Integer,
     user::User,
 };
-use serde::{de::Error, Deserialize, Deserializer};
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
 
 /// Game
 ///
 /// Use BotFather to create and edit games,
 /// their short names will act as unique identifiers
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Game {
     /// Title of the game
     pub title: String,
@@ -48,18 +48,38 @@ impl<'de> Deserialize<'de> for Game {
     }
 }
 
-#[derive(Debug, Deserialize)]
+impl Serialize for Game {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        RawGame {
+            title: self.title.clone(),
+            description: self.description.clone(),
+            photo: self.photo.clone(),
+            text: self.text.as_ref().map(|text| text.data.clone()),
+            text_entities: self.text.as_ref().and_then(Text::to_raw_entities),
+            animation: self.animation.clone(),
+        }
+        .serialize(serializer)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
 struct RawGame {
     title: String,
     description: String,
     photo: Vec<PhotoSize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     text_entities: Option<Vec<RawMessageEntity>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     animation: Option<Animation>,
 }
 
 /// One row of the high scores table for a game
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct GameHighScore {
     /// Position in high score table for the game
     pub position: Integer,
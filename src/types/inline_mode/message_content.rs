@@ -1,4 +1,7 @@
-use crate::types::primitive::{Float, Integer, ParseMode};
+use crate::types::{
+    formatted_text::FormattedText,
+    primitive::{Float, Integer, ParseMode},
+};
 use serde::Serialize;
 
 /// Content of a message to be sent as a result of an inline query
@@ -116,6 +119,13 @@ impl InputMessageContentText {
         self
     }
 
+    /// Sets text and parse mode using formatted text
+    pub fn formatted_text(mut self, text: FormattedText) -> Self {
+        self.message_text = text.render();
+        self.parse_mode = Some(text.get_parse_mode());
+        self
+    }
+
     /// Disables link previews for links in the sent message
     pub fn disable_web_page_preview(mut self, disable_web_page_preview: bool) -> Self {
         self.disable_web_page_preview = Some(disable_web_page_preview);
@@ -6,7 +6,7 @@ use serde::Serialize;
 /// Link to an article or web page
 #[derive(Clone, Debug, Serialize)]
 pub struct InlineQueryResultArticle {
-    id: String,
+    pub(crate) id: String,
     title: String,
     input_message_content: InputMessageContent,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1,4 +1,5 @@
 use crate::types::{
+    formatted_text::FormattedText,
     inline_mode::message_content::InputMessageContent,
     primitive::{Integer, ParseMode},
     reply_markup::InlineKeyboardMarkup,
@@ -12,7 +13,7 @@ use serde::Serialize;
 /// a message with the specified content instead of the audio
 #[derive(Clone, Debug, Serialize)]
 pub struct InlineQueryResultAudio {
-    id: String,
+    pub(crate) id: String,
     audio_url: String,
     title: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -63,6 +64,13 @@ impl InlineQueryResultAudio {
         self
     }
 
+    /// Sets caption and parse mode using formatted text
+    pub fn formatted_caption(mut self, caption: FormattedText) -> Self {
+        self.caption = Some(caption.render());
+        self.parse_mode = Some(caption.get_parse_mode());
+        self
+    }
+
     /// Performer
     pub fn performer<S: Into<String>>(mut self, performer: S) -> Self {
         self.performer = Some(performer.into());
@@ -1,5 +1,6 @@
 use crate::types::{
-    inline_mode::message_content::InputMessageContent, primitive::ParseMode, reply_markup::InlineKeyboardMarkup,
+    formatted_text::FormattedText, inline_mode::message_content::InputMessageContent, primitive::ParseMode,
+    reply_markup::InlineKeyboardMarkup,
 };
 use serde::Serialize;
 
@@ -10,7 +11,7 @@ use serde::Serialize;
 /// a message with the specified content instead of the photo
 #[derive(Clone, Debug, Serialize)]
 pub struct InlineQueryResultCachedPhoto {
-    id: String,
+    pub(crate) id: String,
     photo_file_id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     title: Option<String>,
@@ -70,6 +71,13 @@ impl InlineQueryResultCachedPhoto {
         self
     }
 
+    /// Sets caption and parse mode using formatted text
+    pub fn formatted_caption(mut self, caption: FormattedText) -> Self {
+        self.caption = Some(caption.render());
+        self.parse_mode = Some(caption.get_parse_mode());
+        self
+    }
+
     /// Inline keyboard attached to the message
     pub fn reply_markup<I: Into<InlineKeyboardMarkup>>(mut self, reply_markup: I) -> Self {
         self.reply_markup = Some(reply_markup.into());
@@ -8,7 +8,7 @@ use serde::Serialize;
 /// send a message with the specified content instead of the sticker
 #[derive(Clone, Debug, Serialize)]
 pub struct InlineQueryResultCachedSticker {
-    id: String,
+    pub(crate) id: String,
     sticker_file_id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     reply_markup: Option<InlineKeyboardMarkup>,
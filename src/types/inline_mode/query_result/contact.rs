@@ -10,7 +10,7 @@ use serde::Serialize;
 /// to send a message with the specified content instead of the contact
 #[derive(Clone, Debug, Serialize)]
 pub struct InlineQueryResultContact {
-    id: String,
+    pub(crate) id: String,
     phone_number: String,
     first_name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1,4 +1,5 @@
 use crate::types::{
+    formatted_text::FormattedText,
     inline_mode::message_content::InputMessageContent,
     primitive::{Integer, ParseMode},
     reply_markup::InlineKeyboardMarkup,
@@ -13,7 +14,7 @@ use serde::Serialize;
 /// Currently, only .PDF and .ZIP files can be sent using this method
 #[derive(Clone, Debug, Serialize)]
 pub struct InlineQueryResultDocument {
-    id: String,
+    pub(crate) id: String,
     title: String,
     document_url: String,
     mime_type: String,
@@ -73,6 +74,13 @@ impl InlineQueryResultDocument {
         self
     }
 
+    /// Sets caption and parse mode using formatted text
+    pub fn formatted_caption(mut self, caption: FormattedText) -> Self {
+        self.caption = Some(caption.render());
+        self.parse_mode = Some(caption.get_parse_mode());
+        self
+    }
+
     /// Short description of the result
     pub fn description<S: Into<String>>(mut self, description: S) -> Self {
         self.description = Some(description.into());
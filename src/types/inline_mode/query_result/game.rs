@@ -4,7 +4,7 @@ use serde::Serialize;
 /// Game
 #[derive(Clone, Debug, Serialize)]
 pub struct InlineQueryResultGame {
-    id: String,
+    pub(crate) id: String,
     game_short_name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     reply_markup: Option<InlineKeyboardMarkup>,
@@ -12,7 +12,7 @@ use serde::Serialize;
 /// to send a message with the specified content instead of the location
 #[derive(Clone, Debug, Serialize)]
 pub struct InlineQueryResultLocation {
-    id: String,
+    pub(crate) id: String,
     latitude: Float,
     longitude: Float,
     title: String,
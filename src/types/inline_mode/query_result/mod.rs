@@ -86,3 +86,32 @@ pub enum InlineQueryResult {
     #[serde(rename = "voice")]
     Voice(InlineQueryResultVoice),
 }
+
+impl InlineQueryResult {
+    /// Returns unique identifier of the result
+    pub fn get_id(&self) -> &str {
+        use self::InlineQueryResult::*;
+        match *self {
+            Article(ref result) => &result.id,
+            Audio(ref result) => &result.id,
+            CachedAudio(ref result) => &result.id,
+            CachedDocument(ref result) => &result.id,
+            CachedGif(ref result) => &result.id,
+            CachedMpeg4Gif(ref result) => &result.id,
+            CachedPhoto(ref result) => &result.id,
+            CachedSticker(ref result) => &result.id,
+            CachedVideo(ref result) => &result.id,
+            CachedVoice(ref result) => &result.id,
+            Contact(ref result) => &result.id,
+            Document(ref result) => &result.id,
+            Game(ref result) => &result.id,
+            Gif(ref result) => &result.id,
+            Location(ref result) => &result.id,
+            Mpeg4Gif(ref result) => &result.id,
+            Photo(ref result) => &result.id,
+            Venue(ref result) => &result.id,
+            Video(ref result) => &result.id,
+            Voice(ref result) => &result.id,
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::types::{
+    formatted_text::FormattedText,
     inline_mode::message_content::InputMessageContent,
     primitive::{Integer, ParseMode},
     reply_markup::InlineKeyboardMarkup,
@@ -12,7 +13,7 @@ use serde::Serialize;
 /// to send a message with the specified content instead of the animation
 #[derive(Clone, Debug, Serialize)]
 pub struct InlineQueryResultMpeg4Gif {
-    id: String,
+    pub(crate) id: String,
     mpeg4_url: String,
     thumb_url: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -93,6 +94,13 @@ impl InlineQueryResultMpeg4Gif {
         self
     }
 
+    /// Sets caption and parse mode using formatted text
+    pub fn formatted_caption(mut self, caption: FormattedText) -> Self {
+        self.caption = Some(caption.render());
+        self.parse_mode = Some(caption.get_parse_mode());
+        self
+    }
+
     /// Inline keyboard attached to the message
     pub fn reply_markup<I: Into<InlineKeyboardMarkup>>(mut self, reply_markup: I) -> Self {
         self.reply_markup = Some(reply_markup.into());
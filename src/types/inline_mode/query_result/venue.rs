@@ -12,7 +12,7 @@ use serde::Serialize;
 /// to send a message with the specified content instead of the venue
 #[derive(Clone, Debug, Serialize)]
 pub struct InlineQueryResultVenue {
-    id: String,
+    pub(crate) id: String,
     latitude: Float,
     longitude: Float,
     title: String,
@@ -1,4 +1,5 @@
 use crate::types::{
+    formatted_text::FormattedText,
     inline_mode::message_content::InputMessageContent,
     primitive::{Integer, ParseMode},
     reply_markup::InlineKeyboardMarkup,
@@ -14,7 +15,7 @@ use serde::Serialize;
 /// you must replace its content using input_message_content
 #[derive(Clone, Debug, Serialize)]
 pub struct InlineQueryResultVideo {
-    id: String,
+    pub(crate) id: String,
     video_url: String,
     mime_type: String,
     thumb_url: String,
@@ -77,6 +78,13 @@ impl InlineQueryResultVideo {
         self
     }
 
+    /// Sets caption and parse mode using formatted text
+    pub fn formatted_caption(mut self, caption: FormattedText) -> Self {
+        self.caption = Some(caption.render());
+        self.parse_mode = Some(caption.get_parse_mode());
+        self
+    }
+
     /// Video width
     pub fn video_width(mut self, video_width: Integer) -> Self {
         self.video_width = Some(video_width);
@@ -1,4 +1,5 @@
 use crate::types::{
+    formatted_text::FormattedText,
     inline_mode::message_content::InputMessageContent,
     primitive::{Integer, ParseMode},
     reply_markup::InlineKeyboardMarkup,
@@ -12,7 +13,7 @@ use serde::Serialize;
 /// a message with the specified content instead of the the voice message
 #[derive(Clone, Debug, Serialize)]
 pub struct InlineQueryResultVoice {
-    id: String,
+    pub(crate) id: String,
     voice_url: String,
     title: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -60,6 +61,13 @@ impl InlineQueryResultVoice {
         self
     }
 
+    /// Sets caption and parse mode using formatted text
+    pub fn formatted_caption(mut self, caption: FormattedText) -> Self {
+        self.caption = Some(caption.render());
+        self.parse_mode = Some(caption.get_parse_mode());
+        self
+    }
+
     /// Recording duration in seconds
     pub fn voice_duration(mut self, voice_duration: Integer) -> Self {
         self.voice_duration = Some(voice_duration);
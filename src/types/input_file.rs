@@ -0,0 +1,72 @@
+use std::{fmt, fs, io, path::Path};
+
+/// File to be sent
+///
+/// Can be a file_id of a file which exists on the Telegram servers,
+/// an HTTP URL for Telegram to get a file from the Internet,
+/// or contents of a new file to be uploaded using multipart/form-data
+#[derive(Clone)]
+pub struct InputFile {
+    pub(crate) kind: InputFileKind,
+}
+
+#[derive(Clone)]
+pub(crate) enum InputFileKind {
+    Id(String),
+    Url(String),
+    Upload { file_name: String, data: Vec<u8> },
+}
+
+impl InputFile {
+    /// A file that exists on the Telegram servers
+    pub fn file_id<S: Into<String>>(file_id: S) -> Self {
+        InputFile {
+            kind: InputFileKind::Id(file_id.into()),
+        }
+    }
+
+    /// An HTTP URL for Telegram to get a file from the Internet
+    pub fn url<S: Into<String>>(url: S) -> Self {
+        InputFile {
+            kind: InputFileKind::Url(url.into()),
+        }
+    }
+
+    /// A new file to be uploaded
+    ///
+    /// # Arguments
+    ///
+    /// * file_name - Name of the file
+    /// * data - Contents of the file
+    pub fn bytes<S: Into<String>, D: Into<Vec<u8>>>(file_name: S, data: D) -> Self {
+        InputFile {
+            kind: InputFileKind::Upload {
+                file_name: file_name.into(),
+                data: data.into(),
+            },
+        }
+    }
+
+    /// Reads a new file to be uploaded from a local path
+    pub fn path<P: AsRef<Path>>(path: P) -> Result<Self, io::Error> {
+        let path = path.as_ref();
+        let file_name = path
+            .file_name()
+            .map(|x| x.to_string_lossy().into_owned())
+            .unwrap_or_else(|| String::from("file"));
+        Ok(InputFile::bytes(file_name, fs::read(path)?))
+    }
+}
+
+impl fmt::Debug for InputFile {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            InputFileKind::Id(ref file_id) => write!(out, "InputFile::Id({:?})", file_id),
+            InputFileKind::Url(ref url) => write!(out, "InputFile::Url({:?})", url),
+            InputFileKind::Upload {
+                ref file_name,
+                ref data,
+            } => write!(out, "InputFile::Upload({:?}, {} bytes)", file_name, data.len()),
+        }
+    }
+}
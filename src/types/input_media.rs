@@ -1,4 +1,8 @@
-use crate::types::primitive::{Integer, ParseMode};
+use crate::types::{
+    formatted_text::FormattedText,
+    primitive::{Integer, ParseMode},
+    validation::{check_caption, ValidationError},
+};
 use serde::Serialize;
 
 /// Photo or video to be sent in a media group
@@ -12,6 +16,15 @@ pub enum MediaGroupItem {
     Video(InputMediaVideo),
 }
 
+impl MediaGroupItem {
+    pub(crate) fn validate(&self) -> Result<(), ValidationError> {
+        match self {
+            MediaGroupItem::Photo(photo) => check_caption(photo.caption.as_deref(), photo.parse_mode),
+            MediaGroupItem::Video(video) => check_caption(video.caption.as_deref(), video.parse_mode),
+        }
+    }
+}
+
 /// Content of a media message to be sent
 #[derive(Clone, Debug, derive_more::From, Serialize)]
 #[serde(tag = "type")]
@@ -98,6 +111,13 @@ impl InputMediaAnimation {
         self
     }
 
+    /// Sets caption and parse mode using formatted text
+    pub fn formatted_caption(mut self, caption: FormattedText) -> Self {
+        self.caption = Some(caption.render());
+        self.parse_mode = Some(caption.get_parse_mode());
+        self
+    }
+
     /// Set width
     pub fn width(mut self, width: Integer) -> Self {
         self.width = Some(width);
@@ -182,6 +202,13 @@ impl InputMediaAudio {
         self
     }
 
+    /// Sets caption and parse mode using formatted text
+    pub fn formatted_caption(mut self, caption: FormattedText) -> Self {
+        self.caption = Some(caption.render());
+        self.parse_mode = Some(caption.get_parse_mode());
+        self
+    }
+
     /// Set duration
     pub fn duration(mut self, duration: Integer) -> Self {
         self.duration = Some(duration);
@@ -256,6 +283,13 @@ impl InputMediaDocument {
         self.parse_mode = Some(parse_mode);
         self
     }
+
+    /// Sets caption and parse mode using formatted text
+    pub fn formatted_caption(mut self, caption: FormattedText) -> Self {
+        self.caption = Some(caption.render());
+        self.parse_mode = Some(caption.get_parse_mode());
+        self
+    }
 }
 
 /// Photo to be sent
@@ -296,6 +330,13 @@ impl InputMediaPhoto {
         self.parse_mode = Some(parse_mode);
         self
     }
+
+    /// Sets caption and parse mode using formatted text
+    pub fn formatted_caption(mut self, caption: FormattedText) -> Self {
+        self.caption = Some(caption.render());
+        self.parse_mode = Some(caption.get_parse_mode());
+        self
+    }
 }
 
 /// Video to be sent
@@ -366,6 +407,13 @@ impl InputMediaVideo {
         self
     }
 
+    /// Sets caption and parse mode using formatted text
+    pub fn formatted_caption(mut self, caption: FormattedText) -> Self {
+        self.caption = Some(caption.render());
+        self.parse_mode = Some(caption.get_parse_mode());
+        self
+    }
+
     /// Set width
     pub fn width(mut self, width: Integer) -> Self {
         self.width = Some(width);
@@ -1,8 +1,8 @@
 use crate::types::primitive::Float;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// Point on the map
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Location {
     /// Longitude as defined by sender
     pub longitude: Float,
@@ -17,9 +17,10 @@ use crate::types::{
     video_note::VideoNote,
     voice::Voice,
 };
+use serde_json::Value;
 
 /// Contains message data
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 #[allow(clippy::large_enum_variant)]
 pub enum MessageData {
     /// Message is an animation, information about the animation
@@ -114,4 +115,8 @@ pub enum MessageData {
         /// Voice data
         data: Voice,
     },
+    /// Message data of unknown kind
+    ///
+    /// Contains all fields of the message which are not recognized
+    Unknown(Value),
 }
@@ -1,7 +1,7 @@
 use crate::types::{chat::ChannelChat, primitive::Integer, user::User};
 
 /// Contains information about original message
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Forward {
     /// Sender of the original message
     pub from: ForwardFrom,
@@ -10,7 +10,7 @@ pub struct Forward {
 }
 
 /// Sender of the original message
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum ForwardFrom {
     /// Information about user
     User(User),
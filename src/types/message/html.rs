@@ -0,0 +1,246 @@
+use crate::types::message::text::{Text, TextEntity, TextEntityData, TextLink};
+
+/// An error when parsing HTML
+#[derive(Debug, failure::Fail)]
+pub enum ParseHtmlError {
+    /// Tag is not supported by Telegram
+    #[fail(display = "Unsupported tag: \"{}\"", _0)]
+    UnsupportedTag(String),
+    /// End tag does not match a start tag
+    #[fail(display = "Unexpected end tag: \"{}\"", _0)]
+    UnexpectedEndTag(String),
+    /// Tag is not closed
+    #[fail(display = "Tag is not closed: \"{}\"", _0)]
+    UnclosedTag(String),
+    /// Link does not have href attribute
+    #[fail(display = "Link has no href attribute")]
+    NoHref,
+}
+
+enum Tag {
+    Bold,
+    Italic,
+    Code,
+    Pre,
+    Link(String),
+}
+
+impl Text {
+    /// Parses text formatted with HTML supported by Telegram
+    ///
+    /// Supported tags: b, strong, i, em, code, pre and a with href attribute
+    pub fn from_html(html: &str) -> Result<Text, ParseHtmlError> {
+        let mut data = String::new();
+        let mut len = 0;
+        let mut stack: Vec<(String, Tag, usize)> = Vec::new();
+        let mut entities = Vec::new();
+        let mut rest = html;
+        while let Some(idx) = rest.find(&['<', '&'][..]) {
+            let (text, tail) = rest.split_at(idx);
+            push_str(&mut data, &mut len, text);
+            if tail.starts_with('&') {
+                let (decoded, tail) = decode_entity(tail);
+                push_str(&mut data, &mut len, &decoded);
+                rest = tail;
+                continue;
+            }
+            let end = match tail.find('>') {
+                Some(end) => end,
+                None => return Err(ParseHtmlError::UnclosedTag(String::from(tail))),
+            };
+            let tag = &tail[1..end];
+            rest = &tail[end + 1..];
+            if let Some(name) = tag.strip_prefix('/') {
+                let name = name.trim().to_lowercase();
+                match stack.pop() {
+                    Some((start_name, tag, offset)) if start_name == name => {
+                        let units: Vec<u16> = data.encode_utf16().skip(offset).collect();
+                        let entity_data = TextEntityData {
+                            offset,
+                            length: units.len(),
+                            data: String::from_utf16_lossy(&units),
+                        };
+                        entities.push(match tag {
+                            Tag::Bold => TextEntity::Bold(entity_data),
+                            Tag::Italic => TextEntity::Italic(entity_data),
+                            Tag::Code => TextEntity::Code(entity_data),
+                            Tag::Pre => TextEntity::Pre(entity_data),
+                            Tag::Link(url) => TextEntity::TextLink(TextLink { data: entity_data, url }),
+                        });
+                    }
+                    _ => return Err(ParseHtmlError::UnexpectedEndTag(name)),
+                }
+            } else {
+                let (name, attrs) = match tag.find(char::is_whitespace) {
+                    Some(idx) => (&tag[..idx], &tag[idx..]),
+                    None => (tag, ""),
+                };
+                let name = name.to_lowercase();
+                let tag = match name.as_str() {
+                    "b" | "strong" => Tag::Bold,
+                    "i" | "em" => Tag::Italic,
+                    "code" => Tag::Code,
+                    "pre" => Tag::Pre,
+                    "a" => match get_href(attrs) {
+                        Some(href) => Tag::Link(href),
+                        None => return Err(ParseHtmlError::NoHref),
+                    },
+                    _ => return Err(ParseHtmlError::UnsupportedTag(name)),
+                };
+                stack.push((name, tag, len));
+            }
+        }
+        push_str(&mut data, &mut len, rest);
+        if let Some((name, _, _)) = stack.pop() {
+            return Err(ParseHtmlError::UnclosedTag(name));
+        }
+        entities.sort_by_key(|entity| entity.get_data().offset);
+        Ok(Text {
+            data,
+            entities: if entities.is_empty() { None } else { Some(entities) },
+            warnings: Vec::new(),
+        })
+    }
+}
+
+fn push_str(data: &mut String, len: &mut usize, value: &str) {
+    data.push_str(value);
+    *len += value.encode_utf16().count();
+}
+
+/// Decodes an HTML entity at the start of a string
+///
+/// Unknown entities are kept as is
+fn decode_entity(data: &str) -> (String, &str) {
+    if let Some(end) = data.find(';') {
+        let name = &data[1..end];
+        let decoded = match name {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            _ => if let Some(code) = name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+                u32::from_str_radix(code, 16).ok()
+            } else if let Some(code) = name.strip_prefix('#') {
+                code.parse().ok()
+            } else {
+                None
+            }
+            .and_then(std::char::from_u32),
+        };
+        if let Some(decoded) = decoded {
+            return (decoded.to_string(), &data[end + 1..]);
+        }
+    }
+    (String::from("&"), &data[1..])
+}
+
+/// Returns a value of href attribute
+///
+/// Attribute names are compared case-insensitively, values may be quoted or unquoted
+fn get_href(attrs: &str) -> Option<String> {
+    let mut rest = attrs.trim_start();
+    while !rest.is_empty() {
+        let name_end = rest.find(|c: char| c == '=' || c.is_whitespace()).unwrap_or(rest.len());
+        let name = &rest[..name_end];
+        rest = rest[name_end..].trim_start();
+        let value = if rest.starts_with('=') {
+            let (value, tail) = split_attr_value(rest[1..].trim_start())?;
+            rest = tail.trim_start();
+            Some(value)
+        } else {
+            None
+        };
+        if name.eq_ignore_ascii_case("href") {
+            return value.map(decode_entities);
+        }
+    }
+    None
+}
+
+/// Splits an attribute value from the rest of attributes
+fn split_attr_value(data: &str) -> Option<(&str, &str)> {
+    match data.chars().next() {
+        Some(quote @ '"') | Some(quote @ '\'') => {
+            let data = &data[1..];
+            let end = data.find(quote)?;
+            Some((&data[..end], &data[end + 1..]))
+        }
+        _ => {
+            let end = data.find(char::is_whitespace).unwrap_or(data.len());
+            Some((&data[..end], &data[end..]))
+        }
+    }
+}
+
+fn decode_entities(value: &str) -> String {
+    let mut result = String::new();
+    let mut rest = value;
+    while let Some(idx) = rest.find('&') {
+        result.push_str(&rest[..idx]);
+        let (decoded, tail) = decode_entity(&rest[idx..]);
+        result.push_str(&decoded);
+        rest = tail;
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_html() {
+        let text = Text::from_html(
+            "😀 <b>bold <I>italic</I></b> &lt;&amp;&#33;&#x21;&unknown; \
+             <a href=\"https://example.com/?a=1&amp;b=2\">link</a> <code>code</code><pre>pre</pre>",
+        )
+        .unwrap();
+        assert_eq!(text.data, "😀 bold italic <&!!&unknown; link codepre");
+        assert_eq!(
+            text.to_html(),
+            "😀 <b>bold <i>italic</i></b> &lt;&amp;!!&amp;unknown; \
+             <a href=\"https://example.com/?a=1&amp;b=2\">link</a> <code>code</code><pre>pre</pre>"
+        );
+        let entities = text.entities.unwrap();
+        assert_eq!(entities.len(), 5);
+        assert_eq!(
+            entities[1],
+            TextEntity::Italic(TextEntityData {
+                offset: 8,
+                length: 6,
+                data: String::from("italic")
+            })
+        );
+
+        for (html, error) in &[
+            ("<u>text</u>", "Unsupported tag: \"u\""),
+            ("<b>text</i>", "Unexpected end tag: \"i\""),
+            ("<b>text", "Tag is not closed: \"b\""),
+            ("<b text", "Tag is not closed: \"<b text\""),
+            ("<a>text</a>", "Link has no href attribute"),
+            (
+                "<a data-href=\"https://example.com\">text</a>",
+                "Link has no href attribute",
+            ),
+            ("<a href>text</a>", "Link has no href attribute"),
+        ] {
+            assert_eq!(Text::from_html(html).unwrap_err().to_string(), *error);
+        }
+    }
+
+    #[test]
+    fn test_get_href() {
+        assert_eq!(
+            get_href(" data-href=\"https://a.com\" HREF = 'https://b.com/?a=1&amp;b=2'"),
+            Some(String::from("https://b.com/?a=1&b=2"))
+        );
+        assert_eq!(
+            get_href(" title=\"href=x\" href=https://c.com target=_blank"),
+            Some(String::from("https://c.com"))
+        );
+        assert_eq!(get_href(" disabled href=\"\""), Some(String::new()));
+        assert_eq!(get_href(" href=\"https://d.com"), None);
+    }
+}
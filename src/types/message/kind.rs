@@ -4,7 +4,7 @@ use crate::types::{
 };
 
 /// Contains chat-specific data
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum MessageKind {
     /// Channel chat
     Channel {
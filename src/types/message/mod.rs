@@ -1,19 +1,23 @@
 use crate::types::{chat::Chat, message::raw::RawMessage, primitive::Integer, user::User};
-use serde::{de::Error, Deserialize, Deserializer};
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
 
 mod data;
 mod forward;
+mod html;
 mod kind;
 mod raw;
+mod render;
+mod split;
 #[cfg(test)]
 mod tests;
 mod text;
 
 pub(crate) use self::raw::RawMessageEntity;
-pub use self::{data::*, forward::*, kind::*, text::*};
+pub use self::{data::*, forward::*, html::*, kind::*, split::*, text::*};
 
 /// This object represents a message
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Message {
     /// Unique message identifier inside this chat
     pub id: Integer,
@@ -242,7 +246,147 @@ impl Message {
             message!(MessageData::Text(text), commands);
         }
 
-        Err(ParseError::NoData)
+        message!(MessageData::Unknown(Value::Object(raw.unknown)), None);
+    }
+
+    fn to_raw(&self) -> RawMessage {
+        let (chat, from, author_signature) = match self.kind {
+            MessageKind::Channel {
+                ref chat,
+                ref author_signature,
+            } => (Chat::Channel(chat.clone()), None, author_signature.clone()),
+            MessageKind::Group { ref chat, ref from } => (Chat::Group(chat.clone()), Some(from.clone()), None),
+            MessageKind::Private { ref chat, ref from } => (Chat::Private(chat.clone()), Some(from.clone()), None),
+            MessageKind::Supergroup { ref chat, ref from } => {
+                (Chat::Supergroup(chat.clone()), Some(from.clone()), None)
+            }
+        };
+
+        let mut raw = RawMessage {
+            message_id: self.id,
+            from,
+            date: self.date,
+            chat,
+            forward_from: None,
+            forward_from_chat: None,
+            forward_from_message_id: None,
+            forward_signature: None,
+            forward_date: None,
+            reply_to_message: self.reply_to.as_ref().map(|msg| Box::new(msg.to_raw())),
+            edit_date: self.edit_date,
+            media_group_id: self.media_group_id.clone(),
+            author_signature,
+            text: None,
+            entities: None,
+            caption_entities: None,
+            audio: None,
+            animation: None,
+            document: None,
+            game: None,
+            photo: None,
+            sticker: None,
+            video: None,
+            voice: None,
+            video_note: None,
+            caption: None,
+            contact: None,
+            location: None,
+            venue: None,
+            new_chat_members: None,
+            left_chat_member: None,
+            new_chat_title: None,
+            new_chat_photo: None,
+            delete_chat_photo: None,
+            group_chat_created: None,
+            supergroup_chat_created: None,
+            channel_chat_created: None,
+            migrate_to_chat_id: None,
+            migrate_from_chat_id: None,
+            pinned_message: None,
+            invoice: None,
+            successful_payment: None,
+            connected_website: None,
+            passport_data: None,
+            unknown: Default::default(),
+        };
+
+        if let Some(ref forward) = self.forward {
+            raw.forward_date = Some(forward.date);
+            match forward.from {
+                ForwardFrom::User(ref user) => raw.forward_from = Some(user.clone()),
+                ForwardFrom::Channel {
+                    ref chat,
+                    message_id,
+                    ref signature,
+                } => {
+                    raw.forward_from_chat = Some(Chat::Channel(chat.clone()));
+                    raw.forward_from_message_id = Some(message_id);
+                    raw.forward_signature = signature.clone();
+                }
+            }
+        }
+
+        macro_rules! caption {
+            ($caption:expr) => {
+                if let Some(ref text) = $caption {
+                    raw.caption = Some(text.data.clone());
+                    raw.caption_entities = text.to_raw_entities();
+                }
+            };
+        }
+
+        match self.data {
+            MessageData::Animation(ref data) => raw.animation = Some(data.clone()),
+            MessageData::Audio { ref caption, ref data } => {
+                caption!(caption);
+                raw.audio = Some(data.clone());
+            }
+            MessageData::ChannelChatCreated => raw.channel_chat_created = Some(true),
+            MessageData::ConnectedWebsite(ref data) => raw.connected_website = Some(data.clone()),
+            MessageData::Contact(ref data) => raw.contact = Some(data.clone()),
+            MessageData::DeleteChatPhoto => raw.delete_chat_photo = Some(true),
+            MessageData::Document { ref caption, ref data } => {
+                caption!(caption);
+                raw.document = Some(data.clone());
+            }
+            MessageData::Game(ref data) => raw.game = Some(data.clone()),
+            MessageData::GroupChatCreated => raw.group_chat_created = Some(true),
+            MessageData::Invoice(ref data) => raw.invoice = Some(data.clone()),
+            MessageData::LeftChatMember(ref data) => raw.left_chat_member = Some(data.clone()),
+            MessageData::Location(ref data) => raw.location = Some(data.clone()),
+            MessageData::MigrateFromChatId(data) => raw.migrate_from_chat_id = Some(data),
+            MessageData::MigrateToChatId(data) => raw.migrate_to_chat_id = Some(data),
+            MessageData::NewChatMembers(ref data) => raw.new_chat_members = Some(data.clone()),
+            MessageData::NewChatPhoto(ref data) => raw.new_chat_photo = Some(data.clone()),
+            MessageData::NewChatTitle(ref data) => raw.new_chat_title = Some(data.clone()),
+            MessageData::PassportData(ref data) => raw.passport_data = Some(data.clone()),
+            MessageData::PinnedMessage(ref data) => raw.pinned_message = Some(Box::new(data.to_raw())),
+            MessageData::Photo { ref caption, ref data } => {
+                caption!(caption);
+                raw.photo = Some(data.clone());
+            }
+            MessageData::Sticker(ref data) => raw.sticker = Some(data.clone()),
+            MessageData::SuccessfulPayment(ref data) => raw.successful_payment = Some(data.clone()),
+            MessageData::SupergroupChatCreated => raw.supergroup_chat_created = Some(true),
+            MessageData::Text(ref data) => {
+                raw.text = Some(data.data.clone());
+                raw.entities = data.to_raw_entities();
+            }
+            MessageData::Venue(ref data) => raw.venue = Some(data.clone()),
+            MessageData::Video { ref caption, ref data } => {
+                caption!(caption);
+                raw.video = Some(data.clone());
+            }
+            MessageData::VideoNote(ref data) => raw.video_note = Some(data.clone()),
+            MessageData::Voice { ref caption, ref data } => {
+                caption!(caption);
+                raw.voice = Some(data.clone());
+            }
+            MessageData::Unknown(Value::Object(ref fields)) => raw.unknown = fields.clone(),
+            MessageData::Unknown(_) => {}
+        }
+
+        raw
     }
 }
 
@@ -256,8 +400,17 @@ impl<'de> Deserialize<'de> for Message {
     }
 }
 
+impl Serialize for Message {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.to_raw().serialize(serializer)
+    }
+}
+
 /// Result of editMessage* requests
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[allow(clippy::large_enum_variant)]
 #[serde(untagged)]
 pub enum EditMessageResult {
@@ -275,8 +428,6 @@ enum ParseError {
     BadText(#[cause] ParseTextError),
     #[fail(display = "\"{}\" field is missing", _0)]
     MissingField(&'static str),
-    #[fail(display = "Can not get message data")]
-    NoData,
 }
 
 fn get_commands(text: &Text) -> Option<Vec<BotCommand>> {
@@ -17,92 +17,233 @@ use crate::types::{
     video_note::VideoNote,
     voice::Voice,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize)]
 pub(super) struct RawMessage {
     pub message_id: Integer,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub from: Option<User>,
     pub date: Integer,
     pub chat: Chat,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub forward_from: Option<User>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub forward_from_chat: Option<Chat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub forward_from_message_id: Option<Integer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub forward_signature: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub forward_date: Option<Integer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub reply_to_message: Option<Box<RawMessage>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub edit_date: Option<Integer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub media_group_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub author_signature: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub entities: Option<Vec<RawMessageEntity>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub caption_entities: Option<Vec<RawMessageEntity>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub audio: Option<Audio>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub animation: Option<Animation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub document: Option<Document>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub game: Option<Game>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub photo: Option<Vec<PhotoSize>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub sticker: Option<Sticker>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub video: Option<Video>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub voice: Option<Voice>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub video_note: Option<VideoNote>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub caption: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub contact: Option<Contact>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub location: Option<Location>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub venue: Option<Venue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub new_chat_members: Option<Vec<User>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub left_chat_member: Option<User>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub new_chat_title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub new_chat_photo: Option<Vec<PhotoSize>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub delete_chat_photo: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub group_chat_created: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub supergroup_chat_created: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub channel_chat_created: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub migrate_to_chat_id: Option<Integer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub migrate_from_chat_id: Option<Integer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub pinned_message: Option<Box<RawMessage>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub invoice: Option<Invoice>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub successful_payment: Option<SuccessfulPayment>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub connected_website: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub passport_data: Option<PassportData>,
+    #[serde(flatten)]
+    pub unknown: Map<String, Value>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+deserialize_with_unknown!(RawMessage {
+    required: [message_id, date, chat],
+    optional: [
+        from,
+        forward_from,
+        forward_from_chat,
+        forward_from_message_id,
+        forward_signature,
+        forward_date,
+        reply_to_message,
+        edit_date,
+        media_group_id,
+        author_signature,
+        text,
+        entities,
+        caption_entities,
+        audio,
+        animation,
+        document,
+        game,
+        photo,
+        sticker,
+        video,
+        voice,
+        video_note,
+        caption,
+        contact,
+        location,
+        venue,
+        new_chat_members,
+        left_chat_member,
+        new_chat_title,
+        new_chat_photo,
+        delete_chat_photo,
+        group_chat_created,
+        supergroup_chat_created,
+        channel_chat_created,
+        migrate_to_chat_id,
+        migrate_from_chat_id,
+        pinned_message,
+        invoice,
+        successful_payment,
+        connected_website,
+        passport_data
+    ]
+});
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub(crate) struct RawMessageEntity {
     #[serde(rename = "type")]
     pub kind: RawMessageEntityKind,
     pub offset: Integer,
     pub length: Integer,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub user: Option<User>,
+    /// Set by TextParseMode::Lenient
+    #[serde(default, rename = "tgbot_lenient", skip_serializing)]
+    pub lenient: bool,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug)]
 pub(crate) enum RawMessageEntityKind {
-    #[serde(rename = "bold")]
     Bold,
-    #[serde(rename = "bot_command")]
     BotCommand,
-    #[serde(rename = "cashtag")]
     Cashtag,
-    #[serde(rename = "code")]
     Code,
-    #[serde(rename = "email")]
     Email,
-    #[serde(rename = "hashtag")]
     Hashtag,
-    #[serde(rename = "italic")]
     Italic,
-    #[serde(rename = "mention")]
     Mention,
-    #[serde(rename = "phone_number")]
     PhoneNumber,
-    #[serde(rename = "pre")]
     Pre,
-    #[serde(rename = "text_link")]
     TextLink,
-    #[serde(rename = "text_mention")]
     TextMention,
-    #[serde(rename = "url")]
     Url,
+    Unknown(String),
+}
+
+impl RawMessageEntityKind {
+    fn as_str(&self) -> &str {
+        use self::RawMessageEntityKind::*;
+        match self {
+            Bold => "bold",
+            BotCommand => "bot_command",
+            Cashtag => "cashtag",
+            Code => "code",
+            Email => "email",
+            Hashtag => "hashtag",
+            Italic => "italic",
+            Mention => "mention",
+            PhoneNumber => "phone_number",
+            Pre => "pre",
+            TextLink => "text_link",
+            TextMention => "text_mention",
+            Url => "url",
+            Unknown(kind) => kind,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for RawMessageEntityKind {
+    fn deserialize<D>(deserializer: D) -> Result<RawMessageEntityKind, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use self::RawMessageEntityKind::*;
+        let kind = String::deserialize(deserializer)?;
+        Ok(match kind.as_str() {
+            "bold" => Bold,
+            "bot_command" => BotCommand,
+            "cashtag" => Cashtag,
+            "code" => Code,
+            "email" => Email,
+            "hashtag" => Hashtag,
+            "italic" => Italic,
+            "mention" => Mention,
+            "phone_number" => PhoneNumber,
+            "pre" => Pre,
+            "text_link" => TextLink,
+            "text_mention" => TextMention,
+            "url" => Url,
+            _ => Unknown(kind),
+        })
+    }
+}
+
+impl Serialize for RawMessageEntityKind {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
 }
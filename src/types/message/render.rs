@@ -0,0 +1,244 @@
+use crate::types::message::text::{Text, TextEntity, TextEntityData};
+
+enum Style {
+    Bold,
+    Italic,
+    Code,
+    Pre,
+    Link(String),
+}
+
+struct Span {
+    offset: usize,
+    end: usize,
+    style: Style,
+}
+
+impl Text {
+    /// Returns text with entities as HTML
+    ///
+    /// Overlapping entities are split, so tags are always properly nested
+    /// Entities highlighted by clients automatically (mentions, URLs, hashtags, etc) are rendered as plain text
+    pub fn to_html(&self) -> String {
+        let units: Vec<u16> = self.data.encode_utf16().collect();
+        let spans = self.get_spans(units.len());
+        let mut boundaries = vec![0, units.len()];
+        for span in &spans {
+            boundaries.push(span.offset);
+            boundaries.push(span.end);
+        }
+        boundaries.sort_unstable();
+        boundaries.dedup();
+
+        let mut result = String::new();
+        let mut stack: Vec<usize> = Vec::new();
+        for window in boundaries.windows(2) {
+            let (start, end) = (window[0], window[1]);
+            let is_active = |idx: &usize| spans[*idx].offset <= start && spans[*idx].end >= end;
+            let keep = stack.iter().position(|idx| !is_active(idx)).unwrap_or(stack.len());
+            while stack.len() > keep {
+                if let Some(idx) = stack.pop() {
+                    close_html_tag(&mut result, &spans[idx].style);
+                }
+            }
+            let mut opened: Vec<usize> = (0..spans.len())
+                .filter(|idx| is_active(idx) && !stack.contains(idx))
+                .collect();
+            // Longer spans are opened first, so they are closed and reopened less often
+            opened.sort_by(|a, b| spans[*b].end.cmp(&spans[*a].end).then(a.cmp(b)));
+            for idx in opened {
+                open_html_tag(&mut result, &spans[idx].style);
+                stack.push(idx);
+            }
+            result += &escape_html(&String::from_utf16_lossy(&units[start..end]));
+        }
+        while let Some(idx) = stack.pop() {
+            close_html_tag(&mut result, &spans[idx].style);
+        }
+        result
+    }
+
+    /// Returns text with entities as Markdown
+    ///
+    /// Markdown does not support nested entities, so an entity overlapping a previous one is rendered as plain text
+    /// An entity is also rendered as plain text when its content can not be represented in Markdown
+    pub fn to_markdown(&self) -> String {
+        let units: Vec<u16> = self.data.encode_utf16().collect();
+        let mut spans = self.get_spans(units.len());
+        spans.sort_by_key(|span| span.offset);
+
+        let mut result = String::new();
+        let mut pos = 0;
+        for span in spans {
+            if span.offset < pos {
+                continue;
+            }
+            result += &escape_markdown(&String::from_utf16_lossy(&units[pos..span.offset]));
+            let content = String::from_utf16_lossy(&units[span.offset..span.end]);
+            let formatted = match span.style {
+                Style::Bold if !content.contains('*') => Some(format!("*{}*", content)),
+                Style::Italic if !content.contains('_') => Some(format!("_{}_", content)),
+                Style::Code if !content.contains('`') => Some(format!("`{}`", content)),
+                Style::Pre if !content.contains('`') => Some(format!("```{}```", content)),
+                Style::Link(ref url) if !content.contains(']') && !url.contains(')') => {
+                    Some(format!("[{}]({})", content, url))
+                }
+                _ => None,
+            };
+            result += &formatted.unwrap_or_else(|| escape_markdown(&content));
+            pos = span.end;
+        }
+        result += &escape_markdown(&String::from_utf16_lossy(&units[pos..]));
+        result
+    }
+
+    fn get_spans(&self, len: usize) -> Vec<Span> {
+        let mut spans = Vec::new();
+        if let Some(ref entities) = self.entities {
+            for entity in entities {
+                let (data, style) = match entity {
+                    TextEntity::Bold(data) => (data, Style::Bold),
+                    TextEntity::Italic(data) => (data, Style::Italic),
+                    TextEntity::Code(data) => (data, Style::Code),
+                    TextEntity::Pre(data) => (data, Style::Pre),
+                    TextEntity::TextLink(link) => (&link.data, Style::Link(link.url.clone())),
+                    TextEntity::TextMention(mention) => {
+                        (&mention.data, Style::Link(format!("tg://user?id={}", mention.user.id)))
+                    }
+                    _ => continue,
+                };
+                let TextEntityData { offset, length, .. } = *data;
+                let end = offset.saturating_add(length).min(len);
+                if offset < end {
+                    spans.push(Span { offset, end, style });
+                }
+            }
+        }
+        spans
+    }
+}
+
+fn open_html_tag(result: &mut String, style: &Style) {
+    match style {
+        Style::Bold => result.push_str("<b>"),
+        Style::Italic => result.push_str("<i>"),
+        Style::Code => result.push_str("<code>"),
+        Style::Pre => result.push_str("<pre>"),
+        Style::Link(url) => {
+            result.push_str("<a href=\"");
+            result.push_str(&escape_html(url));
+            result.push_str("\">");
+        }
+    }
+}
+
+fn close_html_tag(result: &mut String, style: &Style) {
+    result.push_str(match style {
+        Style::Bold => "</b>",
+        Style::Italic => "</i>",
+        Style::Code => "</code>",
+        Style::Pre => "</pre>",
+        Style::Link(_) => "</a>",
+    })
+}
+
+/// Escapes a string to be used in HTML text or attribute
+pub(crate) fn escape_html(data: &str) -> String {
+    let mut result = String::with_capacity(data.len());
+    for c in data.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// Escapes a string to be used in Markdown outside of entities
+pub(crate) fn escape_markdown(data: &str) -> String {
+    let mut result = String::with_capacity(data.len());
+    for c in data.chars() {
+        if let '_' | '*' | '`' | '[' = c {
+            result.push('\\');
+        }
+        result.push(c);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::Message;
+    use serde_json::json;
+
+    fn parse_text(text: &str, entities: serde_json::Value) -> Message {
+        serde_json::from_value(json!({
+            "message_id": 1, "date": 0,
+            "from": {"id": 1, "first_name": "firstname", "is_bot": false},
+            "chat": {"id": 1, "type": "private", "first_name": "firstname"},
+            "text": text,
+            "entities": entities
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_to_html() {
+        let msg = parse_text(
+            "😀 bold italic <tag> & link @mention",
+            json!([
+                {"type": "bold", "offset": 3, "length": 11},
+                {"type": "italic", "offset": 8, "length": 12},
+                {"type": "text_link", "offset": 23, "length": 4, "url": "https://example.com/?a=1&b=\"2\""},
+                {"type": "mention", "offset": 28, "length": 8}
+            ]),
+        );
+        assert_eq!(
+            msg.get_text().unwrap().to_html(),
+            "😀 <b>bold <i>italic</i></b><i> &lt;tag&gt;</i> &amp; \
+             <a href=\"https://example.com/?a=1&amp;b=&quot;2&quot;\">link</a> @mention"
+        );
+
+        let msg = parse_text(
+            "user code",
+            json!([
+                {"type": "text_mention", "offset": 0, "length": 4, "user": {"id": 2, "first_name": "user", "is_bot": false}},
+                {"type": "pre", "offset": 5, "length": 4},
+                {"type": "code", "offset": 5, "length": 4}
+            ]),
+        );
+        assert_eq!(
+            msg.get_text().unwrap().to_html(),
+            "<a href=\"tg://user?id=2\">user</a> <pre><code>code</code></pre>"
+        );
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let msg = parse_text(
+            "😀 bold italic *_[` link code",
+            json!([
+                {"type": "bold", "offset": 3, "length": 11},
+                {"type": "italic", "offset": 8, "length": 6},
+                {"type": "text_link", "offset": 20, "length": 4, "url": "https://example.com"},
+                {"type": "code", "offset": 25, "length": 4}
+            ]),
+        );
+        assert_eq!(
+            msg.get_text().unwrap().to_markdown(),
+            "😀 *bold italic* \\*\\_\\[\\` [link](https://example.com) `code`"
+        );
+
+        let msg = parse_text(
+            "a*b c_d",
+            json!([
+                {"type": "bold", "offset": 0, "length": 3},
+                {"type": "italic", "offset": 4, "length": 3}
+            ]),
+        );
+        assert_eq!(msg.get_text().unwrap().to_markdown(), "a\\*b c\\_d");
+    }
+}
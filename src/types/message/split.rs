@@ -0,0 +1,147 @@
+use crate::types::message::{
+    html::ParseHtmlError,
+    text::{Text, TextEntityData},
+};
+
+/// Maximum length of a message text in UTF-16 code units
+pub const MAX_TEXT_LENGTH: usize = 4096;
+
+/// Maximum length of a media caption in UTF-16 code units
+pub const MAX_CAPTION_LENGTH: usize = 1024;
+
+const SEPARATORS: [&[u16]; 3] = [&[0x0a, 0x0a], &[0x0a], &[0x20]];
+
+impl Text {
+    /// Splits text into parts not longer than limit
+    ///
+    /// Length is measured in UTF-16 code units, the same way Telegram does
+    /// Text is split at paragraph, line or word boundaries when possible, the separator at a split point is dropped
+    /// Entities crossing a split point are split between parts
+    pub fn split(&self, limit: usize) -> Vec<Text> {
+        // A surrogate pair can not be split, so a part must fit at least two units
+        let limit = limit.max(2);
+        let units: Vec<u16> = self.data.encode_utf16().collect();
+        let mut parts = Vec::new();
+        let mut start = 0;
+        while units.len() - start > limit {
+            let mut end = start + limit;
+            if (0xdc00..=0xdfff).contains(&units[end]) {
+                end -= 1;
+            }
+            let (part_end, next_start) = find_split(&units, start, end);
+            parts.push(self.slice(&units, start, part_end));
+            start = next_start;
+        }
+        if start < units.len() || parts.is_empty() {
+            parts.push(self.slice(&units, start, units.len()));
+        }
+        parts
+    }
+
+    fn slice(&self, units: &[u16], start: usize, end: usize) -> Text {
+        let entities = self.entities.as_ref().and_then(|entities| {
+            let entities: Vec<_> = entities
+                .iter()
+                .filter_map(|entity| {
+                    let data = entity.get_data();
+                    let offset = data.offset.max(start);
+                    let length = data.offset.saturating_add(data.length).min(end).checked_sub(offset)?;
+                    if length == 0 {
+                        return None;
+                    }
+                    Some(entity.with_data(TextEntityData {
+                        offset: offset - start,
+                        length,
+                        data: String::from_utf16_lossy(&units[offset..offset + length]),
+                    }))
+                })
+                .collect();
+            if entities.is_empty() {
+                None
+            } else {
+                Some(entities)
+            }
+        });
+        Text {
+            data: String::from_utf16_lossy(&units[start..end]),
+            entities,
+            warnings: Vec::new(),
+        }
+    }
+}
+
+/// Returns end of the current part and start of the next one
+fn find_split(units: &[u16], start: usize, end: usize) -> (usize, usize) {
+    for separator in SEPARATORS.iter() {
+        if let Some(idx) = (start + 1..=end).rev().find(|idx| units[*idx..].starts_with(separator)) {
+            return (idx, idx + separator.len());
+        }
+    }
+    (end, end)
+}
+
+/// Splits plain text into parts not longer than limit
+///
+/// See Text::split() for details
+pub fn split_text(data: &str, limit: usize) -> Vec<String> {
+    Text::from(data)
+        .split(limit)
+        .into_iter()
+        .map(|part| part.data)
+        .collect()
+}
+
+/// Splits text formatted with HTML into parts not longer than limit
+///
+/// Limit applies to the text without tags, tags are closed and reopened at split points
+/// See Text::split() for details
+pub fn split_html(html: &str, limit: usize) -> Result<Vec<String>, ParseHtmlError> {
+    Ok(Text::from_html(html)?.split(limit).iter().map(Text::to_html).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::message::text::TextEntity;
+
+    #[test]
+    fn test_split_text() {
+        assert_eq!(split_text("", 10), vec![""]);
+        assert_eq!(split_text("short", 10), vec!["short"]);
+        assert_eq!(
+            split_text("first line\nsecond\n\nparagraph", 20),
+            vec!["first line\nsecond", "paragraph"]
+        );
+        assert_eq!(split_text("one two three four", 9), vec!["one two", "three", "four"]);
+        assert_eq!(split_text("abcdefghij", 4), vec!["abcd", "efgh", "ij"]);
+        // Surrogate pairs are never split
+        assert_eq!(split_text("a😀😀", 2), vec!["a", "😀", "😀"]);
+        for part in split_text(&"word ".repeat(2000), MAX_TEXT_LENGTH) {
+            assert!(part.encode_utf16().count() <= MAX_TEXT_LENGTH);
+        }
+    }
+
+    #[test]
+    fn test_split_entities() {
+        let text = Text::from_html("<b>bold text</b> <a href=\"https://example.com\">link</a>").unwrap();
+        let parts = text.split(6);
+        assert_eq!(parts.len(), 3);
+        assert_eq!(parts[0].data, "bold");
+        assert_eq!(
+            parts[0].entities,
+            Some(vec![TextEntity::Bold(TextEntityData {
+                offset: 0,
+                length: 4,
+                data: String::from("bold")
+            })])
+        );
+        assert_eq!(parts[1].to_html(), "<b>text</b>");
+        assert_eq!(parts[2].to_html(), "<a href=\"https://example.com\">link</a>");
+
+        assert_eq!(
+            split_html("<b>bold &amp; <i>italic</i> text</b>", 12).unwrap(),
+            vec!["<b>bold &amp;</b>", "<b><i>italic</i> text</b>"]
+        );
+        assert!(split_html("<b>unclosed", 12).is_err());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::types::{chat::ChannelChat, message::*, user::User};
+use crate::types::{chat::ChannelChat, message::*, tests::assert_round_trip, user::User};
 
 use serde_json::json;
 
@@ -15,6 +15,7 @@ fn test_deserialize_message_channel() {
         "text": "test"
     });
     let msg: Message = serde_json::from_value(input).unwrap();
+    assert_round_trip(&msg);
     assert_eq!(msg.id, 1);
     assert_eq!(msg.date, 0);
     assert_eq!(msg.get_chat_id(), 1);
@@ -31,7 +32,7 @@ fn test_deserialize_message_channel() {
     } else {
         panic!("Unexpected message kind: {:?}", msg.kind);
     }
-    if let MessageData::Text(Text { data, entities }) = msg.data {
+    if let MessageData::Text(Text { data, entities, .. }) = msg.data {
         assert_eq!(data, "test");
         assert!(entities.is_none());
     } else {
@@ -59,6 +60,7 @@ fn test_deserialize_message_group() {
         "edit_date": 1
     });
     let msg: Message = serde_json::from_value(input).unwrap();
+    assert_round_trip(&msg);
     assert_eq!(msg.id, 1);
     assert_eq!(msg.date, 0);
     assert_eq!(msg.get_chat_id(), 1);
@@ -75,7 +77,7 @@ fn test_deserialize_message_group() {
     } else {
         panic!("Unexpected message kind: {:?}", msg.kind);
     }
-    if let MessageData::Text(Text { data, entities }) = msg.data {
+    if let MessageData::Text(Text { data, entities, .. }) = msg.data {
         assert_eq!(data, "test");
         assert!(entities.is_none());
     } else {
@@ -108,6 +110,7 @@ fn test_deserialize_message_private() {
         "text": "test"
     });
     let msg: Message = serde_json::from_value(input).unwrap();
+    assert_round_trip(&msg);
     assert_eq!(msg.id, 1);
     assert_eq!(msg.date, 0);
     assert_eq!(msg.get_chat_id(), 1);
@@ -122,7 +125,7 @@ fn test_deserialize_message_private() {
     } else {
         panic!("Unexpected message kind: {:?}", msg.kind);
     }
-    if let MessageData::Text(Text { data, entities }) = msg.data {
+    if let MessageData::Text(Text { data, entities, .. }) = msg.data {
         assert_eq!(data, "test");
         assert!(entities.is_none());
     } else {
@@ -156,6 +159,7 @@ fn test_deserialize_message_supergroup() {
         "text": "test"
     });
     let msg: Message = serde_json::from_value(input).unwrap();
+    assert_round_trip(&msg);
     assert_eq!(msg.id, 1);
     assert_eq!(msg.date, 0);
     assert_eq!(msg.get_chat_id(), 1);
@@ -171,7 +175,7 @@ fn test_deserialize_message_supergroup() {
     } else {
         panic!("Unexpected message kind: {:?}", msg.kind);
     }
-    if let MessageData::Text(Text { data, entities }) = msg.data {
+    if let MessageData::Text(Text { data, entities, .. }) = msg.data {
         assert_eq!(data, "test");
         assert!(entities.is_none());
     } else {
@@ -198,6 +202,7 @@ fn test_deserialize_message_forward() {
         "forward_date": 0
     });
     let msg: Message = serde_json::from_value(input).unwrap();
+    assert_round_trip(&msg);
     if let Some(Forward {
         date,
         from: ForwardFrom::User(user),
@@ -222,6 +227,7 @@ fn test_deserialize_message_forward() {
         "forward_date": 0
     });
     let msg: Message = serde_json::from_value(input).unwrap();
+    assert_round_trip(&msg);
     if let Some(Forward {
         date,
         from: ForwardFrom::Channel {
@@ -270,6 +276,7 @@ fn test_deserialize_message_reply() {
         }
     });
     let msg: Message = serde_json::from_value(input).unwrap();
+    assert_round_trip(&msg);
     if let Some(msg) = msg.reply_to {
         assert_eq!(msg.id, 1);
     } else {
@@ -294,6 +301,7 @@ fn test_deserialize_message_data() {
         }
     });
     let msg: Message = serde_json::from_value(input).unwrap();
+    assert_round_trip(&msg);
     if let MessageData::Animation(animation) = msg.data {
         assert_eq!(animation.file_id, String::from("fileid"));
         assert_eq!(animation.width, 200);
@@ -337,6 +345,7 @@ fn test_deserialize_message_entities() {
         ]
     });
     let msg: Message = serde_json::from_value(input).unwrap();
+    assert_round_trip(&msg);
     assert_eq!(msg.commands.unwrap().len(), 1);
     if let MessageData::Text(text) = msg.data {
         let entities = text.entities.unwrap();
@@ -536,3 +545,131 @@ fn test_deserialize_message_bad_entities() {
         assert_eq!(err.to_string(), error.to_string());
     }
 }
+
+#[test]
+fn test_serialize_message_data() {
+    let chat = json!({"id": 1, "type": "private", "first_name": "firstname"});
+    let from = json!({"id": 1, "first_name": "firstname", "is_bot": false});
+    for data in vec![
+        json!({"photo": [{"file_id": "fileid", "width": 1, "height": 1}], "caption": "/start photo", "caption_entities": [
+            {"type": "bot_command", "offset": 0, "length": 6}
+        ]}),
+        json!({"game": {"title": "title", "description": "description", "photo": [], "text": "text", "text_entities": [
+            {"type": "text_link", "offset": 0, "length": 4, "url": "https://example.com"}
+        ]}}),
+        json!({"passport_data": {
+            "data": [
+                {"type": "email", "email": "user@example.com", "hash": "hash"},
+                {"type": "passport", "data": "data", "hash": "hash",
+                 "front_side": {"file_id": "fileid", "file_size": 1, "file_date": 0},
+                 "selfie": {"file_id": "fileid", "file_size": 1, "file_date": 0}}
+            ],
+            "credentials": {"data": "data", "hash": "hash", "secret": "secret"}
+        }}),
+        json!({"pinned_message": {"message_id": 2, "date": 0, "chat": chat, "from": from, "text": "pinned"}}),
+        json!({"new_chat_members": [from]}),
+        json!({"delete_chat_photo": true}),
+        json!({"contact": {"phone_number": "+79001231212", "first_name": "firstname"}}),
+        json!({"location": {"longitude": 1.5, "latitude": 2.5}}),
+        json!({"text": "@username", "entities": [
+            {"type": "text_mention", "offset": 0, "length": 9, "user": from}
+        ], "forward_date": 0, "forward_from_message_id": 1, "forward_signature": "signature", "forward_from_chat": {
+            "id": 2, "type": "channel", "title": "channeltitle"
+        }}),
+    ] {
+        let mut input = json!({"message_id": 1, "date": 0, "chat": chat, "from": from});
+        for (key, value) in data.as_object().unwrap() {
+            input[key] = value.clone();
+        }
+        let msg: Message = serde_json::from_value(input.clone()).unwrap();
+        assert_round_trip(&msg);
+        assert_eq!(serde_json::to_value(&msg).unwrap(), input);
+    }
+}
+
+#[test]
+fn test_deserialize_message_unknown() {
+    let input = json!({
+        "message_id": 1, "date": 0,
+        "from": {"id": 1, "first_name": "firstname", "is_bot": false},
+        "chat": {"id": 1, "type": "private", "first_name": "firstname"},
+        "poll": {"id": "poll-id"}
+    });
+    let msg: Message = serde_json::from_value(input.clone()).unwrap();
+    assert_eq!(msg.data, MessageData::Unknown(json!({"poll": {"id": "poll-id"}})));
+    assert_eq!(serde_json::to_value(&msg).unwrap(), input);
+
+    let input = json!({
+        "message_id": 1, "date": 0,
+        "from": {"id": 1, "first_name": "firstname", "is_bot": false},
+        "chat": {"id": 1, "type": "private", "first_name": "firstname"},
+        "text": "spoiler",
+        "entities": [{"type": "spoiler", "offset": 0, "length": 7}]
+    });
+    let msg: Message = serde_json::from_value(input.clone()).unwrap();
+    assert_eq!(
+        msg.get_text().unwrap().entities,
+        Some(vec![TextEntity::Unknown(UnknownTextEntity {
+            kind: String::from("spoiler"),
+            data: TextEntityData {
+                offset: 0,
+                length: 7,
+                data: String::from("spoiler")
+            }
+        })])
+    );
+    assert_eq!(serde_json::to_value(&msg).unwrap(), input);
+}
+
+#[test]
+fn test_deserialize_message_bad_entities_lenient() {
+    let input = json!({
+        "message_id": 1, "date": 0,
+        "from": {"id": 1, "first_name": "firstname", "is_bot": false},
+        "chat": {"id": 1, "type": "supergroup", "title": "supergrouptitle"},
+        "text": "bad 😀",
+        "entities": [
+            {"type": "bold", "offset": -1, "length": 1},
+            {"type": "italic", "offset": 0, "length": 100},
+            {"type": "code", "offset": 4, "length": 1},
+            {"type": "text_link", "offset": 0, "length": 3},
+            {"type": "bold", "offset": 1, "length": Integer::MAX}
+        ]
+    });
+    assert!(serde_json::from_value::<Message>(input.clone()).is_err());
+    let mut value = input.clone();
+    assert!(TextParseMode::Strict.prepare(&mut value).is_empty());
+    assert_eq!(value, input);
+    let warnings = TextParseMode::Lenient.prepare(&mut value);
+    assert_eq!(
+        warnings,
+        vec![
+            "Entity is dropped: Offset \"-1\" is out of text bounds",
+            "Length \"100\" is clamped to \"6\"",
+            "Entity is dropped: Can not get UTF-16 text data: invalid utf-16: lone surrogate found",
+            "Entity is dropped: URL is required for text_link entity",
+            "Length \"9223372036854775807\" is clamped to \"5\"",
+        ]
+    );
+    let msg: Message = serde_json::from_value(value).unwrap();
+    let text = msg.get_text().unwrap();
+    assert_eq!(
+        text.entities,
+        Some(vec![
+            TextEntity::Italic(TextEntityData {
+                offset: 0,
+                length: 6,
+                data: String::from("bad 😀")
+            }),
+            TextEntity::Bold(TextEntityData {
+                offset: 1,
+                length: 5,
+                data: String::from("ad 😀")
+            })
+        ])
+    );
+    assert_eq!(text.get_warnings(), warnings.as_slice());
+    let mut input = input;
+    input["entities"] = json!([{"type": "bold", "offset": 1, "length": Integer::MAX}]);
+    assert!(serde_json::from_value::<Message>(input).is_err());
+}
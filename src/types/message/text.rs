@@ -3,8 +3,94 @@ use crate::types::{
     primitive::Integer,
     user::User,
 };
+use serde_json::Value;
 use std::string::FromUtf16Error;
 
+/// Field which marks an entity to be parsed leniently
+const LENIENT_FIELD: &str = "tgbot_lenient";
+
+/// Fields of objects which contain a text and its entities
+const TEXT_FIELDS: [(&str, &str); 3] = [
+    ("text", "entities"),
+    ("caption", "caption_entities"),
+    ("text", "text_entities"),
+];
+
+/// How to handle malformed text entities
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TextParseMode {
+    /// Fail the whole text (and the object containing it) on a malformed entity
+    Strict,
+    /// Drop or clamp malformed entities before parsing
+    Lenient,
+}
+
+impl TextParseMode {
+    /// Prepares raw JSON of an update or a message for parsing
+    ///
+    /// In lenient mode text entities of all nested objects are marked,
+    /// so malformed ones are dropped or clamped when parsing and described by Text::get_warnings()
+    /// Returns a warning for each entity which is dropped or clamped
+    pub fn prepare(self, value: &mut Value) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if self == TextParseMode::Lenient {
+            mark_entities(value, &mut warnings);
+        }
+        warnings
+    }
+}
+
+fn mark_entities(value: &mut Value, warnings: &mut Vec<String>) {
+    match value {
+        Value::Object(fields) => {
+            for (text_field, entities_field) in TEXT_FIELDS.iter() {
+                let text: Vec<u16> = match fields.get(*text_field) {
+                    Some(Value::String(text)) => text.encode_utf16().collect(),
+                    _ => continue,
+                };
+                if let Some(Value::Array(entities)) = fields.get_mut(*entities_field) {
+                    for entity in entities.iter_mut().filter(|entity| entity.is_object()) {
+                        entity[LENIENT_FIELD] = Value::Bool(true);
+                        if let Ok(raw) = serde_json::from_value(entity.clone()) {
+                            let mut text_warnings = Vec::new();
+                            parse_entity_lenient(&text, raw, &mut text_warnings);
+                            warnings.extend(text_warnings);
+                        }
+                    }
+                }
+            }
+            for value in fields.values_mut() {
+                mark_entities(value, warnings);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                mark_entities(item, warnings);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Parses an entity marked by TextParseMode::Lenient
+///
+/// A length exceeding the text is clamped, any other malformed entity is dropped
+fn parse_entity_lenient(text: &[u16], mut entity: RawMessageEntity, warnings: &mut Vec<String>) -> Option<TextEntity> {
+    let len = text.len() as i64;
+    let (offset, length) = (entity.offset, entity.length);
+    if offset >= 0 && offset <= len && length > len - offset {
+        warnings.push(format!("Length \"{}\" is clamped to \"{}\"", length, len - offset));
+        entity.length = len - offset;
+    }
+    match parse_entity(text, entity) {
+        Ok(entity) => Some(entity),
+        Err(err) => {
+            warnings.push(format!("Entity is dropped: {}", err));
+            None
+        }
+    }
+}
+
 /// Text with entities
 #[derive(Clone, Debug)]
 pub struct Text {
@@ -12,6 +98,29 @@ pub struct Text {
     pub data: String,
     /// Text entities
     pub entities: Option<Vec<TextEntity>>,
+    pub(crate) warnings: Vec<String>,
+}
+
+impl PartialEq for Text {
+    fn eq(&self, other: &Text) -> bool {
+        self.data == other.data && self.entities == other.entities
+    }
+}
+
+impl From<String> for Text {
+    fn from(data: String) -> Text {
+        Text {
+            data,
+            entities: None,
+            warnings: Vec::new(),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for Text {
+    fn from(data: &'a str) -> Text {
+        Text::from(String::from(data))
+    }
 }
 
 impl Text {
@@ -20,36 +129,64 @@ impl Text {
         entities: Option<Vec<RawMessageEntity>>,
     ) -> Result<Text, ParseTextError> {
         let data = data.into();
+        let mut warnings = Vec::new();
         let entities = if let Some(entities) = entities {
             if entities.is_empty() {
                 None
             } else {
                 let text: Vec<u16> = data.encode_utf16().collect();
-                let len = text.len() as i64;
                 let mut result = Vec::new();
                 for entity in entities {
-                    let (offset, length) = (entity.offset, entity.length);
-                    if offset > len || offset < 0 {
-                        return Err(ParseTextError::BadOffset(offset));
-                    }
-                    let limit = offset + length;
-                    if limit > len || limit < 0 {
-                        return Err(ParseTextError::BadLength(length));
+                    if entity.lenient {
+                        result.extend(parse_entity_lenient(&text, entity, &mut warnings));
+                    } else {
+                        result.push(parse_entity(&text, entity)?);
                     }
-                    let (offset, length) = (offset as usize, length as usize);
-                    let data =
-                        String::from_utf16(&text.iter().skip(offset).take(length).cloned().collect::<Vec<u16>>())
-                            .map_err(ParseTextError::FromUtf16)?;
-                    let data = TextEntityData { offset, length, data };
-                    result.push(TextEntity::from_raw(entity, data)?)
                 }
-                Some(result)
+                if result.is_empty() {
+                    None
+                } else {
+                    Some(result)
+                }
             }
         } else {
             None
         };
-        Ok(Text { data, entities })
+        Ok(Text {
+            data,
+            entities,
+            warnings,
+        })
+    }
+
+    /// Returns descriptions of malformed entities which are dropped or clamped
+    ///
+    /// Entities are checked leniently only in JSON prepared by TextParseMode::Lenient,
+    /// which is the default for update streams
+    pub fn get_warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    /// Returns entities in the wire format
+    pub(crate) fn to_raw_entities(&self) -> Option<Vec<RawMessageEntity>> {
+        self.entities
+            .as_ref()
+            .map(|entities| entities.iter().map(TextEntity::to_raw).collect())
+    }
+}
+
+fn parse_entity(text: &[u16], entity: RawMessageEntity) -> Result<TextEntity, ParseTextError> {
+    let len = text.len() as i64;
+    let (offset, length) = (entity.offset, entity.length);
+    if offset > len || offset < 0 {
+        return Err(ParseTextError::BadOffset(offset));
     }
+    if length > len - offset || length < 0 {
+        return Err(ParseTextError::BadLength(length));
+    }
+    let (offset, length) = (offset as usize, length as usize);
+    let data = String::from_utf16(&text[offset..offset + length]).map_err(ParseTextError::FromUtf16)?;
+    TextEntity::from_raw(entity, TextEntityData { offset, length, data })
 }
 
 /// Respresents an entity in a text
@@ -81,6 +218,8 @@ pub enum TextEntity {
     TextMention(TextMention),
     /// URL
     Url(TextEntityData),
+    /// Entity of unknown type
+    Unknown(UnknownTextEntity),
 }
 
 impl TextEntity {
@@ -114,8 +253,98 @@ impl TextEntity {
                 None => return Err(ParseTextError::NoUser),
             },
             RawMessageEntityKind::Url => TextEntity::Url(data),
+            RawMessageEntityKind::Unknown(kind) => TextEntity::Unknown(UnknownTextEntity { kind, data }),
         })
     }
+
+    fn to_raw(&self) -> RawMessageEntity {
+        let (kind, data, url, user) = match self {
+            TextEntity::Bold(data) => (RawMessageEntityKind::Bold, data, None, None),
+            TextEntity::BotCommand(command) => (RawMessageEntityKind::BotCommand, &command.data, None, None),
+            TextEntity::Cashtag(data) => (RawMessageEntityKind::Cashtag, data, None, None),
+            TextEntity::Code(data) => (RawMessageEntityKind::Code, data, None, None),
+            TextEntity::Email(data) => (RawMessageEntityKind::Email, data, None, None),
+            TextEntity::Hashtag(data) => (RawMessageEntityKind::Hashtag, data, None, None),
+            TextEntity::Italic(data) => (RawMessageEntityKind::Italic, data, None, None),
+            TextEntity::Mention(data) => (RawMessageEntityKind::Mention, data, None, None),
+            TextEntity::PhoneNumber(data) => (RawMessageEntityKind::PhoneNumber, data, None, None),
+            TextEntity::Pre(data) => (RawMessageEntityKind::Pre, data, None, None),
+            TextEntity::TextLink(link) => (RawMessageEntityKind::TextLink, &link.data, Some(link.url.clone()), None),
+            TextEntity::TextMention(mention) => (
+                RawMessageEntityKind::TextMention,
+                &mention.data,
+                None,
+                Some(mention.user.clone()),
+            ),
+            TextEntity::Url(data) => (RawMessageEntityKind::Url, data, None, None),
+            TextEntity::Unknown(entity) => (
+                RawMessageEntityKind::Unknown(entity.kind.clone()),
+                &entity.data,
+                None,
+                None,
+            ),
+        };
+        RawMessageEntity {
+            kind,
+            offset: data.offset as Integer,
+            length: data.length as Integer,
+            url,
+            user,
+            lenient: false,
+        }
+    }
+
+    pub(crate) fn get_data(&self) -> &TextEntityData {
+        match self {
+            TextEntity::Bold(data)
+            | TextEntity::Cashtag(data)
+            | TextEntity::Code(data)
+            | TextEntity::Email(data)
+            | TextEntity::Hashtag(data)
+            | TextEntity::Italic(data)
+            | TextEntity::Mention(data)
+            | TextEntity::PhoneNumber(data)
+            | TextEntity::Pre(data)
+            | TextEntity::Url(data) => data,
+            TextEntity::BotCommand(command) => &command.data,
+            TextEntity::TextLink(link) => &link.data,
+            TextEntity::TextMention(mention) => &mention.data,
+            TextEntity::Unknown(entity) => &entity.data,
+        }
+    }
+
+    /// Returns a copy of entity with given data
+    pub(crate) fn with_data(&self, data: TextEntityData) -> TextEntity {
+        match self {
+            TextEntity::Bold(_) => TextEntity::Bold(data),
+            TextEntity::BotCommand(command) => TextEntity::BotCommand(BotCommand {
+                command: command.command.clone(),
+                bot_name: command.bot_name.clone(),
+                data,
+            }),
+            TextEntity::Cashtag(_) => TextEntity::Cashtag(data),
+            TextEntity::Code(_) => TextEntity::Code(data),
+            TextEntity::Email(_) => TextEntity::Email(data),
+            TextEntity::Hashtag(_) => TextEntity::Hashtag(data),
+            TextEntity::Italic(_) => TextEntity::Italic(data),
+            TextEntity::Mention(_) => TextEntity::Mention(data),
+            TextEntity::PhoneNumber(_) => TextEntity::PhoneNumber(data),
+            TextEntity::Pre(_) => TextEntity::Pre(data),
+            TextEntity::TextLink(link) => TextEntity::TextLink(TextLink {
+                data,
+                url: link.url.clone(),
+            }),
+            TextEntity::TextMention(mention) => TextEntity::TextMention(TextMention {
+                data,
+                user: mention.user.clone(),
+            }),
+            TextEntity::Url(_) => TextEntity::Url(data),
+            TextEntity::Unknown(entity) => TextEntity::Unknown(UnknownTextEntity {
+                kind: entity.kind.clone(),
+                data,
+            }),
+        }
+    }
 }
 
 /// Bot command
@@ -147,6 +376,15 @@ pub struct TextMention {
     pub user: User,
 }
 
+/// Entity of unknown type
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct UnknownTextEntity {
+    /// Type of the entity
+    pub kind: String,
+    /// Actual data of text entity
+    pub data: TextEntityData,
+}
+
 /// Actual data of text entity
 #[derive(Clone, Debug, PartialEq, PartialOrd)]
 pub struct TextEntityData {
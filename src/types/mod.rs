@@ -1,3 +1,53 @@
+/// Implements Deserialize for a struct which collects fields of unknown kinds into `unknown` map
+///
+/// Unlike #[serde(flatten)], known fields are deserialized in place without buffering the whole object
+macro_rules! deserialize_with_unknown {
+    ($name:ident { required: [$($required:ident),*], optional: [$($optional:ident),*] }) => {
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<$name, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct FieldsVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for FieldsVisitor {
+                    type Value = $name;
+
+                    fn expecting(&self, out: &mut std::fmt::Formatter) -> std::fmt::Result {
+                        out.write_str(concat!("struct ", stringify!($name)))
+                    }
+
+                    fn visit_map<A>(self, mut map: A) -> Result<$name, A::Error>
+                    where
+                        A: serde::de::MapAccess<'de>,
+                    {
+                        use serde::de::Error;
+                        $(let mut $required = None;)*
+                        $(let mut $optional = None;)*
+                        let mut unknown = serde_json::Map::new();
+                        while let Some(key) = map.next_key::<String>()? {
+                            match key.as_str() {
+                                $(stringify!($required) => $required = Some(map.next_value()?),)*
+                                $(stringify!($optional) => $optional = map.next_value()?,)*
+                                _ => {
+                                    unknown.insert(key, map.next_value()?);
+                                }
+                            }
+                        }
+                        Ok($name {
+                            $($required: $required.ok_or_else(|| A::Error::missing_field(stringify!($required)))?,)*
+                            $($optional,)*
+                            unknown,
+                        })
+                    }
+                }
+
+                deserializer.deserialize_map(FieldsVisitor)
+            }
+        }
+    };
+}
+
 mod animation;
 mod audio;
 mod callback_query;
@@ -5,8 +55,10 @@ mod chat;
 mod contact;
 mod document;
 mod file;
+mod formatted_text;
 mod games;
 mod inline_mode;
+mod input_file;
 mod input_media;
 mod location;
 mod message;
@@ -17,15 +69,23 @@ mod primitive;
 mod reply_markup;
 mod response;
 mod stickers;
+#[cfg(test)]
+mod tests;
 mod update;
 mod user;
+mod validation;
 mod venue;
 mod video;
 mod video_note;
 mod voice;
 
 pub use self::{
-    animation::*, audio::*, callback_query::*, chat::*, contact::*, document::*, file::*, games::*, inline_mode::*,
-    input_media::*, location::*, message::*, passport::*, payments::*, photo_size::*, primitive::*, reply_markup::*,
-    response::*, stickers::*, update::*, user::*, venue::*, video::*, video_note::*, voice::*,
+    animation::*, audio::*, callback_query::*, chat::*, contact::*, document::*, file::*, formatted_text::*, games::*,
+    inline_mode::*, input_file::*, input_media::*, location::*, message::*, passport::*, payments::*, photo_size::*,
+    primitive::*, reply_markup::*, response::*, stickers::*, update::*, user::*, validation::ValidationError, venue::*,
+    video::*, video_note::*, voice::*,
+};
+
+pub(crate) use self::validation::{
+    check_bytes, check_caption, check_count, check_emojis, check_length, check_mask_position, check_range,
 };
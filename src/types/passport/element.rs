@@ -1,8 +1,8 @@
 use crate::types::passport::PassportFile;
-use serde::{de::Error, Deserialize, Deserializer, Serialize};
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
 
 /// Information about documents or other Telegram Passport elements shared with the bot by the user
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum EncryptedPassportElement {
     /// Address
     Address(EncryptedPassportElementAddress),
@@ -32,6 +32,42 @@ pub enum EncryptedPassportElement {
     UtilityBill(EncryptedPassportElementUtilityBill),
 }
 
+impl EncryptedPassportElement {
+    /// Returns type of the element
+    pub fn get_kind(&self) -> EncryptedPassportElementKind {
+        use self::EncryptedPassportElement::*;
+        match self {
+            Address(_) => EncryptedPassportElementKind::Address,
+            BankStatement(_) => EncryptedPassportElementKind::BankStatement,
+            DriverLicense(_) => EncryptedPassportElementKind::DriverLicense,
+            Email(_) => EncryptedPassportElementKind::Email,
+            IdentityCard(_) => EncryptedPassportElementKind::IdentityCard,
+            InternalPassport(_) => EncryptedPassportElementKind::InternalPassport,
+            Passport(_) => EncryptedPassportElementKind::Passport,
+            PassportRegistration(_) => EncryptedPassportElementKind::PassportRegistration,
+            PersonalDetails(_) => EncryptedPassportElementKind::PersonalDetails,
+            PhoneNumber(_) => EncryptedPassportElementKind::PhoneNumber,
+            RentalAgreement(_) => EncryptedPassportElementKind::RentalAgreement,
+            TemporaryRegistration(_) => EncryptedPassportElementKind::TemporaryRegistration,
+            UtilityBill(_) => EncryptedPassportElementKind::UtilityBill,
+        }
+    }
+
+    /// Returns base64-encoded encrypted data of the element
+    pub fn get_data(&self) -> Option<&str> {
+        use self::EncryptedPassportElement::*;
+        match self {
+            Address(element) => Some(&element.data),
+            DriverLicense(element) => Some(&element.data),
+            IdentityCard(element) => Some(&element.data),
+            InternalPassport(element) => Some(&element.data),
+            Passport(element) => Some(&element.data),
+            PersonalDetails(element) => Some(&element.data),
+            _ => None,
+        }
+    }
+}
+
 impl<'de> Deserialize<'de> for EncryptedPassportElement {
     fn deserialize<D>(deserializer: D) -> Result<EncryptedPassportElement, D::Error>
     where
@@ -127,8 +163,100 @@ impl<'de> Deserialize<'de> for EncryptedPassportElement {
     }
 }
 
+impl Serialize for EncryptedPassportElement {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use self::EncryptedPassportElement::*;
+        let (kind, hash) = match self {
+            Address(element) => (EncryptedPassportElementKind::Address, &element.hash),
+            BankStatement(element) => (EncryptedPassportElementKind::BankStatement, &element.hash),
+            DriverLicense(element) => (EncryptedPassportElementKind::DriverLicense, &element.hash),
+            Email(element) => (EncryptedPassportElementKind::Email, &element.hash),
+            IdentityCard(element) => (EncryptedPassportElementKind::IdentityCard, &element.hash),
+            InternalPassport(element) => (EncryptedPassportElementKind::InternalPassport, &element.hash),
+            Passport(element) => (EncryptedPassportElementKind::Passport, &element.hash),
+            PassportRegistration(element) => (EncryptedPassportElementKind::PassportRegistration, &element.hash),
+            PersonalDetails(element) => (EncryptedPassportElementKind::PersonalDetails, &element.hash),
+            PhoneNumber(element) => (EncryptedPassportElementKind::PhoneNumber, &element.hash),
+            RentalAgreement(element) => (EncryptedPassportElementKind::RentalAgreement, &element.hash),
+            TemporaryRegistration(element) => (EncryptedPassportElementKind::TemporaryRegistration, &element.hash),
+            UtilityBill(element) => (EncryptedPassportElementKind::UtilityBill, &element.hash),
+        };
+        let mut raw = RawEncryptedPassportElement {
+            kind,
+            data: None,
+            phone_number: None,
+            email: None,
+            files: None,
+            front_side: None,
+            reverse_side: None,
+            selfie: None,
+            translation: None,
+            hash: hash.clone(),
+        };
+        match self {
+            Address(element) => raw.data = Some(element.data.clone()),
+            PersonalDetails(element) => raw.data = Some(element.data.clone()),
+            Email(element) => raw.email = Some(element.email.clone()),
+            PhoneNumber(element) => raw.phone_number = Some(element.phone_number.clone()),
+            BankStatement(EncryptedPassportElementBankStatement { files, translation, .. })
+            | PassportRegistration(EncryptedPassportElementPassportRegistration { files, translation, .. })
+            | RentalAgreement(EncryptedPassportElementRentalAgreement { files, translation, .. })
+            | TemporaryRegistration(EncryptedPassportElementTemporaryRegistration { files, translation, .. })
+            | UtilityBill(EncryptedPassportElementUtilityBill { files, translation, .. }) => {
+                raw.files = Some(files.clone());
+                raw.translation = translation.clone();
+            }
+            DriverLicense(EncryptedPassportElementDriverLicense {
+                data,
+                front_side,
+                reverse_side,
+                selfie,
+                translation,
+                ..
+            })
+            | IdentityCard(EncryptedPassportElementIdentityCard {
+                data,
+                front_side,
+                reverse_side,
+                selfie,
+                translation,
+                ..
+            }) => {
+                raw.data = Some(data.clone());
+                raw.front_side = Some(front_side.clone());
+                raw.reverse_side = Some(reverse_side.clone());
+                raw.selfie = Some(selfie.clone());
+                raw.translation = translation.clone();
+            }
+            InternalPassport(EncryptedPassportElementInternalPassport {
+                data,
+                front_side,
+                selfie,
+                translation,
+                ..
+            })
+            | Passport(EncryptedPassportElementPassport {
+                data,
+                front_side,
+                selfie,
+                translation,
+                ..
+            }) => {
+                raw.data = Some(data.clone());
+                raw.front_side = Some(front_side.clone());
+                raw.selfie = Some(selfie.clone());
+                raw.translation = translation.clone();
+            }
+        }
+        raw.serialize(serializer)
+    }
+}
+
 /// Address
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct EncryptedPassportElementAddress {
     /// Base64-encoded encrypted
     /// Telegram Passport element data provided by the user
@@ -141,7 +269,7 @@ pub struct EncryptedPassportElementAddress {
 }
 
 /// Bank statement
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct EncryptedPassportElementBankStatement {
     /// Array of encrypted files with
     /// documents provided by the user
@@ -159,7 +287,7 @@ pub struct EncryptedPassportElementBankStatement {
 }
 
 /// Driver license
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct EncryptedPassportElementDriverLicense {
     /// Base64-encoded encrypted
     /// Telegram Passport element data provided by the user
@@ -192,7 +320,7 @@ pub struct EncryptedPassportElementDriverLicense {
 }
 
 /// E-Mail
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct EncryptedPassportElementEmail {
     /// User's verified email address
     pub email: String,
@@ -202,7 +330,7 @@ pub struct EncryptedPassportElementEmail {
 }
 
 /// Identity card
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct EncryptedPassportElementIdentityCard {
     /// Base64-encoded encrypted
     /// Telegram Passport element data provided by the user
@@ -235,7 +363,7 @@ pub struct EncryptedPassportElementIdentityCard {
 }
 
 /// Internal passport
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct EncryptedPassportElementInternalPassport {
     /// Base64-encoded encrypted
     /// Telegram Passport element data provided by the user
@@ -263,7 +391,7 @@ pub struct EncryptedPassportElementInternalPassport {
 }
 
 /// Passport
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct EncryptedPassportElementPassport {
     /// Base64-encoded encrypted
     /// Telegram Passport element data provided by the user
@@ -291,7 +419,7 @@ pub struct EncryptedPassportElementPassport {
 }
 
 /// Passport registration
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct EncryptedPassportElementPassportRegistration {
     /// Array of encrypted files with
     /// documents provided by the user
@@ -309,7 +437,7 @@ pub struct EncryptedPassportElementPassportRegistration {
 }
 
 /// Personal details
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct EncryptedPassportElementPersonalDetails {
     /// Base64-encoded encrypted
     /// Telegram Passport element data provided by the user
@@ -322,7 +450,7 @@ pub struct EncryptedPassportElementPersonalDetails {
 }
 
 /// Phone number
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct EncryptedPassportElementPhoneNumber {
     /// User's verified phone number
     pub phone_number: String,
@@ -332,7 +460,7 @@ pub struct EncryptedPassportElementPhoneNumber {
 }
 
 /// Rental agreement
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct EncryptedPassportElementRentalAgreement {
     /// Array of encrypted files with
     /// documents provided by the user
@@ -350,7 +478,7 @@ pub struct EncryptedPassportElementRentalAgreement {
 }
 
 /// Temporary registration
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct EncryptedPassportElementTemporaryRegistration {
     /// Array of encrypted files with
     /// documents provided by the user
@@ -368,7 +496,7 @@ pub struct EncryptedPassportElementTemporaryRegistration {
 }
 
 /// Utility bill
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct EncryptedPassportElementUtilityBill {
     /// Array of encrypted files with
     /// documents provided by the user
@@ -385,23 +513,31 @@ pub struct EncryptedPassportElementUtilityBill {
     pub hash: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct RawEncryptedPassportElement {
     #[serde(rename = "type")]
     kind: EncryptedPassportElementKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
     data: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     phone_number: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     files: Option<Vec<PassportFile>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     front_side: Option<PassportFile>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     reverse_side: Option<PassportFile>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     selfie: Option<PassportFile>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     translation: Option<Vec<PassportFile>>,
     hash: String,
 }
 
 /// Type of encrypted passport element
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub enum EncryptedPassportElementKind {
     /// Address
     #[serde(rename = "address")]
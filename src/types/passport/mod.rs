@@ -1,5 +1,5 @@
 use crate::types::primitive::Integer;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 mod element;
 mod error;
@@ -9,7 +9,7 @@ mod tests;
 pub use self::{element::*, error::*};
 
 /// Telegram Passport data shared with the bot by the user
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct PassportData {
     /// Array with information about documents
     /// and other Telegram Passport elements
@@ -23,7 +23,7 @@ pub struct PassportData {
 ///
 /// Currently all Telegram Passport files are in JPEG
 /// format when decrypted and don't exceed 10MB
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct PassportFile {
     /// Unique identifier for this file
     pub file_id: String,
@@ -37,7 +37,7 @@ pub struct PassportFile {
 ///
 /// See the Telegram Passport Documentation for a complete description
 /// of the data decryption and authentication processes
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct EncryptedCredentials {
     /// Base64-encoded encrypted JSON-serialized data
     /// with unique user's payload,
@@ -0,0 +1,461 @@
+use crate::types::primitive::Integer;
+use lazy_static::lazy_static;
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+use std::{collections::HashMap, fmt, str::FromStr};
+
+/// Currencies from https://core.telegram.org/bots/payments/currencies.json
+///
+/// Limits of amounts depend on exchange rates and are updated by Telegram from time to time,
+/// the embedded copy may be slightly out of date
+const CURRENCIES: &str = include_str!("currencies.json");
+
+macro_rules! currencies {
+    ($($(#[$attr:meta])* $variant:ident => $code:expr,)*) => {
+        /// ISO 4217 currency supported by Telegram payments
+        #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+        pub enum Currency {
+            $($(#[$attr])* $variant,)*
+        }
+
+        impl Currency {
+            /// All supported currencies
+            pub const ALL: &'static [Currency] = &[$(Currency::$variant,)*];
+
+            /// Returns three-letter ISO 4217 currency code
+            pub fn get_code(self) -> &'static str {
+                match self {
+                    $(Currency::$variant => $code,)*
+                }
+            }
+        }
+    };
+}
+
+currencies! {
+    /// United Arab Emirates Dirham
+    Aed => "AED",
+    /// Afghan Afghani
+    Afn => "AFN",
+    /// Albanian Lek
+    All => "ALL",
+    /// Armenian Dram
+    Amd => "AMD",
+    /// Argentine Peso
+    Ars => "ARS",
+    /// Australian Dollar
+    Aud => "AUD",
+    /// Azerbaijani Manat
+    Azn => "AZN",
+    /// Bosnia & Herzegovina Convertible Mark
+    Bam => "BAM",
+    /// Bangladeshi Taka
+    Bdt => "BDT",
+    /// Bulgarian Lev
+    Bgn => "BGN",
+    /// Brunei Dollar
+    Bnd => "BND",
+    /// Bolivian Boliviano
+    Bob => "BOB",
+    /// Brazilian Real
+    Brl => "BRL",
+    /// Belarusian Ruble
+    Byn => "BYN",
+    /// Canadian Dollar
+    Cad => "CAD",
+    /// Swiss Franc
+    Chf => "CHF",
+    /// Chilean Peso
+    Clp => "CLP",
+    /// Chinese Renminbi Yuan
+    Cny => "CNY",
+    /// Colombian Peso
+    Cop => "COP",
+    /// Costa Rican Colón
+    Crc => "CRC",
+    /// Czech Koruna
+    Czk => "CZK",
+    /// Danish Krone
+    Dkk => "DKK",
+    /// Dominican Peso
+    Dop => "DOP",
+    /// Algerian Dinar
+    Dzd => "DZD",
+    /// Egyptian Pound
+    Egp => "EGP",
+    /// Ethiopian Birr
+    Etb => "ETB",
+    /// Euro
+    Eur => "EUR",
+    /// British Pound
+    Gbp => "GBP",
+    /// Georgian Lari
+    Gel => "GEL",
+    /// Guatemalan Quetzal
+    Gtq => "GTQ",
+    /// Hong Kong Dollar
+    Hkd => "HKD",
+    /// Honduran Lempira
+    Hnl => "HNL",
+    /// Croatian Kuna
+    Hrk => "HRK",
+    /// Hungarian Forint
+    Huf => "HUF",
+    /// Indonesian Rupiah
+    Idr => "IDR",
+    /// Israeli New Sheqel
+    Ils => "ILS",
+    /// Indian Rupee
+    Inr => "INR",
+    /// Icelandic Króna
+    Isk => "ISK",
+    /// Jamaican Dollar
+    Jmd => "JMD",
+    /// Japanese Yen
+    Jpy => "JPY",
+    /// Kenyan Shilling
+    Kes => "KES",
+    /// Kyrgyzstani Som
+    Kgs => "KGS",
+    /// South Korean Won
+    Krw => "KRW",
+    /// Kazakhstani Tenge
+    Kzt => "KZT",
+    /// Lebanese Pound
+    Lbp => "LBP",
+    /// Sri Lankan Rupee
+    Lkr => "LKR",
+    /// Moroccan Dirham
+    Mad => "MAD",
+    /// Moldovan Leu
+    Mdl => "MDL",
+    /// Mongolian Tögrög
+    Mnt => "MNT",
+    /// Mauritian Rupee
+    Mur => "MUR",
+    /// Maldivian Rufiyaa
+    Mvr => "MVR",
+    /// Mexican Peso
+    Mxn => "MXN",
+    /// Malaysian Ringgit
+    Myr => "MYR",
+    /// Mozambican Metical
+    Mzn => "MZN",
+    /// Nigerian Naira
+    Ngn => "NGN",
+    /// Nicaraguan Córdoba
+    Nio => "NIO",
+    /// Norwegian Krone
+    Nok => "NOK",
+    /// Nepalese Rupee
+    Npr => "NPR",
+    /// New Zealand Dollar
+    Nzd => "NZD",
+    /// Panamanian Balboa
+    Pab => "PAB",
+    /// Peruvian Nuevo Sol
+    Pen => "PEN",
+    /// Philippine Peso
+    Php => "PHP",
+    /// Pakistani Rupee
+    Pkr => "PKR",
+    /// Polish Złoty
+    Pln => "PLN",
+    /// Paraguayan Guaraní
+    Pyg => "PYG",
+    /// Qatari Riyal
+    Qar => "QAR",
+    /// Romanian Leu
+    Ron => "RON",
+    /// Serbian Dinar
+    Rsd => "RSD",
+    /// Russian Ruble
+    Rub => "RUB",
+    /// Saudi Riyal
+    Sar => "SAR",
+    /// Swedish Krona
+    Sek => "SEK",
+    /// Singapore Dollar
+    Sgd => "SGD",
+    /// Thai Baht
+    Thb => "THB",
+    /// Tajikistani Somoni
+    Tjs => "TJS",
+    /// Turkish Lira
+    Try => "TRY",
+    /// Trinidad and Tobago Dollar
+    Ttd => "TTD",
+    /// New Taiwan Dollar
+    Twd => "TWD",
+    /// Tanzanian Shilling
+    Tzs => "TZS",
+    /// Ukrainian Hryvnia
+    Uah => "UAH",
+    /// Ugandan Shilling
+    Ugx => "UGX",
+    /// United States Dollar
+    Usd => "USD",
+    /// Uruguayan Peso
+    Uyu => "UYU",
+    /// Uzbekistani Som
+    Uzs => "UZS",
+    /// Vietnamese Đồng
+    Vnd => "VND",
+    /// Yemeni Rial
+    Yer => "YER",
+    /// South African Rand
+    Zar => "ZAR",
+}
+
+#[derive(Deserialize)]
+struct RawCurrencyInfo {
+    title: String,
+    symbol: String,
+    exp: u32,
+    min_amount: String,
+    max_amount: String,
+}
+
+struct CurrencyInfo {
+    title: String,
+    symbol: String,
+    exp: u32,
+    min_amount: Integer,
+    max_amount: Integer,
+}
+
+lazy_static! {
+    static ref TABLE: HashMap<String, CurrencyInfo> = {
+        let raw: HashMap<String, RawCurrencyInfo> =
+            serde_json::from_str(CURRENCIES).expect("Failed to parse currencies.json");
+        raw.into_iter()
+            .map(|(code, info)| {
+                let info = CurrencyInfo {
+                    title: info.title,
+                    symbol: info.symbol,
+                    exp: info.exp,
+                    min_amount: info.min_amount.parse().expect("Invalid min_amount in currencies.json"),
+                    max_amount: info.max_amount.parse().expect("Invalid max_amount in currencies.json"),
+                };
+                (code, info)
+            })
+            .collect()
+    };
+}
+
+fn get_info(currency: Currency) -> &'static CurrencyInfo {
+    TABLE
+        .get(currency.get_code())
+        .expect("Currency is missing in currencies.json")
+}
+
+impl Currency {
+    /// Returns english name of the currency
+    pub fn get_title(self) -> &'static str {
+        &get_info(self).title
+    }
+
+    /// Returns symbol of the currency
+    pub fn get_symbol(self) -> &'static str {
+        &get_info(self).symbol
+    }
+
+    /// Returns number of digits past the decimal point
+    pub fn get_exponent(self) -> u32 {
+        get_info(self).exp
+    }
+
+    /// Returns minimum amount in the smallest units of the currency
+    pub fn get_min_amount(self) -> Integer {
+        get_info(self).min_amount
+    }
+
+    /// Returns maximum amount in the smallest units of the currency
+    pub fn get_max_amount(self) -> Integer {
+        get_info(self).max_amount
+    }
+}
+
+impl FromStr for Currency {
+    type Err = ParseMoneyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Currency::ALL
+            .iter()
+            .find(|currency| currency.get_code().eq_ignore_ascii_case(s))
+            .cloned()
+            .ok_or_else(|| ParseMoneyError::UnknownCurrency(String::from(s)))
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.get_code())
+    }
+}
+
+impl<'de> Deserialize<'de> for Currency {
+    fn deserialize<D>(deserializer: D) -> Result<Currency, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for Currency {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.get_code())
+    }
+}
+
+/// Amount of money in the smallest units of a currency
+///
+/// Formats as "US$ 1.45" and parses from "US$ 1.45", "US$1.45", "USD 1.45" or "1.45 USD"
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Money {
+    currency: Currency,
+    amount: Integer,
+}
+
+impl Money {
+    /// Creates a new Money
+    ///
+    /// # Arguments
+    ///
+    /// * currency - Currency
+    /// * amount - Amount in the smallest units of the currency, e.g. 145 for US$ 1.45
+    pub fn new(currency: Currency, amount: Integer) -> Self {
+        Money { currency, amount }
+    }
+
+    /// Creates a new Money from a three-letter currency code
+    pub fn from_code(currency: &str, amount: Integer) -> Result<Self, ParseMoneyError> {
+        Ok(Money::new(currency.parse()?, amount))
+    }
+
+    /// Parses an amount without currency, such as "1.45" or "1,000"
+    pub fn parse_amount(currency: Currency, amount: &str) -> Result<Self, ParseMoneyError> {
+        let invalid = || ParseMoneyError::InvalidAmount(String::from(amount));
+        let value = amount.trim();
+        let (negative, value) = match value.strip_prefix('-') {
+            Some(value) => (true, value),
+            None => (false, value),
+        };
+        let (integer, fraction) = match value.find('.') {
+            Some(idx) => (&value[..idx], &value[idx + 1..]),
+            None => (value, ""),
+        };
+        let exp = currency.get_exponent();
+        let integer = integer.replace(',', "");
+        if integer.is_empty()
+            || fraction.len() > exp as usize
+            || !integer.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+        let fraction = format!("{:0<width$}", fraction, width = exp as usize);
+        let result = format!("{}{}", integer, fraction)
+            .parse::<Integer>()
+            .map_err(|_| invalid())?;
+        Ok(Money::new(currency, if negative { -result } else { result }))
+    }
+
+    /// Returns the currency
+    pub fn get_currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// Returns amount in the smallest units of the currency
+    pub fn get_amount(&self) -> Integer {
+        self.amount
+    }
+
+    /// Returns amount without currency, such as "1.45"
+    pub fn format_amount(&self) -> String {
+        let exp = self.currency.get_exponent() as usize;
+        let sign = if self.amount < 0 { "-" } else { "" };
+        let digits = format!("{:0>width$}", self.amount.unsigned_abs(), width = exp + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - exp);
+        if exp == 0 {
+            format!("{}{}", sign, integer)
+        } else {
+            format!("{}{}.{}", sign, integer, fraction)
+        }
+    }
+
+    /// Returns whether the amount is allowed by Telegram for a payment
+    pub fn is_within_limits(&self) -> bool {
+        self.amount >= self.currency.get_min_amount() && self.amount <= self.currency.get_max_amount()
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.currency.get_symbol(), self.format_amount())
+    }
+}
+
+impl FromStr for Money {
+    type Err = ParseMoneyError;
+
+    /// Parses an amount with a currency code or symbol, such as "US$ 1.45" or "1.45 USD"
+    ///
+    /// A label may match several currencies, e.g. "¥" is a part of "CN¥",
+    /// only currencies which leave a valid amount are considered,
+    /// an error is returned if more than one currency remains
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.trim();
+        let mut found: Option<Money> = None;
+        let mut error = None;
+        for currency in Currency::ALL {
+            for label in &[currency.get_code(), currency.get_symbol()] {
+                let amount = match strip_label(value, label) {
+                    Some(amount) => amount,
+                    None => continue,
+                };
+                match Money::parse_amount(*currency, amount) {
+                    Ok(money) => match found {
+                        Some(ref found) if found.currency != money.currency => {
+                            return Err(ParseMoneyError::AmbiguousCurrency(String::from(s)));
+                        }
+                        _ => found = Some(money),
+                    },
+                    Err(err) => error = error.or(Some(err)),
+                }
+            }
+        }
+        found.ok_or_else(|| error.unwrap_or_else(|| ParseMoneyError::UnknownCurrency(String::from(s))))
+    }
+}
+
+/// Removes a currency code or symbol from the start or the end of a string ignoring ASCII case
+fn strip_label<'a>(value: &'a str, label: &str) -> Option<&'a str> {
+    let len = label.len();
+    if value.len() < len {
+        return None;
+    }
+    let split = value.len() - len;
+    match (value.get(..len), value.get(split..)) {
+        (Some(prefix), _) if prefix.eq_ignore_ascii_case(label) => Some(&value[len..]),
+        (_, Some(suffix)) if suffix.eq_ignore_ascii_case(label) => Some(&value[..split]),
+        _ => None,
+    }
+}
+
+/// An error when parsing currency or money
+#[derive(Debug, failure::Fail)]
+pub enum ParseMoneyError {
+    /// Currency is not supported by Telegram
+    #[fail(display = "Unknown currency: \"{}\"", _0)]
+    UnknownCurrency(String),
+    /// Currency label matches more than one currency
+    #[fail(display = "Ambiguous currency: \"{}\"", _0)]
+    AmbiguousCurrency(String),
+    /// Amount is not a number or has too many digits past the decimal point
+    #[fail(display = "Invalid amount: \"{}\"", _0)]
+    InvalidAmount(String),
+}
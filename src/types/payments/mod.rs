@@ -1,8 +1,14 @@
 use crate::types::{primitive::Integer, user::User};
 use serde::{Deserialize, Serialize};
 
+mod currency;
+#[cfg(test)]
+mod tests;
+
+pub use self::currency::*;
+
 /// Basic information about an invoice
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Invoice {
     /// Product name
     pub title: String,
@@ -20,6 +26,13 @@ pub struct Invoice {
     pub total_amount: Integer,
 }
 
+impl Invoice {
+    /// Returns total price with parsed currency
+    pub fn get_total_amount(&self) -> Result<Money, ParseMoneyError> {
+        Money::from_code(&self.currency, self.total_amount)
+    }
+}
+
 /// Portion of the price for goods or services
 #[derive(Clone, Debug, Serialize)]
 pub struct LabeledPrice {
@@ -34,20 +47,24 @@ pub struct LabeledPrice {
 }
 
 /// Information about an order
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct OrderInfo {
     /// User name
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
     /// User's phone number
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub phone_number: Option<String>,
     /// User email
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub email: Option<String>,
     /// User shipping address
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub shipping_address: Option<ShippingAddress>,
 }
 
 /// Information about an incoming pre-checkout query
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct PreCheckoutQuery {
     /// Unique query identifier
     pub id: String,
@@ -64,13 +81,22 @@ pub struct PreCheckoutQuery {
     /// Bot specified invoice payload
     pub invoice_payload: String,
     /// Identifier of the shipping option chosen by the user
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub shipping_option_id: Option<String>,
     /// Order info provided by the user
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub order_info: Option<OrderInfo>,
 }
 
+impl PreCheckoutQuery {
+    /// Returns total price with parsed currency
+    pub fn get_total_amount(&self) -> Result<Money, ParseMoneyError> {
+        Money::from_code(&self.currency, self.total_amount)
+    }
+}
+
 /// Shipping address
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct ShippingAddress {
     /// ISO 3166-1 alpha-2 country code
     pub country_code: String,
@@ -98,7 +124,7 @@ pub struct ShippingOption {
 }
 
 /// Information about an incoming shipping query
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct ShippingQuery {
     /// Unique query identifier
     pub id: String,
@@ -111,7 +137,7 @@ pub struct ShippingQuery {
 }
 
 /// Basic information about a successful payment
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct SuccessfulPayment {
     /// Three-letter ISO 4217 currency code
     pub currency: String,
@@ -124,11 +150,20 @@ pub struct SuccessfulPayment {
     /// Bot specified invoice payload
     pub invoice_payload: String,
     /// Identifier of the shipping option chosen by the user
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub shipping_option_id: Option<String>,
     /// Order info provided by the user
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub order_info: Option<OrderInfo>,
     /// Telegram payment identifier
     pub telegram_payment_charge_id: String,
     /// Provider payment identifier
     pub provider_payment_charge_id: String,
 }
+
+impl SuccessfulPayment {
+    /// Returns total price with parsed currency
+    pub fn get_total_amount(&self) -> Result<Money, ParseMoneyError> {
+        Money::from_code(&self.currency, self.total_amount)
+    }
+}
@@ -0,0 +1,73 @@
+use crate::types::*;
+use serde_json::json;
+
+#[test]
+fn test_currency() {
+    assert_eq!(Currency::ALL.len(), 86);
+    for currency in Currency::ALL {
+        assert_eq!(currency.get_code().parse::<Currency>().unwrap(), *currency);
+        assert!(!currency.get_title().is_empty());
+        assert!(currency.get_min_amount() > 0);
+        assert!(currency.get_max_amount() > currency.get_min_amount());
+    }
+    assert_eq!(Currency::Usd.get_code(), "USD");
+    assert_eq!(Currency::Usd.get_symbol(), "US$");
+    assert_eq!(Currency::Usd.get_exponent(), 2);
+    assert_eq!(Currency::Usd.get_min_amount(), 100);
+    assert_eq!(Currency::Jpy.get_exponent(), 0);
+    assert_eq!("eur".parse::<Currency>().unwrap(), Currency::Eur);
+    assert!("XXX".parse::<Currency>().is_err());
+    assert_eq!(serde_json::to_value(Currency::Gbp).unwrap(), json!("GBP"));
+    assert_eq!(serde_json::from_value::<Currency>(json!("RUB")).unwrap(), Currency::Rub);
+    assert!(serde_json::from_value::<Currency>(json!("ABC")).is_err());
+}
+
+#[test]
+fn test_money() {
+    let money = Money::new(Currency::Usd, 145);
+    assert_eq!(money.to_string(), "US$ 1.45");
+    assert_eq!(money.format_amount(), "1.45");
+    assert_eq!(Money::new(Currency::Usd, 5).format_amount(), "0.05");
+    assert_eq!(Money::new(Currency::Usd, -250).format_amount(), "-2.50");
+    assert_eq!(Money::new(Currency::Jpy, 1500).to_string(), "¥ 1500");
+    assert_eq!(Money::new(Currency::Eur, 100_000).to_string(), "€ 1000.00");
+
+    for value in &["US$ 1.45", "US$1.45", "USD 1.45", "1.45 USD", " usd 1.45 "] {
+        assert_eq!(value.parse::<Money>().unwrap(), money, "{}", value);
+    }
+    assert_eq!(
+        "CA$ 1,000.5".parse::<Money>().unwrap(),
+        Money::new(Currency::Cad, 100_050)
+    );
+    assert_eq!("JPY 1500".parse::<Money>().unwrap(), Money::new(Currency::Jpy, 1500));
+    assert_eq!("¥ 1500".parse::<Money>().unwrap(), Money::new(Currency::Jpy, 1500));
+    assert_eq!("1500 CN¥".parse::<Money>().unwrap(), Money::new(Currency::Cny, 150_000));
+    assert_eq!(Money::parse_amount(Currency::Usd, "-3").unwrap().get_amount(), -300);
+    for value in &["US$ 1.456", "JPY 1.5", "US$", "US$ 1.4a", "US$ .5"] {
+        match value.parse::<Money>() {
+            Err(ParseMoneyError::InvalidAmount(_)) => {}
+            result => panic!("Unexpected result for {}: {:?}", value, result),
+        }
+    }
+    match "1.45 XYZ".parse::<Money>() {
+        Err(ParseMoneyError::UnknownCurrency(_)) => {}
+        result => panic!("Unexpected result: {:?}", result),
+    }
+
+    assert!(money.is_within_limits());
+    assert!(!Money::new(Currency::Usd, 99).is_within_limits());
+    assert!(!Money::new(Currency::Usd, 1_000_001).is_within_limits());
+}
+
+#[test]
+fn test_get_total_amount() {
+    let invoice: Invoice = serde_json::from_value(json!({
+        "title": "t",
+        "description": "d",
+        "start_parameter": "s",
+        "currency": "USD",
+        "total_amount": 145
+    }))
+    .unwrap();
+    assert_eq!(invoice.get_total_amount().unwrap(), Money::new(Currency::Usd, 145));
+}
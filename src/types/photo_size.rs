@@ -1,8 +1,8 @@
 use crate::types::primitive::Integer;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// Size of a photo or a file / sticker thumbnail
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct PhotoSize {
     /// Unique identifier for this file
     pub file_id: String,
@@ -11,5 +11,6 @@ pub struct PhotoSize {
     /// Photo height
     pub height: Integer,
     /// File size
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub file_size: Option<Integer>,
 }
@@ -1,5 +1,10 @@
+use crate::types::validation::ValidationError;
 use serde::Serialize;
 
+const MAX_ROW_BUTTONS: usize = 8;
+const MAX_BUTTONS: usize = 100;
+const MAX_CALLBACK_DATA_LENGTH: usize = 64;
+
 /// Inline keyboard that appears right next to the message it belongs to
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct InlineKeyboardMarkup {
@@ -12,11 +17,77 @@ impl InlineKeyboardMarkup {
         InlineKeyboardMarkup { inline_keyboard }
     }
 
+    /// Returns a KeyboardMarkup with buttons laid out in a grid
+    ///
+    /// # Arguments
+    ///
+    /// * buttons - Buttons to lay out
+    /// * columns - Number of buttons in a row, the last row may contain less
+    pub fn grid<I>(buttons: I, columns: usize) -> Self
+    where
+        I: IntoIterator<Item = InlineKeyboardButton>,
+    {
+        InlineKeyboardMarkup::default().rows(buttons, columns)
+    }
+
     /// Adds a row to keyboard
     pub fn row(mut self, row: Vec<InlineKeyboardButton>) -> Self {
         self.inline_keyboard.push(row);
         self
     }
+
+    /// Adds buttons to keyboard in rows of given number of columns
+    pub fn rows<I>(mut self, buttons: I, columns: usize) -> Self
+    where
+        I: IntoIterator<Item = InlineKeyboardButton>,
+    {
+        self.inline_keyboard.extend(into_grid(buttons, columns));
+        self
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), ValidationError> {
+        let mut count = 0;
+        for (row_idx, row) in self.inline_keyboard.iter().enumerate() {
+            if row.is_empty() || row.len() > MAX_ROW_BUTTONS {
+                return Err(ValidationError::new(
+                    "reply_markup",
+                    format!(
+                        "row {} contains {} buttons, expected 1-{}",
+                        row_idx,
+                        row.len(),
+                        MAX_ROW_BUTTONS
+                    ),
+                ));
+            }
+            count += row.len();
+            for (button_idx, button) in row.iter().enumerate() {
+                button.validate(row_idx == 0 && button_idx == 0)?;
+            }
+        }
+        if count > MAX_BUTTONS {
+            return Err(ValidationError::new(
+                "reply_markup",
+                format!("keyboard contains {} buttons, expected at most {}", count, MAX_BUTTONS),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Splits buttons into rows of given number of columns
+pub(crate) fn into_grid<I, T>(buttons: I, columns: usize) -> Vec<Vec<T>>
+where
+    I: IntoIterator<Item = T>,
+{
+    let columns = columns.max(1);
+    let mut rows: Vec<Vec<T>> = Vec::new();
+    for button in buttons {
+        match rows.last_mut() {
+            Some(row) if row.len() < columns => row.push(button),
+            _ => rows.push(vec![button]),
+        }
+    }
+    rows
 }
 
 impl From<Vec<Vec<InlineKeyboardButton>>> for InlineKeyboardMarkup {
@@ -159,4 +230,43 @@ impl InlineKeyboardButton {
             pay: Some(true),
         }
     }
+
+    fn validate(&self, is_first: bool) -> Result<(), ValidationError> {
+        let error = |reason: String| Err(ValidationError::new("reply_markup", reason));
+        if self.text.is_empty() {
+            return error(String::from("button text is empty"));
+        }
+        let fields = [
+            self.url.is_some(),
+            self.callback_data.is_some(),
+            self.switch_inline_query.is_some(),
+            self.switch_inline_query_current_chat.is_some(),
+            self.callback_game.is_some(),
+            self.pay.is_some(),
+        ];
+        if fields.iter().filter(|is_set| **is_set).count() != 1 {
+            return error(format!(
+                "button \"{}\" must have exactly one of url, callback_data, switch_inline_query, \
+                 switch_inline_query_current_chat, callback_game or pay",
+                self.text
+            ));
+        }
+        if let Some(ref data) = self.callback_data {
+            if data.is_empty() || data.len() > MAX_CALLBACK_DATA_LENGTH {
+                return error(format!(
+                    "callback data of button \"{}\" is {} bytes, expected 1-{}",
+                    self.text,
+                    data.len(),
+                    MAX_CALLBACK_DATA_LENGTH
+                ));
+            }
+        }
+        if !is_first && (self.callback_game.is_some() || self.pay.is_some()) {
+            return error(format!(
+                "game or pay button \"{}\" must be the first button in the first row",
+                self.text
+            ));
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,158 @@
+use crate::types::{
+    callback_query::CallbackQuery,
+    reply_markup::inline_keyboard::{InlineKeyboardButton, InlineKeyboardMarkup},
+};
+
+const PATH_SEPARATOR: char = '/';
+
+/// Nested inline menu with automatic back navigation
+///
+/// Buttons opening a submenu have callback data of the prefix followed by a path to the submenu,
+/// e.g. "menu:settings/language", every submenu gets a back button leading to its parent
+/// Pass incoming callback queries to handle_callback_query() and replace
+/// reply markup of the message with the returned keyboard
+#[derive(Clone, Debug)]
+pub struct InlineMenu {
+    prefix: String,
+    back_text: String,
+    root: InlineMenuPage,
+}
+
+impl InlineMenu {
+    /// Creates a new menu
+    ///
+    /// # Arguments
+    ///
+    /// * prefix - Prefix of callback data of navigation buttons, must be unique within the bot
+    /// * root - Top level page of the menu
+    pub fn new<S: Into<String>>(prefix: S, root: InlineMenuPage) -> Self {
+        InlineMenu {
+            prefix: prefix.into(),
+            back_text: String::from("‹ Back"),
+            root,
+        }
+    }
+
+    /// Text of the back button, defaults to "‹ Back"
+    pub fn back_text<S: Into<String>>(mut self, back_text: S) -> Self {
+        self.back_text = back_text.into();
+        self
+    }
+
+    /// Returns a keyboard for the top level page
+    pub fn get_keyboard(&self) -> InlineKeyboardMarkup {
+        self.build(&self.root, "", None)
+    }
+
+    /// Returns a keyboard for a page with given callback data
+    ///
+    /// Returns None if data does not belong to this menu or page does not exist
+    pub fn navigate(&self, data: &str) -> Option<InlineKeyboardMarkup> {
+        let path = data.strip_prefix(self.prefix.as_str())?;
+        if path.is_empty() {
+            return Some(self.get_keyboard());
+        }
+        let mut page = &self.root;
+        for id in path.split(PATH_SEPARATOR) {
+            page = page.get_submenu(id)?;
+        }
+        let parent = path.rfind(PATH_SEPARATOR).map_or("", |idx| &path[..idx]);
+        Some(self.build(page, path, Some(parent)))
+    }
+
+    /// Returns a keyboard for a page selected with a callback query
+    ///
+    /// Returns None if the query does not belong to this menu
+    pub fn handle_callback_query(&self, query: &CallbackQuery) -> Option<InlineKeyboardMarkup> {
+        self.navigate(query.data.as_ref()?)
+    }
+
+    fn build(&self, page: &InlineMenuPage, path: &str, parent: Option<&str>) -> InlineKeyboardMarkup {
+        let buttons = page.items.iter().map(|item| match item {
+            MenuItem::Button(button) => button.clone(),
+            MenuItem::Submenu { id, text, .. } => {
+                let data = if path.is_empty() {
+                    format!("{}{}", self.prefix, id)
+                } else {
+                    format!("{}{}{}{}", self.prefix, path, PATH_SEPARATOR, id)
+                };
+                InlineKeyboardButton::with_callback_data(text.clone(), data)
+            }
+        });
+        let markup = InlineKeyboardMarkup::grid(buttons, page.columns);
+        match parent {
+            Some(parent) => markup.row(vec![InlineKeyboardButton::with_callback_data(
+                self.back_text.clone(),
+                format!("{}{}", self.prefix, parent),
+            )]),
+            None => markup,
+        }
+    }
+}
+
+/// A page of inline menu
+#[derive(Clone, Debug)]
+pub struct InlineMenuPage {
+    columns: usize,
+    items: Vec<MenuItem>,
+}
+
+#[derive(Clone, Debug)]
+enum MenuItem {
+    Button(InlineKeyboardButton),
+    Submenu {
+        id: String,
+        text: String,
+        page: InlineMenuPage,
+    },
+}
+
+impl Default for InlineMenuPage {
+    fn default() -> Self {
+        InlineMenuPage {
+            columns: 1,
+            items: Vec::new(),
+        }
+    }
+}
+
+impl InlineMenuPage {
+    /// Number of buttons in a row, defaults to 1
+    pub fn columns(mut self, columns: usize) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Adds a button, handling of the button is up to the bot
+    pub fn button(mut self, button: InlineKeyboardButton) -> Self {
+        self.items.push(MenuItem::Button(button));
+        self
+    }
+
+    /// Adds a button opening a submenu
+    ///
+    /// # Arguments
+    ///
+    /// * id - Identifier of the submenu, unique within the page, must not contain "/"
+    /// * text - Text of the button
+    /// * page - Submenu
+    pub fn submenu<I, T>(mut self, id: I, text: T, page: InlineMenuPage) -> Self
+    where
+        I: Into<String>,
+        T: Into<String>,
+    {
+        self.items.push(MenuItem::Submenu {
+            id: id.into(),
+            text: text.into(),
+            page,
+        });
+        self
+    }
+
+    fn get_submenu(&self, submenu_id: &str) -> Option<&InlineMenuPage> {
+        self.items.iter().find_map(|item| match item {
+            MenuItem::Submenu { id, page, .. } if id == submenu_id => Some(page),
+            _ => None,
+        })
+    }
+}
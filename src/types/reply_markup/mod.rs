@@ -1,12 +1,15 @@
+use crate::types::validation::ValidationError;
 use serde::Serialize;
 
 mod force_reply;
 mod inline_keyboard;
+mod menu;
+mod pagination;
 mod reply_keyboard;
 #[cfg(test)]
 mod tests;
 
-pub use self::{force_reply::*, inline_keyboard::*, reply_keyboard::*};
+pub use self::{force_reply::*, inline_keyboard::*, menu::*, pagination::*, reply_keyboard::*};
 
 /// Reply markup
 #[derive(Clone, Debug, derive_more::From, Serialize)]
@@ -22,6 +25,16 @@ pub enum ReplyMarkup {
     ReplyKeyboardRemove(ReplyKeyboardRemove),
 }
 
+impl ReplyMarkup {
+    pub(crate) fn validate(&self) -> Result<(), ValidationError> {
+        match self {
+            ReplyMarkup::InlineKeyboardMarkup(markup) => markup.validate(),
+            ReplyMarkup::ReplyKeyboardMarkup(markup) => markup.validate(),
+            ReplyMarkup::ForceReply(_) | ReplyMarkup::ReplyKeyboardRemove(_) => Ok(()),
+        }
+    }
+}
+
 impl From<Vec<Vec<InlineKeyboardButton>>> for ReplyMarkup {
     fn from(markup: Vec<Vec<InlineKeyboardButton>>) -> ReplyMarkup {
         ReplyMarkup::InlineKeyboardMarkup(markup.into())
@@ -0,0 +1,96 @@
+use crate::types::reply_markup::inline_keyboard::{InlineKeyboardButton, InlineKeyboardMarkup};
+
+/// Builds inline keyboards for a paginated list of items
+///
+/// Each page contains items laid out in a grid and a navigation row like "‹ 2/7 ›"
+/// Callback data of navigation buttons is the prefix followed by a page number,
+/// use parse_page() to get the number back from a callback query
+#[derive(Clone, Debug)]
+pub struct InlineKeyboardPagination {
+    prefix: String,
+    page_size: usize,
+    columns: usize,
+    previous_text: String,
+    next_text: String,
+}
+
+impl InlineKeyboardPagination {
+    /// Creates a new pagination
+    ///
+    /// # Arguments
+    ///
+    /// * prefix - Prefix of callback data of navigation buttons, must be unique within the bot
+    /// * page_size - Maximum number of items on a page
+    pub fn new<S: Into<String>>(prefix: S, page_size: usize) -> Self {
+        InlineKeyboardPagination {
+            prefix: prefix.into(),
+            page_size: page_size.max(1),
+            columns: 1,
+            previous_text: String::from("‹"),
+            next_text: String::from("›"),
+        }
+    }
+
+    /// Number of items in a row, defaults to 1
+    pub fn columns(mut self, columns: usize) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Text of the previous page button, defaults to "‹"
+    pub fn previous_text<S: Into<String>>(mut self, text: S) -> Self {
+        self.previous_text = text.into();
+        self
+    }
+
+    /// Text of the next page button, defaults to "›"
+    pub fn next_text<S: Into<String>>(mut self, text: S) -> Self {
+        self.next_text = text.into();
+        self
+    }
+
+    /// Returns number of pages for given number of items
+    pub fn get_page_count(&self, item_count: usize) -> usize {
+        item_count.div_ceil(self.page_size).max(1)
+    }
+
+    /// Returns a keyboard for the page
+    ///
+    /// # Arguments
+    ///
+    /// * items - All items of the list
+    /// * page - Zero-based number of the page, a number past the end means the last page
+    pub fn build<I>(&self, items: I, page: usize) -> InlineKeyboardMarkup
+    where
+        I: IntoIterator<Item = InlineKeyboardButton>,
+    {
+        let items: Vec<InlineKeyboardButton> = items.into_iter().collect();
+        let page_count = self.get_page_count(items.len());
+        let page = page.min(page_count - 1);
+        let page_items = items.into_iter().skip(page * self.page_size).take(self.page_size);
+        let markup = InlineKeyboardMarkup::grid(page_items, self.columns);
+        if page_count == 1 {
+            return markup;
+        }
+        let mut navigation = Vec::new();
+        if page > 0 {
+            navigation.push(self.get_button(self.previous_text.clone(), page - 1));
+        }
+        navigation.push(self.get_button(format!("{}/{}", page + 1, page_count), page));
+        if page + 1 < page_count {
+            navigation.push(self.get_button(self.next_text.clone(), page + 1));
+        }
+        markup.row(navigation)
+    }
+
+    /// Returns a page number from callback data of a navigation button
+    ///
+    /// Returns None if data does not belong to this pagination
+    pub fn parse_page(&self, data: &str) -> Option<usize> {
+        data.strip_prefix(self.prefix.as_str())?.parse().ok()
+    }
+
+    fn get_button(&self, text: String, page: usize) -> InlineKeyboardButton {
+        InlineKeyboardButton::with_callback_data(text, format!("{}{}", self.prefix, page))
+    }
+}
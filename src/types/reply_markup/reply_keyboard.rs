@@ -1,3 +1,4 @@
+use crate::types::{reply_markup::inline_keyboard::into_grid, validation::ValidationError};
 use serde::Serialize;
 use std::ops::Not;
 
@@ -61,11 +62,40 @@ impl ReplyKeyboardMarkup {
         self
     }
 
+    /// Returns a KeyboardMarkup with buttons laid out in a grid
+    ///
+    /// # Arguments
+    ///
+    /// * buttons - Buttons to lay out
+    /// * columns - Number of buttons in a row, the last row may contain less
+    pub fn grid<I>(buttons: I, columns: usize) -> Self
+    where
+        I: IntoIterator<Item = KeyboardButton>,
+    {
+        ReplyKeyboardMarkup::from_vec(into_grid(buttons, columns))
+    }
+
     /// Adds a row to keyboard
     pub fn row(mut self, row: Vec<KeyboardButton>) -> Self {
         self.keyboard.push(row);
         self
     }
+
+    /// Adds buttons to keyboard in rows of given number of columns
+    pub fn rows<I>(mut self, buttons: I, columns: usize) -> Self
+    where
+        I: IntoIterator<Item = KeyboardButton>,
+    {
+        self.keyboard.extend(into_grid(buttons, columns));
+        self
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), ValidationError> {
+        if self.keyboard.iter().flatten().any(|button| button.text.is_empty()) {
+            return Err(ValidationError::new("reply_markup", "button text is empty"));
+        }
+        Ok(())
+    }
 }
 
 impl From<Vec<Vec<KeyboardButton>>> for ReplyKeyboardMarkup {
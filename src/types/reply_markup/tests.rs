@@ -1,4 +1,4 @@
-use crate::types::reply_markup::*;
+use crate::types::{reply_markup::*, CallbackQuery};
 
 use serde_json::json;
 
@@ -50,3 +50,163 @@ fn test_serialize() {
     let j = serde_json::to_value(&markup).unwrap();
     assert_eq!(j, json!({"remove_keyboard":true,"selective":true}));
 }
+
+#[test]
+fn test_validate() {
+    let markup: ReplyMarkup = vec![vec![
+        InlineKeyboardButton::with_callback_game("cg"),
+        InlineKeyboardButton::with_callback_data("cd", "cd"),
+    ]]
+    .into();
+    assert!(markup.validate().is_ok());
+
+    for (markup, reason) in &[
+        (
+            vec![vec![InlineKeyboardButton::new("test")]],
+            "button \"test\" must have exactly one of url, callback_data, switch_inline_query, \
+             switch_inline_query_current_chat, callback_game or pay",
+        ),
+        (
+            vec![vec![InlineKeyboardButton::with_callback_data("cd", &"x".repeat(65))]],
+            "callback data of button \"cd\" is 65 bytes, expected 1-64",
+        ),
+        (
+            vec![vec![
+                InlineKeyboardButton::with_url("url", "tg://user?id=1"),
+                InlineKeyboardButton::with_pay("pay"),
+            ]],
+            "game or pay button \"pay\" must be the first button in the first row",
+        ),
+        (vec![vec![]], "row 0 contains 0 buttons, expected 1-8"),
+        (
+            vec![vec![InlineKeyboardButton::with_url("url", "tg://user?id=1"); 9]],
+            "row 0 contains 9 buttons, expected 1-8",
+        ),
+        (
+            vec![vec![InlineKeyboardButton::with_url("url", "tg://user?id=1"); 5]; 21],
+            "keyboard contains 105 buttons, expected at most 100",
+        ),
+    ] {
+        let markup: ReplyMarkup = markup.clone().into();
+        assert_eq!(markup.validate().unwrap_err().reason, *reason);
+    }
+
+    let markup: ReplyMarkup = vec![vec![KeyboardButton::new("")]].into();
+    assert!(markup.validate().is_err());
+}
+
+fn get_callback_data(markup: &InlineKeyboardMarkup) -> Vec<Vec<String>> {
+    let data = serde_json::to_value(markup).unwrap();
+    data["inline_keyboard"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|row| {
+            row.as_array()
+                .unwrap()
+                .iter()
+                .map(|button| {
+                    format!(
+                        "{}={}",
+                        button["text"].as_str().unwrap(),
+                        button["callback_data"].as_str().unwrap()
+                    )
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn get_buttons(count: usize) -> Vec<InlineKeyboardButton> {
+    (0..count)
+        .map(|idx| InlineKeyboardButton::with_callback_data(idx.to_string(), idx.to_string()))
+        .collect()
+}
+
+#[test]
+fn test_grid() {
+    assert_eq!(
+        get_callback_data(&InlineKeyboardMarkup::grid(get_buttons(5), 2)),
+        vec![vec!["0=0", "1=1"], vec!["2=2", "3=3"], vec!["4=4"]]
+    );
+    assert!(get_callback_data(&InlineKeyboardMarkup::grid(get_buttons(0), 2)).is_empty());
+    let markup = ReplyKeyboardMarkup::grid(vec![KeyboardButton::new("a"), KeyboardButton::new("b")], 1);
+    assert_eq!(
+        serde_json::to_value(&markup).unwrap(),
+        json!({"keyboard": [[{"text": "a"}], [{"text": "b"}]]})
+    );
+}
+
+#[test]
+fn test_pagination() {
+    let pagination = InlineKeyboardPagination::new("page:", 2).columns(2);
+    assert_eq!(pagination.get_page_count(0), 1);
+    assert_eq!(pagination.get_page_count(5), 3);
+    assert_eq!(
+        get_callback_data(&pagination.build(get_buttons(5), 0)),
+        vec![vec!["0=0", "1=1"], vec!["1/3=page:0", "›=page:1"]]
+    );
+    assert_eq!(
+        get_callback_data(&pagination.build(get_buttons(5), 1)),
+        vec![vec!["2=2", "3=3"], vec!["‹=page:0", "2/3=page:1", "›=page:2"]]
+    );
+    assert_eq!(
+        get_callback_data(&pagination.build(get_buttons(5), 10)),
+        vec![vec!["4=4"], vec!["‹=page:1", "3/3=page:2"]]
+    );
+    assert_eq!(
+        get_callback_data(&pagination.build(get_buttons(2), 0)),
+        vec![vec!["0=0", "1=1"]]
+    );
+    assert_eq!(pagination.parse_page("page:2"), Some(2));
+    assert_eq!(pagination.parse_page("page:x"), None);
+    assert_eq!(pagination.parse_page("other:2"), None);
+}
+
+#[test]
+fn test_menu() {
+    let menu = InlineMenu::new(
+        "menu:",
+        InlineMenuPage::default()
+            .columns(2)
+            .button(InlineKeyboardButton::with_callback_data("Help", "help"))
+            .submenu(
+                "settings",
+                "Settings",
+                InlineMenuPage::default().submenu(
+                    "lang",
+                    "Language",
+                    InlineMenuPage::default().button(InlineKeyboardButton::with_callback_data("English", "lang:en")),
+                ),
+            ),
+    );
+    assert_eq!(
+        get_callback_data(&menu.get_keyboard()),
+        vec![vec!["Help=help", "Settings=menu:settings"]]
+    );
+    assert_eq!(
+        get_callback_data(&menu.navigate("menu:settings").unwrap()),
+        vec![vec!["Language=menu:settings/lang"], vec!["‹ Back=menu:"]]
+    );
+    assert_eq!(
+        get_callback_data(&menu.navigate("menu:settings/lang").unwrap()),
+        vec![vec!["English=lang:en"], vec!["‹ Back=menu:settings"]]
+    );
+    assert_eq!(
+        get_callback_data(&menu.navigate("menu:").unwrap()),
+        get_callback_data(&menu.get_keyboard())
+    );
+    assert!(menu.navigate("menu:unknown").is_none());
+    assert!(menu.navigate("help").is_none());
+
+    let query: CallbackQuery = serde_json::from_value(json!({
+        "id": "id",
+        "from": {"id": 1, "first_name": "firstname", "is_bot": false},
+        "data": "menu:settings"
+    }))
+    .unwrap();
+    assert_eq!(
+        get_callback_data(&menu.handle_callback_query(&query).unwrap()),
+        get_callback_data(&menu.navigate("menu:settings").unwrap())
+    );
+}
@@ -5,7 +5,7 @@ use crate::types::{
 use serde::{Deserialize, Serialize};
 
 /// The part of the face relative to which the mask should be placed
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub enum MaskPositionPoint {
     /// “forehead”
     #[serde(rename = "forehead")]
@@ -22,7 +22,7 @@ pub enum MaskPositionPoint {
 }
 
 /// Position on faces where a mask should be placed by default
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct MaskPosition {
     /// The part of the face relative
     /// to which the mask should be placed
@@ -46,7 +46,7 @@ pub struct MaskPosition {
 }
 
 /// Sticker
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Sticker {
     /// Unique identifier for this file
     pub file_id: String,
@@ -55,19 +55,24 @@ pub struct Sticker {
     /// Sticker height
     pub height: Integer,
     /// Sticker thumbnail in the .webp or .jpg format
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub thumb: Option<PhotoSize>,
     /// Emoji associated with the sticker
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub emoji: Option<String>,
     /// Name of the sticker set to which the sticker belongs
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub set_name: Option<String>,
     /// For mask stickers, the position where the mask should be placed
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub mask_position: Option<MaskPosition>,
     /// File size
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub file_size: Option<Integer>,
 }
 
 /// Sticker set
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct StickerSet {
     /// Sticker set name
     pub name: String,
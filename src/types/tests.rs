@@ -0,0 +1,11 @@
+use serde::{de::DeserializeOwned, Serialize};
+use std::fmt::Debug;
+
+/// Asserts that a value is the same after serialization and deserialization
+pub(crate) fn assert_round_trip<T>(value: &T)
+where
+    T: Debug + DeserializeOwned + PartialEq + Serialize,
+{
+    let data = serde_json::to_value(value).unwrap();
+    assert_eq!(&serde_json::from_value::<T>(data).unwrap(), value);
+}
@@ -1,15 +1,16 @@
 use crate::types::{
     callback_query::CallbackQuery,
     inline_mode::{ChosenInlineResult, InlineQuery},
-    message::Message,
+    message::{Message, TextParseMode},
     payments::{PreCheckoutQuery, ShippingQuery},
     primitive::Integer,
     user::User,
 };
-use serde::{de::Error, Deserialize, Deserializer, Serialize};
+use serde::{ser::SerializeMap, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
 
 /// Incoming update
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Update {
     /// The update‘s unique identifier
     ///
@@ -24,6 +25,21 @@ pub struct Update {
 }
 
 impl Update {
+    /// Parses an update and returns it along with its raw JSON
+    pub fn from_raw_json(raw: Value) -> Result<(Update, Value), serde_json::Error> {
+        let update = serde_json::from_value(raw.clone())?;
+        Ok((update, raw))
+    }
+
+    /// Parses an update using given mode of parsing text entities
+    ///
+    /// Returns the update along with warnings about malformed entities dropped or clamped in lenient mode
+    pub fn parse(mut raw: Value, mode: TextParseMode) -> Result<(Update, Vec<String>), serde_json::Error> {
+        let warnings = mode.prepare(&mut raw);
+        let update = serde_json::from_value(raw)?;
+        Ok((update, warnings))
+    }
+
     /// Returns a chat ID from update
     pub fn get_chat_id(&self) -> Option<Integer> {
         match self.kind {
@@ -58,12 +74,13 @@ impl Update {
             UpdateKind::CallbackQuery(ref query) => &query.from,
             UpdateKind::ShippingQuery(ref query) => &query.from,
             UpdateKind::PreCheckoutQuery(ref query) => &query.from,
+            UpdateKind::Unknown(_) => return None,
         })
     }
 }
 
 /// Kind of update
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 #[allow(clippy::large_enum_variant)]
 pub enum UpdateKind {
     /// New incoming message of any kind — text, photo, sticker, etc
@@ -87,6 +104,10 @@ pub enum UpdateKind {
     ShippingQuery(ShippingQuery),
     /// New incoming pre-checkout query. Contains full information about checkout
     PreCheckoutQuery(PreCheckoutQuery),
+    /// Update of unknown kind
+    ///
+    /// Contains all fields of the update except update_id
+    Unknown(Value),
 }
 
 impl<'de> Deserialize<'de> for Update {
@@ -116,14 +137,43 @@ impl<'de> Deserialize<'de> for Update {
             } else if let Some(data) = raw.pre_checkout_query {
                 UpdateKind::PreCheckoutQuery(data)
             } else {
-                return Err(D::Error::custom("Can not detect update kind"));
+                UpdateKind::Unknown(Value::Object(raw.unknown))
             },
         })
     }
 }
 
+impl Serialize for Update {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("update_id", &self.id)?;
+        match self.kind {
+            UpdateKind::Message(ref data) => map.serialize_entry("message", data)?,
+            UpdateKind::EditedMessage(ref data) => map.serialize_entry("edited_message", data)?,
+            UpdateKind::ChannelPost(ref data) => map.serialize_entry("channel_post", data)?,
+            UpdateKind::EditedChannelPost(ref data) => map.serialize_entry("edited_channel_post", data)?,
+            UpdateKind::InlineQuery(ref data) => map.serialize_entry("inline_query", data)?,
+            UpdateKind::ChosenInlineResult(ref data) => map.serialize_entry("chosen_inline_result", data)?,
+            UpdateKind::CallbackQuery(ref data) => map.serialize_entry("callback_query", data)?,
+            UpdateKind::ShippingQuery(ref data) => map.serialize_entry("shipping_query", data)?,
+            UpdateKind::PreCheckoutQuery(ref data) => map.serialize_entry("pre_checkout_query", data)?,
+            UpdateKind::Unknown(ref data) => {
+                if let Value::Object(ref fields) = data {
+                    for (key, value) in fields {
+                        map.serialize_entry(key, value)?;
+                    }
+                }
+            }
+        }
+        map.end()
+    }
+}
+
 /// Information about the current status of a webhook
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct WebhookInfo {
     /// Webhook URL, may be empty if webhook is not set up
     pub url: String,
@@ -132,10 +182,13 @@ pub struct WebhookInfo {
     /// Number of updates awaiting delivery
     pub pending_update_count: Integer,
     ///  Unix time for the most recent error that happened when trying to deliver an update via webhook
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub last_error_date: Option<Integer>,
     /// Error message in human-readable format for the most recent error that happened when trying to deliver an update via webhook
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub last_error_message: Option<String>,
     /// Maximum allowed number of simultaneous HTTPS connections to the webhook for update delivery
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub max_connections: Option<Integer>,
     /// A list of update types the bot is subscribed to
     /// Defaults to all update types
@@ -174,7 +227,7 @@ pub enum AllowedUpdate {
     PreCheckoutQuery,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug)]
 struct RawUpdate {
     update_id: Integer,
     message: Option<Message>,
@@ -186,8 +239,24 @@ struct RawUpdate {
     callback_query: Option<CallbackQuery>,
     shipping_query: Option<ShippingQuery>,
     pre_checkout_query: Option<PreCheckoutQuery>,
+    unknown: Map<String, Value>,
 }
 
+deserialize_with_unknown!(RawUpdate {
+    required: [update_id],
+    optional: [
+        message,
+        edited_message,
+        channel_post,
+        edited_channel_post,
+        inline_query,
+        chosen_inline_result,
+        callback_query,
+        shipping_query,
+        pre_checkout_query
+    ]
+});
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -213,7 +282,8 @@ mod tests {
                 "text": "test"
             }
         });
-        let update: Update = serde_json::from_value(input).unwrap();
+        let update: Update = serde_json::from_value(input.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&update).unwrap(), input);
         assert_eq!(update.get_chat_id(), Some(1));
         assert_eq!(update.get_user().map(|u| u.id), Some(1));
         if let Update {
@@ -227,4 +297,55 @@ mod tests {
             panic!("Unexpected update {:?}", update);
         }
     }
+
+    #[test]
+    fn test_deserialize_unknown() {
+        let input = json!({
+            "update_id": 1,
+            "poll": {
+                "id": "poll-id"
+            }
+        });
+        let (update, raw) = Update::from_raw_json(input.clone()).unwrap();
+        assert_eq!(update.id, 1);
+        assert_eq!(raw, input);
+        assert_eq!(update.get_chat_id(), None);
+        assert!(update.get_user().is_none());
+        assert_eq!(
+            update.kind,
+            UpdateKind::Unknown(json!({
+                "poll": {
+                    "id": "poll-id"
+                }
+            }))
+        );
+        assert_eq!(serde_json::to_value(&update).unwrap(), input);
+        assert!(serde_json::from_value::<Update>(json!({"poll": {"id": "poll-id"}})).is_err());
+    }
+
+    #[test]
+    fn test_parse_lenient() {
+        let input = json!({
+            "update_id": 1,
+            "message": {
+                "message_id": 1,
+                "date": 0,
+                "from": {"id": 1, "is_bot": false, "first_name": "test"},
+                "chat": {"id": 1, "type": "private", "first_name": "test"},
+                "text": "test",
+                "entities": [{"type": "bold", "offset": 5, "length": 1}]
+            }
+        });
+        assert!(Update::parse(input.clone(), TextParseMode::Strict).is_err());
+        let (update, warnings) = Update::parse(input, TextParseMode::Lenient).unwrap();
+        assert_eq!(update.id, 1);
+        assert_eq!(
+            warnings,
+            vec![String::from("Entity is dropped: Offset \"5\" is out of text bounds")]
+        );
+        match update.kind {
+            UpdateKind::Message(ref msg) => assert_eq!(msg.get_text().unwrap().get_warnings(), warnings.as_slice()),
+            ref kind => panic!("Unexpected update kind: {:?}", kind),
+        }
+    }
 }
@@ -1,8 +1,8 @@
 use crate::types::{photo_size::PhotoSize, primitive::Integer};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// Telegram user or bot
-#[derive(Clone, Debug, Deserialize, PartialEq, PartialOrd)]
+#[derive(Clone, Debug, Deserialize, PartialEq, PartialOrd, Serialize)]
 pub struct User {
     /// Unique identifier for this user or bot
     pub id: Integer,
@@ -11,15 +11,28 @@ pub struct User {
     /// User‘s or bot’s first name
     pub first_name: String,
     /// User‘s or bot’s last name
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub last_name: Option<String>,
     /// User‘s or bot’s username
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub username: Option<String>,
     /// IETF language tag of the user's language
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub language_code: Option<String>,
 }
 
+impl User {
+    /// Returns first and last name of the user
+    pub fn get_full_name(&self) -> String {
+        match self.last_name {
+            Some(ref last_name) => format!("{} {}", self.first_name, last_name),
+            None => self.first_name.clone(),
+        }
+    }
+}
+
 /// User's profile pictures
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct UserProfilePhotos {
     /// Total number of profile pictures the target user has
     pub total_count: Integer,
@@ -0,0 +1,255 @@
+use crate::types::{
+    message::{Text, MAX_CAPTION_LENGTH},
+    primitive::{Float, Integer, ParseMode},
+    stickers::MaskPosition,
+};
+
+/// An error when a parameter of a method is not valid
+#[derive(Debug, failure::Fail)]
+#[fail(display = "Invalid parameter \"{}\": {}", parameter, reason)]
+pub struct ValidationError {
+    /// Name of the parameter
+    pub parameter: &'static str,
+    /// Why the value is not valid
+    pub reason: String,
+}
+
+impl ValidationError {
+    pub(crate) fn new<S: Into<String>>(parameter: &'static str, reason: S) -> Self {
+        ValidationError {
+            parameter,
+            reason: reason.into(),
+        }
+    }
+}
+
+/// Checks length of a text in characters
+///
+/// When parse mode is set, length of the text without markup is checked
+/// Markdown is not parsed, so only a lower bound is checked for it
+pub(crate) fn check_length(
+    parameter: &'static str,
+    value: &str,
+    parse_mode: Option<ParseMode>,
+    min: usize,
+    max: usize,
+) -> Result<(), ValidationError> {
+    let len = match parse_mode {
+        None => Some(value.encode_utf16().count()),
+        Some(ParseMode::Html) => Text::from_html(value).ok().map(|text| text.data.encode_utf16().count()),
+        Some(ParseMode::Markdown) => None,
+    };
+    match len {
+        Some(len) if len < min || len > max => Err(ValidationError::new(
+            parameter,
+            format!("length is {} characters, expected {}-{}", len, min, max),
+        )),
+        None if min > 0 && value.is_empty() => Err(ValidationError::new(
+            parameter,
+            format!("text is empty, expected {}-{} characters", min, max),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Checks length of a media caption
+pub(crate) fn check_caption(value: Option<&str>, parse_mode: Option<ParseMode>) -> Result<(), ValidationError> {
+    match value {
+        Some(value) => check_length("caption", value, parse_mode, 0, MAX_CAPTION_LENGTH),
+        None => Ok(()),
+    }
+}
+
+/// Checks length of a text in bytes
+pub(crate) fn check_bytes(parameter: &'static str, value: &str, min: usize, max: usize) -> Result<(), ValidationError> {
+    let len = value.len();
+    if len < min || len > max {
+        Err(ValidationError::new(
+            parameter,
+            format!("length is {} bytes, expected {}-{}", len, min, max),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Checks number of items
+pub(crate) fn check_count(
+    parameter: &'static str,
+    count: usize,
+    min: usize,
+    max: usize,
+) -> Result<(), ValidationError> {
+    if count < min || count > max {
+        Err(ValidationError::new(
+            parameter,
+            format!("contains {} items, expected {}-{}", count, min, max),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Checks that a number is in range
+pub(crate) fn check_range(
+    parameter: &'static str,
+    value: Integer,
+    min: Integer,
+    max: Integer,
+) -> Result<(), ValidationError> {
+    if value < min || value > max {
+        Err(ValidationError::new(
+            parameter,
+            format!("value is {}, expected {}-{}", value, min, max),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Checks that a string consists of one or more emoji
+pub(crate) fn check_emojis(parameter: &'static str, value: &str) -> Result<(), ValidationError> {
+    if value.is_empty() {
+        return Err(ValidationError::new(parameter, "expected one or more emoji"));
+    }
+    let mut prev = None;
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        let valid = if is_emoji_component(c) {
+            prev.is_some()
+        } else if is_keycap_base(c) {
+            chars.peek().cloned().is_some_and(is_emoji_component)
+        } else {
+            is_emoji(c)
+        };
+        if !valid {
+            return Err(ValidationError::new(parameter, format!("{:?} is not an emoji", c)));
+        }
+        prev = Some(c);
+    }
+    Ok(())
+}
+
+fn is_emoji(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x00A9
+            | 0x00AE
+            | 0x203C
+            | 0x2049
+            | 0x2122
+            | 0x2139
+            | 0x2194..=0x21AA
+            | 0x231A..=0x23FF
+            | 0x24C2
+            | 0x25AA..=0x25FE
+            | 0x2600..=0x27BF
+            | 0x2934
+            | 0x2935
+            | 0x2B05..=0x2B55
+            | 0x3030
+            | 0x303D
+            | 0x3297
+            | 0x3299
+            | 0x1F000..=0x1FAFF
+    )
+}
+
+/// Zero width joiner, variation selector, combining keycap and tags
+fn is_emoji_component(c: char) -> bool {
+    matches!(c as u32, 0x200D | 0xFE0F | 0x20E3 | 0xE0020..=0xE007F)
+}
+
+fn is_keycap_base(c: char) -> bool {
+    c.is_ascii_digit() || c == '#' || c == '*'
+}
+
+/// Checks that shifts of a mask position are finite and scale is positive
+pub(crate) fn check_mask_position(parameter: &'static str, value: &MaskPosition) -> Result<(), ValidationError> {
+    let check = |name: &str, value: Float, is_valid: bool, expected: &str| {
+        if value.is_finite() && is_valid {
+            Ok(())
+        } else {
+            Err(ValidationError::new(
+                parameter,
+                format!("{} is {}, expected {}", name, value, expected),
+            ))
+        }
+    };
+    check("x_shift", value.x_shift, true, "a finite number")?;
+    check("y_shift", value.y_shift, true, "a finite number")?;
+    check("scale", value.scale, value.scale > 0.0, "a positive number")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::MaskPositionPoint;
+
+    #[test]
+    fn test_check_length() {
+        assert!(check_length("text", "😀", None, 1, 2).is_ok());
+        assert!(check_length("text", "😀😀", None, 1, 2).is_err());
+        assert!(check_length("text", "", None, 1, 2).is_err());
+        assert!(check_length("text", "<b>ab</b>", Some(ParseMode::Html), 1, 2).is_ok());
+        assert!(check_length("text", "<b>abc</b>", Some(ParseMode::Html), 1, 2).is_err());
+        assert!(check_length("text", "*abcdef*", Some(ParseMode::Markdown), 1, 2).is_ok());
+        assert!(check_length("text", "", Some(ParseMode::Markdown), 1, 2).is_err());
+        assert_eq!(
+            check_length("caption", "abc", None, 0, 2).unwrap_err().to_string(),
+            "Invalid parameter \"caption\": length is 3 characters, expected 0-2"
+        );
+    }
+
+    #[test]
+    fn test_check_bytes_count_range() {
+        assert!(check_bytes("data", "ab", 1, 2).is_ok());
+        assert!(check_bytes("data", "ы", 1, 1).is_err());
+        assert!(check_count("media", 2, 2, 10).is_ok());
+        assert!(check_count("media", 11, 2, 10).is_err());
+        assert!(check_range("limit", 100, 1, 100).is_ok());
+        assert!(check_range("limit", 0, 1, 100).is_err());
+    }
+
+    #[test]
+    fn test_check_emojis_mask_position() {
+        for value in &["😀", "😀😎", "👍🏽", "👨‍👩‍👧", "❤️", "1️⃣", "#⃣", "🇺🇸", "©"]
+        {
+            assert!(check_emojis("emojis", value).is_ok(), "{}", value);
+        }
+        for value in &["", "a", "😀a", "1", "\u{200d}😀", "😀 "] {
+            assert!(check_emojis("emojis", value).is_err(), "{}", value);
+        }
+        assert_eq!(
+            check_emojis("emojis", "x").unwrap_err().to_string(),
+            "Invalid parameter \"emojis\": 'x' is not an emoji"
+        );
+
+        let mut position = MaskPosition {
+            point: MaskPositionPoint::Eyes,
+            x_shift: -1.0,
+            y_shift: 0.5,
+            scale: 2.0,
+        };
+        assert!(check_mask_position("mask_position", &position).is_ok());
+        position.scale = 0.0;
+        assert!(check_mask_position("mask_position", &position).is_err());
+        position.scale = 1.0;
+        position.y_shift = Float::NAN;
+        assert!(check_mask_position("mask_position", &position).is_err());
+        position.y_shift = 1.5;
+        position.scale = 3.0;
+        assert!(check_mask_position("mask_position", &position).is_ok());
+        position.x_shift = Float::INFINITY;
+        assert_eq!(
+            check_mask_position("mask_position", &position).unwrap_err().to_string(),
+            "Invalid parameter \"mask_position\": x_shift is inf, expected a finite number"
+        );
+        position.x_shift = 0.0;
+        position.scale = -1.0;
+        assert_eq!(
+            check_mask_position("mask_position", &position).unwrap_err().to_string(),
+            "Invalid parameter \"mask_position\": scale is -1, expected a positive number"
+        );
+    }
+}
@@ -1,8 +1,8 @@
 use crate::types::location::Location;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// Venue
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Venue {
     /// Venue location
     pub location: Location,
@@ -11,9 +11,11 @@ pub struct Venue {
     /// Address of the venue
     pub address: String,
     /// Foursquare identifier of the venue
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub foursquare_id: Option<String>,
     /// Foursquare type of the venue
     /// For example: “arts_entertainment/default”,
     /// “arts_entertainment/aquarium” or “food/icecream”
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub foursquare_type: Option<String>,
 }
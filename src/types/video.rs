@@ -1,8 +1,8 @@
 use crate::types::{photo_size::PhotoSize, primitive::Integer};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// Video file
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Video {
     /// Unique identifier for this file
     pub file_id: String,
@@ -13,9 +13,12 @@ pub struct Video {
     /// Duration of the video in seconds as defined by sender
     pub duration: Integer,
     /// Video thumbnail
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub thumb: Option<PhotoSize>,
     /// Mime type of a file as defined by sender
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub mime_type: Option<String>,
     /// File size
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub file_size: Option<Integer>,
 }
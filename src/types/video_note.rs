@@ -1,8 +1,8 @@
 use crate::types::{photo_size::PhotoSize, primitive::Integer};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// Video message
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct VideoNote {
     /// Unique identifier for this file
     pub file_id: String,
@@ -11,7 +11,9 @@ pub struct VideoNote {
     ///  Duration of the video in seconds
     pub duration: Integer,
     /// Video thumbnail
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub thumb: Option<PhotoSize>,
     /// File size
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub file_size: Option<Integer>,
 }
@@ -0,0 +1,457 @@
+use crate::{
+    api::{Api, ApiFuture},
+    methods::{AnswerCallbackQuery, EditMessageReplyMarkup},
+    types::{check_bytes, CallbackQuery, InlineKeyboardButton, InlineKeyboardMarkup, ValidationError},
+};
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Weekday};
+use futures::Future;
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+// Callback data is limited to 64 bytes, the longest data after the prefix is "t:YYYY-MM-DD HH:MM"
+const MAX_PREFIX_LENGTH: usize = 64 - 18;
+const HOURS_COLUMNS: usize = 6;
+const MINUTES_COLUMNS: usize = 4;
+
+/// Names of months and weekdays used by calendar
+#[derive(Clone, Debug)]
+pub struct CalendarLocale {
+    month_names: Vec<String>,
+    weekday_names: Vec<String>,
+}
+
+impl CalendarLocale {
+    /// Creates a new locale
+    ///
+    /// # Arguments
+    ///
+    /// * month_names - Names of months starting from January
+    /// * weekday_names - Short names of weekdays starting from Monday
+    pub fn new(month_names: [&str; 12], weekday_names: [&str; 7]) -> Self {
+        CalendarLocale {
+            month_names: month_names.iter().map(|name| name.to_string()).collect(),
+            weekday_names: weekday_names.iter().map(|name| name.to_string()).collect(),
+        }
+    }
+}
+
+impl Default for CalendarLocale {
+    fn default() -> Self {
+        CalendarLocale::new(
+            [
+                "January",
+                "February",
+                "March",
+                "April",
+                "May",
+                "June",
+                "July",
+                "August",
+                "September",
+                "October",
+                "November",
+                "December",
+            ],
+            ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"],
+        )
+    }
+}
+
+/// A value selected in calendar
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CalendarValue {
+    /// Date selected in a calendar without time
+    Date(NaiveDate),
+    /// Date and time selected in a calendar with time
+    DateTime(NaiveDateTime),
+}
+
+/// Result of handling a callback data of calendar
+#[derive(Clone, Debug)]
+pub enum CalendarAction {
+    /// Keyboard of the message must be replaced
+    Navigate(InlineKeyboardMarkup),
+    /// A value is selected
+    Select(CalendarValue),
+    /// Button has no action
+    Ignore,
+}
+
+/// Inline calendar with optional time picker
+///
+/// Callback data of calendar buttons starts with the prefix
+/// Pass incoming callback queries to handle_callback_query(),
+/// navigation is handled by editing the message and the selected value is returned
+#[derive(Clone, Debug)]
+pub struct Calendar {
+    prefix: String,
+    min_date: Option<NaiveDate>,
+    max_date: Option<NaiveDate>,
+    locale: CalendarLocale,
+    week_start: Weekday,
+    minute_step: Option<u32>,
+}
+
+impl Calendar {
+    /// Creates a new calendar
+    ///
+    /// # Arguments
+    ///
+    /// * prefix - Prefix of callback data, must be unique within the bot, up to 46 bytes
+    pub fn new<S: Into<String>>(prefix: S) -> Result<Self, ValidationError> {
+        let prefix = prefix.into();
+        check_bytes("prefix", &prefix, 0, MAX_PREFIX_LENGTH)?;
+        Ok(Calendar {
+            prefix,
+            min_date: None,
+            max_date: None,
+            locale: CalendarLocale::default(),
+            week_start: Weekday::Mon,
+            minute_step: None,
+        })
+    }
+
+    /// Minimum date available for selection
+    pub fn min_date(mut self, min_date: NaiveDate) -> Self {
+        self.min_date = Some(min_date);
+        self
+    }
+
+    /// Maximum date available for selection
+    pub fn max_date(mut self, max_date: NaiveDate) -> Self {
+        self.max_date = Some(max_date);
+        self
+    }
+
+    /// Names of months and weekdays, English by default
+    pub fn locale(mut self, locale: CalendarLocale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// First day of a week, Monday by default
+    pub fn week_start(mut self, week_start: Weekday) -> Self {
+        self.week_start = week_start;
+        self
+    }
+
+    /// Asks for time after a date is selected
+    ///
+    /// # Arguments
+    ///
+    /// * minute_step - Step between minutes available for selection, 1-60
+    pub fn with_time(mut self, minute_step: u32) -> Self {
+        self.minute_step = Some(minute_step.clamp(1, 60));
+        self
+    }
+
+    /// Returns a keyboard for the month containing given date
+    pub fn get_keyboard(&self, date: NaiveDate) -> InlineKeyboardMarkup {
+        let first_day = date.with_day(1).unwrap_or(date);
+        let prev_month = first_day.pred_opt();
+        let next_month = add_month(first_day);
+        let mut markup = InlineKeyboardMarkup::default().row(vec![
+            match prev_month {
+                Some(day) if self.is_month_available(day) => {
+                    self.get_button("‹", &format!("n:{}", day.format("%Y-%m")))
+                }
+                _ => self.get_ignore_button(" "),
+            },
+            self.get_ignore_button(format!(
+                "{} {}",
+                self.locale.month_names[first_day.month0() as usize],
+                first_day.year()
+            )),
+            match next_month {
+                Some(day) if self.is_month_available(day) => {
+                    self.get_button("›", &format!("n:{}", day.format("%Y-%m")))
+                }
+                _ => self.get_ignore_button(" "),
+            },
+        ]);
+        let start = self.week_start.num_days_from_monday() as usize;
+        markup = markup.row(
+            (0..7)
+                .map(|idx| self.get_ignore_button(self.locale.weekday_names[(start + idx) % 7].clone()))
+                .collect(),
+        );
+        let offset = (first_day.weekday().num_days_from_monday() as usize + 7 - start) % 7;
+        let mut cells: Vec<InlineKeyboardButton> = (0..offset).map(|_| self.get_ignore_button(" ")).collect();
+        let mut day = Some(first_day);
+        while let Some(current) = day.filter(|day| day.month() == first_day.month()) {
+            cells.push(if self.is_available(current) {
+                self.get_button(
+                    &current.day().to_string(),
+                    &format!("d:{}", current.format(DATE_FORMAT)),
+                )
+            } else {
+                self.get_ignore_button(current.day().to_string())
+            });
+            day = current.succ_opt();
+        }
+        while !cells.len().is_multiple_of(7) {
+            cells.push(self.get_ignore_button(" "));
+        }
+        markup.rows(cells, 7)
+    }
+
+    /// Handles callback data
+    ///
+    /// Returns None if data does not belong to calendar
+    pub fn handle(&self, data: &str) -> Option<CalendarAction> {
+        let data = data.strip_prefix(self.prefix.as_str())?;
+        if data == "i" {
+            return Some(CalendarAction::Ignore);
+        }
+        let (action, value) = (data.get(..2)?, data.get(2..)?);
+        Some(match action {
+            "n:" => {
+                let date = NaiveDate::parse_from_str(&format!("{}-01", value), DATE_FORMAT)
+                    .ok()
+                    .filter(|date| self.is_month_available(*date))?;
+                CalendarAction::Navigate(self.get_keyboard(date))
+            }
+            "d:" => {
+                let date = self.parse_date(value)?;
+                if self.minute_step.is_some() {
+                    CalendarAction::Navigate(self.get_hours_keyboard(date))
+                } else {
+                    CalendarAction::Select(CalendarValue::Date(date))
+                }
+            }
+            "h:" => {
+                let (date, hour) = self.parse_time(value)?;
+                CalendarAction::Navigate(self.get_minutes_keyboard(date, hour))
+            }
+            "t:" => {
+                let step = self.minute_step?;
+                let (date, time) = value.split_at(value.find(' ')?);
+                let date = self.parse_date(date)?;
+                let time = NaiveTime::parse_from_str(time.trim(), "%H:%M")
+                    .ok()
+                    .filter(|time| time.minute() % step == 0)?;
+                CalendarAction::Select(CalendarValue::DateTime(date.and_time(time)))
+            }
+            _ => return None,
+        })
+    }
+
+    /// Handles a callback query
+    ///
+    /// Returns None if query does not belong to calendar
+    /// Otherwise answers the query, edits the message for navigation buttons
+    /// and resolves to the selected value if any
+    pub fn handle_callback_query(&self, api: &Api, query: &CallbackQuery) -> Option<ApiFuture<Option<CalendarValue>>> {
+        let action = self.handle(query.data.as_ref()?)?;
+        let answer = api.execute(&AnswerCallbackQuery::new(query.id.clone()));
+        Some(match action {
+            CalendarAction::Navigate(markup) => {
+                let method = match (&query.message, &query.inline_message_id) {
+                    (Some(message), _) => EditMessageReplyMarkup::new(message.get_chat_id(), message.id),
+                    (None, Some(inline_message_id)) => {
+                        EditMessageReplyMarkup::with_inline_message_id(inline_message_id.clone())
+                    }
+                    (None, None) => return Some(ApiFuture::new(answer.map(|_| None))),
+                };
+                let edit = api.execute(&method.reply_markup(markup));
+                ApiFuture::new(answer.join(edit).map(|_| None))
+            }
+            CalendarAction::Select(value) => ApiFuture::new(answer.map(move |_| Some(value))),
+            CalendarAction::Ignore => ApiFuture::new(answer.map(|_| None)),
+        })
+    }
+
+    fn get_hours_keyboard(&self, date: NaiveDate) -> InlineKeyboardMarkup {
+        let month = date.format("%Y-%m");
+        let date = date.format(DATE_FORMAT);
+        let hours = (0..24).map(|hour| self.get_button(&format!("{:02}", hour), &format!("h:{} {:02}", date, hour)));
+        InlineKeyboardMarkup::default()
+            .row(vec![
+                self.get_button("‹", &format!("n:{}", month)),
+                self.get_ignore_button(date.to_string()),
+            ])
+            .rows(hours, HOURS_COLUMNS)
+    }
+
+    fn get_minutes_keyboard(&self, date: NaiveDate, hour: u32) -> InlineKeyboardMarkup {
+        let step = self.minute_step.unwrap_or(60) as usize;
+        let date = date.format(DATE_FORMAT);
+        let minutes = (0..60).step_by(step).map(|minute| {
+            self.get_button(
+                &format!("{:02}:{:02}", hour, minute),
+                &format!("t:{} {:02}:{:02}", date, hour, minute),
+            )
+        });
+        InlineKeyboardMarkup::default()
+            .row(vec![
+                self.get_button("‹", &format!("d:{}", date)),
+                self.get_ignore_button(date.to_string()),
+            ])
+            .rows(minutes, MINUTES_COLUMNS)
+    }
+
+    fn parse_date(&self, value: &str) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(value, DATE_FORMAT)
+            .ok()
+            .filter(|date| self.is_available(*date))
+    }
+
+    fn parse_time(&self, value: &str) -> Option<(NaiveDate, u32)> {
+        let (date, hour) = value.split_at(value.find(' ')?);
+        let hour = hour.trim().parse().ok().filter(|hour| *hour < 24)?;
+        Some((self.parse_date(date)?, hour))
+    }
+
+    /// Whether any date of the month containing given date is in range
+    fn is_month_available(&self, date: NaiveDate) -> bool {
+        let first_day = date.with_day(1).unwrap_or(date);
+        let last_day = add_month(first_day).and_then(|day| day.pred_opt()).unwrap_or(date);
+        self.min_date.is_none_or(|min_date| last_day >= min_date)
+            && self.max_date.is_none_or(|max_date| first_day <= max_date)
+    }
+
+    fn is_available(&self, date: NaiveDate) -> bool {
+        self.min_date.is_none_or(|min_date| date >= min_date) && self.max_date.is_none_or(|max_date| date <= max_date)
+    }
+
+    fn get_button(&self, text: &str, data: &str) -> InlineKeyboardButton {
+        InlineKeyboardButton::with_callback_data(text.to_string(), format!("{}{}", self.prefix, data))
+    }
+
+    fn get_ignore_button<S: Into<String>>(&self, text: S) -> InlineKeyboardButton {
+        InlineKeyboardButton::with_callback_data(text.into(), format!("{}i", self.prefix))
+    }
+}
+
+fn add_month(date: NaiveDate) -> Option<NaiveDate> {
+    if date.month() == 12 {
+        NaiveDate::from_ymd_opt(date.year() + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(date.year(), date.month() + 1, 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_rows(markup: &InlineKeyboardMarkup) -> Vec<String> {
+        let data = serde_json::to_value(markup).unwrap();
+        data["inline_keyboard"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|row| {
+                row.as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|button| {
+                        let data = button["callback_data"].as_str().unwrap();
+                        if data == "c:i" {
+                            format!("[{}]", button["text"].as_str().unwrap())
+                        } else {
+                            format!("{}={}", button["text"].as_str().unwrap(), data)
+                        }
+                    })
+                    .collect::<Vec<String>>()
+                    .join(" ")
+            })
+            .collect()
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_month_keyboard() {
+        let calendar = Calendar::new("c:")
+            .unwrap()
+            .min_date(date(2019, 1, 30))
+            .max_date(date(2019, 2, 2))
+            .week_start(Weekday::Sun)
+            .locale(CalendarLocale::new(
+                [
+                    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+                ],
+                ["M", "T", "W", "T", "F", "S", "S"],
+            ));
+        let rows = get_rows(&calendar.get_keyboard(date(2019, 2, 15)));
+        assert_eq!(rows.len(), 7);
+        assert_eq!(rows[0], "‹=c:n:2019-01 [Feb 2019] [ ]");
+        assert_eq!(rows[1], "[S] [M] [T] [W] [T] [F] [S]");
+        assert_eq!(rows[2], "[ ] [ ] [ ] [ ] [ ] 1=c:d:2019-02-01 2=c:d:2019-02-02");
+        assert_eq!(rows[3], "[3] [4] [5] [6] [7] [8] [9]");
+        assert_eq!(rows[6], "[24] [25] [26] [27] [28] [ ] [ ]");
+
+        let rows = get_rows(&Calendar::new("c:").unwrap().get_keyboard(date(2018, 12, 1)));
+        assert_eq!(rows[0], "‹=c:n:2018-11 [December 2018] ›=c:n:2019-01");
+        assert_eq!(rows[1], "[Mo] [Tu] [We] [Th] [Fr] [Sa] [Su]");
+        assert_eq!(rows[2], "[ ] [ ] [ ] [ ] [ ] 1=c:d:2018-12-01 2=c:d:2018-12-02");
+    }
+
+    #[test]
+    fn test_prefix_length() {
+        let prefix = "p".repeat(46);
+        let calendar = Calendar::new(prefix.as_str()).unwrap().with_time(1);
+        match calendar.handle(&format!("{}d:2019-12-31", prefix)) {
+            Some(CalendarAction::Navigate(markup)) => assert!(markup.validate().is_ok()),
+            action => panic!("Unexpected action: {:?}", action),
+        }
+        assert!(calendar.get_keyboard(date(2019, 12, 31)).validate().is_ok());
+        assert!(Calendar::new("p".repeat(47)).is_err());
+    }
+
+    #[test]
+    fn test_handle() {
+        let calendar = Calendar::new("c:").unwrap().min_date(date(2019, 1, 1));
+        assert!(calendar.handle("x:i").is_none());
+        assert!(calendar.handle("c:x:1").is_none());
+        assert!(calendar.handle("c:d:2018-12-31").is_none());
+        assert!(calendar.handle("c:n:2018-12").is_none());
+        assert!(calendar.handle("c:t:2019-03-08 13:20").is_none());
+        match calendar.handle("c:i") {
+            Some(CalendarAction::Ignore) => {}
+            action => panic!("Unexpected action: {:?}", action),
+        }
+        match calendar.handle("c:n:2019-03") {
+            Some(CalendarAction::Navigate(markup)) => {
+                assert_eq!(get_rows(&markup)[0], "‹=c:n:2019-02 [March 2019] ›=c:n:2019-04")
+            }
+            action => panic!("Unexpected action: {:?}", action),
+        }
+        match calendar.handle("c:d:2019-03-08") {
+            Some(CalendarAction::Select(value)) => assert_eq!(value, CalendarValue::Date(date(2019, 3, 8))),
+            action => panic!("Unexpected action: {:?}", action),
+        }
+
+        let calendar = calendar.with_time(20);
+        match calendar.handle("c:d:2019-03-08") {
+            Some(CalendarAction::Navigate(markup)) => {
+                let rows = get_rows(&markup);
+                assert_eq!(rows.len(), 5);
+                assert_eq!(rows[0], "‹=c:n:2019-03 [2019-03-08]");
+                assert!(rows[1].starts_with("00=c:h:2019-03-08 00 01=c:h:2019-03-08 01"));
+            }
+            action => panic!("Unexpected action: {:?}", action),
+        }
+        match calendar.handle("c:h:2019-03-08 13") {
+            Some(CalendarAction::Navigate(markup)) => assert_eq!(
+                get_rows(&markup),
+                vec![
+                    "‹=c:d:2019-03-08 [2019-03-08]",
+                    "13:00=c:t:2019-03-08 13:00 13:20=c:t:2019-03-08 13:20 13:40=c:t:2019-03-08 13:40"
+                ]
+            ),
+            action => panic!("Unexpected action: {:?}", action),
+        }
+        assert!(calendar.handle("c:h:2019-03-08 24").is_none());
+        assert!(calendar.handle("c:t:2019-03-08 13:10").is_none());
+        match calendar.handle("c:t:2019-03-08 13:20") {
+            Some(CalendarAction::Select(value)) => assert_eq!(
+                value,
+                CalendarValue::DateTime(date(2019, 3, 8).and_hms_opt(13, 20, 0).unwrap())
+            ),
+            action => panic!("Unexpected action: {:?}", action),
+        }
+    }
+}